@@ -0,0 +1,311 @@
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Scheme, host and optional path prefix, e.g. `https://api.bank.co.id`.
+    /// The prefix is part of the signed `EndpointUrl`.
+    pub base_url: String,
+    /// Sent as `X-CLIENT-KEY` when requesting the access token
+    pub client_key: String,
+    /// Sent as `X-PARTNER-ID`, usually the same as the client key
+    pub partner_id: String,
+    /// Sent as `CHANNEL-ID`
+    pub channel_id: String,
+    pub token_path: String,
+    /// How long before its expiry a cached token is replaced
+    pub token_refresh_margin: std::time::Duration,
+    pub timeout: std::time::Duration,
+    /// Signing and response quirks of the provider
+    pub profile: kamu_snap_response::QuirkProfile,
+    /// Verifies the `X-SIGNATURE` of every response before it is decoded,
+    /// for providers that sign their responses
+    pub response_verifier: Option<kamu_snap_crypto::ResponseVerifier>,
+}
+
+impl Config {
+    pub fn new<B, K, C>(base_url: B, client_key: K, channel_id: C) -> Self
+    where
+        B: Into<String>,
+        K: Into<String>,
+        C: Into<String>,
+    {
+        let client_key = client_key.into();
+
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            partner_id: client_key.clone(),
+            client_key,
+            channel_id: channel_id.into(),
+            token_path: "/v1.0/access-token/b2b".to_owned(),
+            token_refresh_margin: std::time::Duration::from_secs(60),
+            timeout: std::time::Duration::from_secs(30),
+            profile: kamu_snap_response::QuirkProfile::STRICT,
+            response_verifier: None,
+        }
+    }
+}
+
+/// Outbound SNAP client: fetches and caches the B2B access token, signs every
+/// request and decodes the SNAP envelope.
+pub struct Client {
+    http: reqwest::Client,
+    config: Config,
+    signer: kamu_snap_crypto::AsymmetricCryptoSigner,
+    symmetric: kamu_snap_crypto::SymmetricCrypto,
+    token: tokio::sync::Mutex<Option<crate::token::CachedToken>>,
+}
+
+impl Client {
+    pub fn new(
+        config: Config,
+        signer: kamu_snap_crypto::AsymmetricCryptoSigner,
+        symmetric: kamu_snap_crypto::SymmetricCrypto,
+    ) -> crate::Result<Self> {
+        let http = reqwest::Client::builder().timeout(config.timeout).build()?;
+
+        Ok(Self::with_http_client(http, config, signer, symmetric))
+    }
+
+    pub fn with_http_client(
+        http: reqwest::Client,
+        config: Config,
+        signer: kamu_snap_crypto::AsymmetricCryptoSigner,
+        symmetric: kamu_snap_crypto::SymmetricCrypto,
+    ) -> Self {
+        Self {
+            http,
+            config,
+            signer,
+            symmetric,
+            token: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn symmetric(&self) -> &kamu_snap_crypto::SymmetricCrypto {
+        &self.symmetric
+    }
+
+    /// Cached access token, requesting a new one when it is about to expire.
+    /// Concurrent callers wait for a single token request.
+    pub async fn access_token(&self) -> crate::Result<String> {
+        let mut token = self.token.lock().await;
+
+        if let Some(cached) = token.as_ref().filter(|x| x.is_fresh()) {
+            return Ok(cached.access_token.clone());
+        }
+
+        let cached = self.request_access_token().await?;
+        let access_token = cached.access_token.clone();
+        *token = Some(cached);
+
+        Ok(access_token)
+    }
+
+    pub async fn invalidate_access_token(&self) {
+        self.token.lock().await.take();
+    }
+
+    async fn request_access_token(&self) -> crate::Result<crate::token::CachedToken> {
+        let timestamp = kamu_snap_response::header::timestamp_now();
+        let string_to_sign =
+            kamu_snap_crypto::string_to_sign::access_token(&self.config.client_key, &timestamp);
+        let signature = self.signer.sign_as_base64(string_to_sign);
+        let body = serde_json::to_vec(&kamu_snap_model::access_token::Request {
+            grant_type: kamu_snap_model::access_token::GrantType::ClientCredentials,
+            additional_info: None,
+        })
+        .map_err(|x| crate::ClientError::Decode(x.to_string()))?;
+
+        let response = self
+            .http
+            .post(format!("{}{}", self.config.base_url, self.config.token_path))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(kamu_snap_response::header::X_TIMESTAMP, &timestamp)
+            .header(kamu_snap_response::header::X_CLIENT_KEY, &self.config.client_key)
+            .header(kamu_snap_response::header::X_SIGNATURE, signature)
+            .body(body)
+            .send()
+            .await?;
+        let response = self
+            .decode::<kamu_snap_model::access_token::Response>(
+                &reqwest::Method::POST,
+                &self.config.token_path,
+                response,
+            )
+            .await?;
+        let payload = response
+            .into_payload()
+            .ok_or_else(|| crate::ClientError::Decode("missing accessToken".to_owned()))?;
+        let expires_in = payload
+            .expires_in
+            .parse::<u64>()
+            .map(std::time::Duration::from_secs)
+            .map_err(|_| crate::ClientError::Decode("invalid expiresIn".to_owned()))?;
+
+        Ok(crate::token::CachedToken::new(
+            payload.access_token,
+            expires_in,
+            self.config.token_refresh_margin,
+        ))
+    }
+
+    /// Send a signed SNAP request to `path` (relative to the base URL,
+    /// including any query string). A request rejected because of the access
+    /// token drops the cached token, so that the next attempt, with a new
+    /// `X-EXTERNAL-ID`, see [`retry::execute`](crate::retry::execute),
+    /// requests a new one.
+    pub async fn send<B, T>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        external_id: &str,
+        body: Option<&B>,
+    ) -> crate::Result<kamu_snap_response::SNAPResponse<T>>
+    where
+        B: serde::Serialize + ?Sized,
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let body = match body {
+            Some(body) => serde_json::to_vec(body).map_err(|x| crate::ClientError::Decode(x.to_string()))?,
+            None => Vec::new(),
+        };
+        let access_token = self.access_token().await?;
+        let result = self
+            .send_once(method, path, external_id, &access_token, &body)
+            .await;
+
+        if let Err(crate::ClientError::Response(
+            kamu_snap_response::ResponseError::InvalidTokenB2B
+            | kamu_snap_response::ResponseError::TokenNotFoundB2B,
+        )) = &result
+        {
+            self.invalidate_access_token().await;
+        }
+
+        result
+    }
+
+    pub async fn post<B, T>(
+        &self,
+        path: &str,
+        external_id: &str,
+        body: &B,
+    ) -> crate::Result<kamu_snap_response::SNAPResponse<T>>
+    where
+        B: serde::Serialize + ?Sized,
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.send(reqwest::Method::POST, path, external_id, Some(body))
+            .await
+    }
+
+    async fn send_once<T>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        external_id: &str,
+        access_token: &str,
+        body: &[u8],
+    ) -> crate::Result<kamu_snap_response::SNAPResponse<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let timestamp = kamu_snap_response::header::timestamp_now();
+        let string_to_sign = kamu_snap_crypto::string_to_sign::symmetric_with(
+            &self.config.profile,
+            method.as_str(),
+            &crate::relative_url(&self.config.base_url, path),
+            access_token,
+            body,
+            &timestamp,
+        );
+        let signature = self.symmetric.sign(string_to_sign);
+
+        let response = self
+            .http
+            .request(method.clone(), format!("{}{}", self.config.base_url, path))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(reqwest::header::AUTHORIZATION, format!("Bearer {access_token}"))
+            .header(kamu_snap_response::header::X_TIMESTAMP, &timestamp)
+            .header(kamu_snap_response::header::X_SIGNATURE, signature)
+            .header(kamu_snap_response::header::X_PARTNER_ID, &self.config.partner_id)
+            .header(kamu_snap_response::header::X_EXTERNAL_ID, external_id)
+            .header(kamu_snap_response::header::CHANNEL_ID, &self.config.channel_id)
+            .body(body.to_vec())
+            .send()
+            .await?;
+
+        self.decode(&method, path, response).await
+    }
+
+    async fn decode<T>(
+        &self,
+        method: &reqwest::Method,
+        path: &str,
+        response: reqwest::Response,
+    ) -> crate::Result<kamu_snap_response::SNAPResponse<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        decode_response(
+            response,
+            method,
+            &crate::relative_url(&self.config.base_url, path),
+            &self.config.profile,
+            self.config.response_verifier.as_ref(),
+        )
+        .await
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SNAPClient")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Verify the signature of a response to `method` on `path` with `verifier`,
+/// when given, and decode the SNAP envelope as `profile` does, turning error
+/// response codes into [`ClientError::Response`](crate::ClientError::Response).
+pub async fn decode_response<T>(
+    response: reqwest::Response,
+    method: &reqwest::Method,
+    path: &str,
+    profile: &kamu_snap_response::QuirkProfile,
+    verifier: Option<&kamu_snap_crypto::ResponseVerifier>,
+) -> crate::Result<kamu_snap_response::SNAPResponse<T>>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+
+    if let Some(verifier) = verifier {
+        let header = |name: &str| headers.get(name).and_then(|x| x.to_str().ok());
+        verifier
+            .verify(
+                method.as_str(),
+                path,
+                header(kamu_snap_response::header::X_TIMESTAMP),
+                header(kamu_snap_response::header::X_SIGNATURE),
+                &body,
+            )
+            .map_err(crate::ClientError::ResponseSignature)?;
+    }
+
+    let response = match kamu_snap_response::SNAPResponse::<T>::from_slice_with(&body, profile) {
+        Ok(response) => response,
+        Err(_) if !status.is_success() => return Err(crate::ClientError::Status(status.as_u16())),
+        Err(error) => return Err(crate::ClientError::Decode(error.to_string())),
+    };
+
+    match response.get_common().and_then(|x| x.to_error()) {
+        Some(error) => Err(crate::ClientError::Response(error)),
+        None => Ok(response),
+    }
+}
@@ -0,0 +1,47 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Transport error: {0}")]
+    Transport(String),
+    #[error("Request timed out")]
+    Timeout,
+    #[error("Unexpected HTTP status {0}")]
+    Status(u16),
+    #[error("Response could not be decoded: {0}")]
+    Decode(String),
+    #[error("{0}")]
+    Response(kamu_snap_response::ResponseError),
+    /// The response is unsigned or its signature does not verify
+    #[error("Invalid response signature: {0}")]
+    ResponseSignature(kamu_snap_crypto::CryptoError),
+    #[error(transparent)]
+    Crypto(#[from] kamu_snap_crypto::CryptoError),
+    /// The operating system random number generator failed
+    #[error("Random number generator failure: {0}")]
+    Random(String),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        if value.is_timeout() {
+            Error::Timeout
+        } else {
+            Error::Transport(value.to_string())
+        }
+    }
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::Transport(_) => kamu_snap_response::ResponseError::ExternalServerError,
+            Error::Timeout => kamu_snap_response::ResponseError::Timeout,
+            Error::Status(_) => kamu_snap_response::ResponseError::ExternalServerError,
+            Error::Decode(_) => kamu_snap_response::ResponseError::ExternalServerError,
+            Error::Response(x) => x,
+            Error::ResponseSignature(x) => x.into(),
+            Error::Crypto(x) => x.into(),
+            Error::Random(_) => kamu_snap_response::ResponseError::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+pub mod client;
+pub mod error;
+pub mod retry;
+
+mod token;
+
+pub use client::{Client as SNAPClient, Config as SNAPClientConfig, decode_response};
+pub use error::Error as ClientError;
+pub use retry::{Decision as RetryDecision, Policy as RetryPolicy};
+
+pub type Result<T> = core::result::Result<T, ClientError>;
+
+/// The URL after the host of a request to `path` relative to `base_url`,
+/// i.e. with the path prefix of `base_url`, as signed in the string to sign.
+pub fn relative_url(base_url: &str, path: &str) -> String {
+    let authority = base_url.split_once("://").map_or(base_url, |(_, x)| x);
+    let prefix = authority
+        .find('/')
+        .map_or("", |x| &authority[x..])
+        .trim_end_matches('/');
+
+    format!("{prefix}{path}")
+}
+
+/// Random numeric `X-EXTERNAL-ID`. SNAP requires it to be unique per partner
+/// within a day.
+pub fn generate_external_id() -> Result<String> {
+    const LENGTH: usize = 20;
+
+    let mut digits = String::with_capacity(LENGTH);
+    let mut bytes = [0_u8; LENGTH];
+
+    while digits.len() < LENGTH {
+        getrandom::getrandom(&mut bytes).map_err(|x| ClientError::Random(x.to_string()))?;
+        // Bytes of 250 and above would make the lower digits more likely
+        digits.extend(
+            bytes
+                .iter()
+                .filter(|x| **x < 250)
+                .take(LENGTH - digits.len())
+                .map(|x| char::from(b'0' + x % 10)),
+        );
+    }
+
+    Ok(digits)
+}
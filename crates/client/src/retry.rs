@@ -0,0 +1,238 @@
+//! Retry and reconciliation policy for outbound SNAP calls.
+//!
+//! Every attempt is sent with a fresh `X-EXTERNAL-ID` (SNAP rejects a reused
+//! one with `Conflict`) while the body, and so its `partnerReferenceNo`, stays
+//! the same so the provider can detect duplicates. When a money-moving call
+//! ends in an unknown state it must not be retried blindly: the caller is told
+//! to run the status inquiry with the `X-EXTERNAL-ID` of the attempt in doubt
+//! as `originalExternalId`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Read-only calls, e.g. inquiries, safe to send again
+    Idempotent,
+    /// Calls that move money or change state, e.g. transfers and payments
+    NonIdempotent,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    pub initial: std::time::Duration,
+    pub max: std::time::Duration,
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+}
+
+impl Backoff {
+    /// Exponential delay before attempt number `attempt + 1`.
+    pub fn delay(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: std::time::Duration::from_millis(500),
+            max: std::time::Duration::from_secs(10),
+            max_attempts: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ServicePolicy {
+    pub operation: Operation,
+    pub backoff: Backoff,
+}
+
+impl ServicePolicy {
+    pub fn new(operation: Operation) -> Self {
+        Self {
+            operation,
+            backoff: Backoff::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Send the same body again, with a new `X-EXTERNAL-ID`, after the delay
+    Retry(std::time::Duration),
+    /// The outcome is unknown, reconcile through the status inquiry
+    Inquire,
+    /// The failure is permanent
+    Fail,
+}
+
+/// Per service code retry policy. Services without an explicit entry are
+/// treated as non-idempotent.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    default: ServicePolicy,
+    services: std::collections::HashMap<u8, ServicePolicy>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            default: ServicePolicy::new(Operation::NonIdempotent),
+            services: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Policy {
+    /// Policy with the read-only SNAP services (balance, account, history and
+    /// status inquiries) marked as idempotent.
+    pub fn snap_defaults() -> Self {
+        let idempotent = [
+            kamu_snap_model::common::SERVICE_CODE_BALANCE_INQUIRY,
+            kamu_snap_model::history::SERVICE_CODE_LIST,
+            kamu_snap_model::history::SERVICE_CODE_DETAIL,
+            kamu_snap_model::common::SERVICE_CODE_INTERNAL_ACCOUNT_INQUIRY,
+            kamu_snap_model::common::SERVICE_CODE_EXTERNAL_ACCOUNT_INQUIRY,
+            kamu_snap_model::common::SERVICE_CODE_TRANSFER_STATUS_INQUIRY,
+            kamu_snap_model::emoney::SERVICE_CODE_ACCOUNT_INQUIRY,
+            kamu_snap_model::emoney::SERVICE_CODE_TOP_UP_STATUS,
+            kamu_snap_model::qris::SERVICE_CODE_QUERY,
+        ];
+
+        idempotent
+            .into_iter()
+            .fold(Self::default(), |policy, service_code| {
+                policy.with_service(service_code, ServicePolicy::new(Operation::Idempotent))
+            })
+    }
+
+    pub fn with_default(mut self, default: ServicePolicy) -> Self {
+        self.default = default;
+
+        self
+    }
+
+    pub fn with_service(mut self, service_code: u8, policy: ServicePolicy) -> Self {
+        self.services.insert(service_code, policy);
+
+        self
+    }
+
+    pub fn get(&self, service_code: u8) -> &ServicePolicy {
+        self.services.get(&service_code).unwrap_or(&self.default)
+    }
+
+    /// Decide what to do after attempt number `attempt` (starting at 1) of a
+    /// call to `service_code` failed with `error`.
+    pub fn decide(&self, service_code: u8, error: &crate::ClientError, attempt: u32) -> Decision {
+        let policy = self.get(service_code);
+        let retry = || {
+            if attempt < policy.backoff.max_attempts {
+                Decision::Retry(policy.backoff.delay(attempt))
+            } else {
+                Decision::Fail
+            }
+        };
+        let in_doubt = || match policy.operation {
+            Operation::Idempotent => retry(),
+            Operation::NonIdempotent => Decision::Inquire,
+        };
+
+        match error {
+            crate::ClientError::Timeout
+            | crate::ClientError::Transport(_)
+            | crate::ClientError::Decode(_)
+            // Processed by the provider, but the outcome cannot be trusted
+            | crate::ClientError::ResponseSignature(_) => in_doubt(),
+            crate::ClientError::Status(429) => retry(),
+            crate::ClientError::Status(status) if *status >= 500 => in_doubt(),
+            crate::ClientError::Status(_) | crate::ClientError::Crypto(_) | crate::ClientError::Random(_) => {
+                Decision::Fail
+            }
+            crate::ClientError::Response(error) => match error.get_category() {
+                kamu_snap_response::ResponseCategory::Business
+                | kamu_snap_response::ResponseCategory::Message
+                | kamu_snap_response::ResponseCategory::Success => Decision::Fail,
+                kamu_snap_response::ResponseCategory::System => match error {
+                    // Reused X-EXTERNAL-ID, nothing was processed
+                    kamu_snap_response::ResponseError::Conflict
+                    | kamu_snap_response::ResponseError::TooManyRequests
+                    | kamu_snap_response::ResponseError::InvalidTokenB2B
+                    | kamu_snap_response::ResponseError::TokenNotFoundB2B => retry(),
+                    // An earlier attempt went through, fetch its result
+                    kamu_snap_response::ResponseError::DuplicatePartnerReferenceNo => {
+                        match policy.operation {
+                            Operation::Idempotent => Decision::Fail,
+                            Operation::NonIdempotent => Decision::Inquire,
+                        }
+                    }
+                    kamu_snap_response::ResponseError::GeneralError
+                    | kamu_snap_response::ResponseError::InternalServerError
+                    | kamu_snap_response::ResponseError::ExternalServerError
+                    | kamu_snap_response::ResponseError::Timeout => in_doubt(),
+                    _ => Decision::Fail,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Attempt {
+    /// Starting at 1
+    pub number: u32,
+    /// `X-EXTERNAL-ID` to send with this attempt
+    pub external_id: String,
+}
+
+#[derive(Debug)]
+pub enum Outcome<T> {
+    Completed(T),
+    Failed(crate::ClientError),
+    /// Run the status inquiry with `original_external_id` before deciding on
+    /// the transaction.
+    NeedsInquiry {
+        error: crate::ClientError,
+        original_external_id: String,
+    },
+}
+
+/// Run `call` until it succeeds or the policy gives up. `call` must send the
+/// same body on every attempt, using the attempt's `X-EXTERNAL-ID`.
+pub async fn execute<T, F, Fut>(policy: &Policy, service_code: u8, mut call: F) -> Outcome<T>
+where
+    F: FnMut(Attempt) -> Fut,
+    Fut: std::future::Future<Output = crate::Result<T>>,
+{
+    let mut number = 1;
+
+    loop {
+        let external_id = match crate::generate_external_id() {
+            Ok(external_id) => external_id,
+            Err(error) => return Outcome::Failed(error),
+        };
+        let attempt = Attempt {
+            number,
+            external_id: external_id.clone(),
+        };
+
+        let error = match call(attempt).await {
+            Ok(value) => return Outcome::Completed(value),
+            Err(error) => error,
+        };
+
+        match policy.decide(service_code, &error, number) {
+            Decision::Retry(delay) => tokio::time::sleep(delay).await,
+            Decision::Inquire => {
+                return Outcome::NeedsInquiry {
+                    error,
+                    original_external_id: external_id,
+                };
+            }
+            Decision::Fail => return Outcome::Failed(error),
+        }
+
+        number += 1;
+    }
+}
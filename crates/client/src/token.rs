@@ -0,0 +1,22 @@
+#[derive(Debug, Clone)]
+pub(crate) struct CachedToken {
+    pub(crate) access_token: String,
+    pub(crate) refresh_at: std::time::Instant,
+}
+
+impl CachedToken {
+    pub(crate) fn new(
+        access_token: String,
+        expires_in: std::time::Duration,
+        margin: std::time::Duration,
+    ) -> Self {
+        Self {
+            access_token,
+            refresh_at: std::time::Instant::now() + expires_in.saturating_sub(margin),
+        }
+    }
+
+    pub(crate) fn is_fresh(&self) -> bool {
+        std::time::Instant::now() < self.refresh_at
+    }
+}
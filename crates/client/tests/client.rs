@@ -0,0 +1,266 @@
+//! Client token handling, retries and envelope decoding against a local HTTP
+//! stub.
+
+const CLIENT_KEY: &str = "partner-1";
+const CHANNEL_ID: &str = "95221";
+const SECRET: &str = "client-secret";
+const PATH: &str = "/v1.0/balance-inquiry";
+/// Balance inquiry
+const SERVICE_CODE: u8 = 11;
+const TOKEN_PATH: &str = "/v1.0/access-token/b2b";
+
+const TOKEN_1: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2007300","responseMessage":"Successful","accessToken":"token-1","tokenType":"Bearer","expiresIn":"900"}"#,
+);
+const TOKEN_2: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2007300","responseMessage":"Successful","accessToken":"token-2","tokenType":"Bearer","expiresIn":"900"}"#,
+);
+const SUCCESSFUL: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2001100","responseMessage":"Successful","referenceNo":"1"}"#,
+);
+const INVALID_TOKEN: (u16, &str) = (
+    401,
+    r#"{"responseCode":"4011101","responseMessage":"Invalid Token (B2B)"}"#,
+);
+
+#[derive(Debug)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    reference_no: String,
+}
+
+type KeyPair = (
+    kamu_snap_crypto::AsymmetricCryptoSigner,
+    kamu_snap_crypto::AsymmetricCryptoVerifier,
+);
+
+fn key_pair() -> KeyPair {
+    (
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap(),
+        kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap(),
+    )
+}
+
+fn client(stub: &kamu_snap_testing::Stub) -> kamu_snap_client::SNAPClient {
+    kamu_snap_client::SNAPClient::new(
+        kamu_snap_client::SNAPClientConfig::new(&stub.url, CLIENT_KEY, CHANNEL_ID),
+        key_pair().0,
+        kamu_snap_crypto::SymmetricCrypto::create(SECRET).unwrap(),
+    )
+    .unwrap()
+}
+
+async fn inquire(client: &kamu_snap_client::SNAPClient) -> kamu_snap_client::Result<Payload> {
+    let response = client
+        .post::<_, Payload>(PATH, "1", &serde_json::json!({ "partnerReferenceNo": "1" }))
+        .await?;
+
+    Ok(response.into_payload().unwrap())
+}
+
+#[tokio::test]
+async fn fetches_and_caches_token() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, SUCCESSFUL, SUCCESSFUL]);
+    let client = client(&stub);
+
+    assert_eq!(inquire(&client).await.unwrap().reference_no, "1");
+    assert_eq!(inquire(&client).await.unwrap().reference_no, "1");
+
+    let requests = stub.requests();
+    let paths = requests.iter().map(|x| x.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, [TOKEN_PATH, PATH, PATH]);
+    assert_eq!(requests[1].headers["AUTHORIZATION"], "Bearer token-1");
+    assert_eq!(requests[2].headers["AUTHORIZATION"], "Bearer token-1");
+}
+
+#[tokio::test]
+async fn signs_requests() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, SUCCESSFUL]);
+    let client = client(&stub);
+    inquire(&client).await.unwrap();
+
+    let requests = stub.requests();
+    let token_request = &requests[0];
+    assert_eq!(token_request.headers["X-CLIENT-KEY"], CLIENT_KEY);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&token_request.body).unwrap(),
+        serde_json::json!({ "grantType": "client_credentials" })
+    );
+    key_pair()
+        .1
+        .verify_base64(
+            &token_request.headers["X-SIGNATURE"],
+            kamu_snap_crypto::string_to_sign::access_token(CLIENT_KEY, &token_request.headers["X-TIMESTAMP"]),
+        )
+        .unwrap();
+
+    let request = &requests[1];
+    assert_eq!(request.headers["X-PARTNER-ID"], CLIENT_KEY);
+    assert_eq!(request.headers["X-EXTERNAL-ID"], "1");
+    assert_eq!(request.headers["CHANNEL-ID"], CHANNEL_ID);
+    kamu_snap_crypto::SymmetricCrypto::create(SECRET)
+        .unwrap()
+        .verify(
+            &request.headers["X-SIGNATURE"],
+            kamu_snap_crypto::string_to_sign::symmetric(
+                "POST",
+                PATH,
+                "token-1",
+                &request.body,
+                &request.headers["X-TIMESTAMP"],
+            ),
+        )
+        .unwrap();
+}
+
+#[tokio::test]
+async fn signs_base_url_prefix() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, SUCCESSFUL]);
+    let client = kamu_snap_client::SNAPClient::new(
+        kamu_snap_client::SNAPClientConfig::new(format!("{}/snap/", stub.url), CLIENT_KEY, CHANNEL_ID),
+        key_pair().0,
+        kamu_snap_crypto::SymmetricCrypto::create(SECRET).unwrap(),
+    )
+    .unwrap();
+    inquire(&client).await.unwrap();
+
+    let requests = stub.requests();
+    let request = &requests[1];
+    assert_eq!(request.path, format!("/snap{PATH}"));
+    kamu_snap_crypto::SymmetricCrypto::create(SECRET)
+        .unwrap()
+        .verify(
+            &request.headers["X-SIGNATURE"],
+            kamu_snap_crypto::string_to_sign::symmetric(
+                "POST",
+                &request.path,
+                "token-1",
+                &request.body,
+                &request.headers["X-TIMESTAMP"],
+            ),
+        )
+        .unwrap();
+}
+
+#[test]
+fn relative_url() {
+    assert_eq!(
+        kamu_snap_client::relative_url("https://api.bank.co.id", PATH),
+        PATH
+    );
+    assert_eq!(
+        kamu_snap_client::relative_url("https://api.bank.co.id/snap/", PATH),
+        format!("/snap{PATH}")
+    );
+    assert_eq!(
+        kamu_snap_client::relative_url("https://api.bank.co.id/snap/v2", "/v1.0/a?b=c"),
+        "/snap/v2/v1.0/a?b=c"
+    );
+}
+
+#[tokio::test]
+async fn drops_rejected_token() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, INVALID_TOKEN, TOKEN_2, SUCCESSFUL]);
+    let client = client(&stub);
+
+    assert!(matches!(
+        inquire(&client).await,
+        Err(kamu_snap_client::ClientError::Response(
+            kamu_snap_response::ResponseError::InvalidTokenB2B
+        ))
+    ));
+    assert_eq!(inquire(&client).await.unwrap().reference_no, "1");
+
+    let requests = stub.requests();
+    let paths = requests.iter().map(|x| x.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, [TOKEN_PATH, PATH, TOKEN_PATH, PATH]);
+    assert_eq!(requests[3].headers["AUTHORIZATION"], "Bearer token-2");
+}
+
+#[tokio::test]
+async fn retries_with_new_token_and_external_id() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, INVALID_TOKEN, TOKEN_2, SUCCESSFUL]);
+    let client = client(&stub);
+    let policy =
+        kamu_snap_client::RetryPolicy::default().with_default(kamu_snap_client::retry::ServicePolicy {
+            operation: kamu_snap_client::retry::Operation::Idempotent,
+            backoff: kamu_snap_client::retry::Backoff {
+                initial: std::time::Duration::ZERO,
+                max: std::time::Duration::ZERO,
+                max_attempts: 2,
+            },
+        });
+
+    let outcome = kamu_snap_client::retry::execute(&policy, SERVICE_CODE, |attempt| {
+        let client = &client;
+
+        async move {
+            client
+                .post::<_, Payload>(
+                    PATH,
+                    &attempt.external_id,
+                    &serde_json::json!({ "partnerReferenceNo": "1" }),
+                )
+                .await
+        }
+    })
+    .await;
+    assert!(matches!(outcome, kamu_snap_client::retry::Outcome::Completed(_)));
+
+    let requests = stub.requests();
+    let paths = requests.iter().map(|x| x.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, [TOKEN_PATH, PATH, TOKEN_PATH, PATH]);
+    assert_eq!(requests[3].headers["AUTHORIZATION"], "Bearer token-2");
+    assert_ne!(
+        requests[1].headers["X-EXTERNAL-ID"],
+        requests[3].headers["X-EXTERNAL-ID"]
+    );
+    assert_eq!(requests[1].body, requests[3].body);
+}
+
+#[tokio::test]
+async fn decodes_error_envelope() {
+    let stub = kamu_snap_testing::Stub::start(vec![
+        TOKEN_1,
+        (
+            403,
+            r#"{"responseCode":"4031114","responseMessage":"Insufficient Funds"}"#,
+        ),
+        (502, "<html>Bad Gateway</html>"),
+        (200, r#"{"responseCode":"2001100","responseMessage":"Successful"#),
+    ]);
+    let client = client(&stub);
+
+    assert!(matches!(
+        inquire(&client).await,
+        Err(kamu_snap_client::ClientError::Response(
+            kamu_snap_response::ResponseError::InsufficientFunds
+        ))
+    ));
+    assert!(matches!(
+        inquire(&client).await,
+        Err(kamu_snap_client::ClientError::Status(502))
+    ));
+    assert!(matches!(
+        inquire(&client).await,
+        Err(kamu_snap_client::ClientError::Decode(_))
+    ));
+}
+
+#[test]
+fn generates_numeric_external_ids() {
+    let external_ids = (0..100)
+        .map(|_| kamu_snap_client::generate_external_id().unwrap())
+        .collect::<std::collections::HashSet<_>>();
+
+    assert_eq!(external_ids.len(), 100);
+    assert!(
+        external_ids
+            .iter()
+            .all(|x| x.len() == 20 && x.bytes().all(|x| x.is_ascii_digit()))
+    );
+}
@@ -0,0 +1,198 @@
+//! Retry decisions per service kind and failure.
+
+const BALANCE_INQUIRY: u8 = kamu_snap_model::common::SERVICE_CODE_BALANCE_INQUIRY;
+const TOP_UP: u8 = kamu_snap_model::emoney::SERVICE_CODE_TOP_UP;
+
+fn response(error: kamu_snap_response::ResponseError) -> kamu_snap_client::ClientError {
+    kamu_snap_client::ClientError::Response(error)
+}
+
+fn delay(attempt: u32) -> kamu_snap_client::RetryDecision {
+    kamu_snap_client::RetryDecision::Retry(kamu_snap_client::retry::Backoff::default().delay(attempt))
+}
+
+#[test]
+fn snap_defaults() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+
+    for service_code in [11, 12, 13, 15, 16, 36, 37, 39, 51] {
+        assert_eq!(
+            policy.get(service_code).operation,
+            kamu_snap_client::retry::Operation::Idempotent,
+            "{service_code}"
+        );
+    }
+    for service_code in [TOP_UP, kamu_snap_model::qris::SERVICE_CODE_GENERATE, 99] {
+        assert_eq!(
+            policy.get(service_code).operation,
+            kamu_snap_client::retry::Operation::NonIdempotent,
+            "{service_code}"
+        );
+    }
+}
+
+#[test]
+fn backoff() {
+    let backoff = kamu_snap_client::retry::Backoff {
+        initial: std::time::Duration::from_secs(1),
+        max: std::time::Duration::from_secs(5),
+        max_attempts: 10,
+    };
+
+    let delays = (1..=5).map(|x| backoff.delay(x).as_secs()).collect::<Vec<_>>();
+    assert_eq!(delays, [1, 2, 4, 5, 5]);
+}
+
+#[test]
+fn transient_errors() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let transient = [
+        kamu_snap_client::ClientError::Timeout,
+        kamu_snap_client::ClientError::Transport("connection reset".to_owned()),
+        kamu_snap_client::ClientError::Status(503),
+        response(kamu_snap_response::ResponseError::GeneralError),
+        response(kamu_snap_response::ResponseError::Timeout),
+    ];
+
+    for error in &transient {
+        assert_eq!(policy.decide(BALANCE_INQUIRY, error, 1), delay(1), "{error}");
+    }
+}
+
+#[test]
+fn retries_unprocessed_requests_of_any_service() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let unprocessed = [
+        kamu_snap_client::ClientError::Status(429),
+        response(kamu_snap_response::ResponseError::Conflict),
+        response(kamu_snap_response::ResponseError::TooManyRequests),
+        response(kamu_snap_response::ResponseError::InvalidTokenB2B),
+    ];
+
+    for error in &unprocessed {
+        assert_eq!(policy.decide(TOP_UP, error, 2), delay(2), "{error}");
+    }
+}
+
+#[test]
+fn fails_on_business_errors() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let permanent = [
+        kamu_snap_client::ClientError::Status(404),
+        kamu_snap_client::ClientError::Random("unavailable".to_owned()),
+        response(kamu_snap_response::ResponseError::InsufficientFunds),
+        response(kamu_snap_response::ResponseError::InvalidFieldFormat(
+            "amount".to_owned(),
+        )),
+        response(kamu_snap_response::ResponseError::Unathorized(
+            "Signature".to_owned(),
+        )),
+    ];
+
+    for error in &permanent {
+        assert_eq!(
+            policy.decide(BALANCE_INQUIRY, error, 1),
+            kamu_snap_client::RetryDecision::Fail,
+            "{error}"
+        );
+        assert_eq!(
+            policy.decide(TOP_UP, error, 1),
+            kamu_snap_client::RetryDecision::Fail,
+            "{error}"
+        );
+    }
+}
+
+#[test]
+fn stops_at_max_attempts() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let error = kamu_snap_client::ClientError::Timeout;
+
+    assert_eq!(policy.decide(BALANCE_INQUIRY, &error, 2), delay(2));
+    assert_eq!(
+        policy.decide(BALANCE_INQUIRY, &error, 3),
+        kamu_snap_client::RetryDecision::Fail
+    );
+}
+
+#[test]
+fn inquires_when_in_doubt() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let in_doubt = [
+        kamu_snap_client::ClientError::Timeout,
+        kamu_snap_client::ClientError::Decode("EOF".to_owned()),
+        kamu_snap_client::ClientError::Status(500),
+        kamu_snap_client::ClientError::ResponseSignature(
+            kamu_snap_crypto::CryptoError::ResponseSignatureVerificationFailed,
+        ),
+        response(kamu_snap_response::ResponseError::InternalServerError),
+        response(kamu_snap_response::ResponseError::DuplicatePartnerReferenceNo),
+    ];
+
+    for error in &in_doubt {
+        assert_eq!(
+            policy.decide(TOP_UP, error, 1),
+            kamu_snap_client::RetryDecision::Inquire,
+            "{error}"
+        );
+    }
+    assert_eq!(
+        policy.decide(
+            BALANCE_INQUIRY,
+            &response(kamu_snap_response::ResponseError::DuplicatePartnerReferenceNo),
+            1
+        ),
+        kamu_snap_client::RetryDecision::Fail
+    );
+}
+
+#[tokio::test]
+async fn execute_reports_attempt_in_doubt() {
+    let policy = kamu_snap_client::RetryPolicy::snap_defaults();
+    let mut external_ids = Vec::new();
+
+    let outcome = kamu_snap_client::retry::execute::<(), _, _>(&policy, TOP_UP, |attempt| {
+        external_ids.push(attempt.external_id);
+        std::future::ready(Err(kamu_snap_client::ClientError::Timeout))
+    })
+    .await;
+
+    match outcome {
+        kamu_snap_client::retry::Outcome::NeedsInquiry {
+            original_external_id, ..
+        } => assert_eq!(external_ids, [original_external_id]),
+        outcome => panic!("unexpected {outcome:?}"),
+    }
+}
+
+#[tokio::test]
+async fn execute_retries_with_fresh_external_id() {
+    let policy =
+        kamu_snap_client::RetryPolicy::default().with_default(kamu_snap_client::retry::ServicePolicy {
+            operation: kamu_snap_client::retry::Operation::Idempotent,
+            backoff: kamu_snap_client::retry::Backoff {
+                initial: std::time::Duration::ZERO,
+                max: std::time::Duration::ZERO,
+                max_attempts: 3,
+            },
+        });
+    let mut external_ids = Vec::new();
+
+    let outcome = kamu_snap_client::retry::execute(&policy, BALANCE_INQUIRY, |attempt| {
+        external_ids.push(attempt.external_id);
+        std::future::ready(match attempt.number {
+            3 => Ok(attempt.number),
+            _ => Err(kamu_snap_client::ClientError::Timeout),
+        })
+    })
+    .await;
+
+    assert!(matches!(outcome, kamu_snap_client::retry::Outcome::Completed(3)));
+    assert_eq!(
+        external_ids
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len(),
+        3
+    );
+}
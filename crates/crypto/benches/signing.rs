@@ -0,0 +1,49 @@
+//! Signing and verification through a shared `&self` instance, against
+//! cloning the instance per request as `&mut self` signing used to require.
+
+const PAYLOAD: &str = "POST:/v1.0/qr/qr-mpm-generate:eyJhbGciOiJIUzI1NiJ9:\
+    5a9c3d4c9e1bb7e0a85d3c6d2f60f8a3b2d7b0c1e6f4a9d8c7b6a5f4e3d2c1b0:2025-08-09T10:00:00+07:00";
+
+fn symmetric(c: &mut criterion::Criterion) {
+    let crypto = kamu_snap_crypto::SymmetricCrypto::create("client-secret").unwrap();
+    let signature = crypto.sign(PAYLOAD);
+    let mut group = c.benchmark_group("symmetric");
+
+    group.bench_function("sign", |b| b.iter(|| crypto.sign(std::hint::black_box(PAYLOAD))));
+    group.bench_function("clone_and_sign", |b| {
+        b.iter(|| crypto.clone().sign(std::hint::black_box(PAYLOAD)))
+    });
+    group.bench_function("verify", |b| {
+        b.iter(|| crypto.verify(&signature, std::hint::black_box(PAYLOAD)))
+    });
+    group.finish();
+}
+
+fn asymmetric(c: &mut criterion::Criterion) {
+    let private_key = rsa::RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 2048).unwrap();
+    let sk_pem =
+        rsa::pkcs8::EncodePrivateKey::to_pkcs8_pem(&private_key, rsa::pkcs8::LineEnding::LF).unwrap();
+    let pk_pem = rsa::pkcs8::EncodePublicKey::to_public_key_pem(
+        &private_key.to_public_key(),
+        rsa::pkcs8::LineEnding::LF,
+    )
+    .unwrap();
+    let signer = kamu_snap_crypto::AsymmetricCryptoSigner::create(sk_pem.as_str()).unwrap();
+    let verifier = kamu_snap_crypto::AsymmetricCryptoVerifier::create(&pk_pem).unwrap();
+    let signature = signer.sign_as_base64(PAYLOAD);
+    let mut group = c.benchmark_group("asymmetric");
+
+    group.bench_function("sign", |b| {
+        b.iter(|| signer.sign_as_base64(std::hint::black_box(PAYLOAD)))
+    });
+    group.bench_function("clone_and_sign", |b| {
+        b.iter(|| signer.clone().sign_as_base64(std::hint::black_box(PAYLOAD)))
+    });
+    group.bench_function("verify", |b| {
+        b.iter(|| verifier.verify_base64(&signature, std::hint::black_box(PAYLOAD)))
+    });
+    group.finish();
+}
+
+criterion::criterion_group!(benches, symmetric, asymmetric);
+criterion::criterion_main!(benches);
@@ -3,3 +3,14 @@ pub mod verifier;
 
 pub use signer::Crypto as AsymmetricCryptoSigner;
 pub use verifier::Crypto as AsymmetricCryptoVerifier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    /// SHA256withRSA, the SNAP standard
+    #[default]
+    RsaPkcs1v15Sha256,
+    /// RSASSA-PSS with SHA-256 and a 32 byte salt
+    RsaPssSha256,
+}
@@ -1,22 +1,158 @@
+/// AES-GCM nonce length of [`Crypto::open_base64`]
+const NONCE_SIZE: usize = 12;
+
+#[derive(Clone)]
+enum Key {
+    Pkcs1v15(rsa::pkcs1v15::SigningKey<sha2::Sha256>),
+    Pss(rsa::pss::BlindedSigningKey<sha2::Sha256>),
+}
+
+impl Key {
+    fn new(algorithm: super::Algorithm, private_key: rsa::RsaPrivateKey) -> Self {
+        match algorithm {
+            super::Algorithm::RsaPkcs1v15Sha256 => Key::Pkcs1v15(rsa::pkcs1v15::SigningKey::new(private_key)),
+            super::Algorithm::RsaPssSha256 => Key::Pss(rsa::pss::BlindedSigningKey::new(private_key)),
+        }
+    }
+
+    fn private_key(&self) -> &rsa::RsaPrivateKey {
+        match self {
+            Key::Pkcs1v15(x) => x.as_ref(),
+            Key::Pss(x) => x.as_ref(),
+        }
+    }
+}
+
+/// RSA signer, SHA256withRSA with base64 output unless configured otherwise.
+/// The private key is zeroized on drop by `rsa::RsaPrivateKey` itself, which
+/// implements `ZeroizeOnDrop`. Signing needs no mutable state, so one
+/// instance can be shared across threads.
 #[derive(Clone)]
 pub struct Crypto {
-    inner: rsa::pkcs1v15::SigningKey<sha2::Sha256>,
+    inner: Key,
+    algorithm: super::Algorithm,
+    encoding: crate::SignatureEncoding,
 }
 
 impl Crypto {
-    pub fn create(sk_pem: &str) -> crate::Result<Self> {
-        let inner =
-            <rsa::pkcs1v15::SigningKey<sha2::Sha256> as rsa::pkcs8::DecodePrivateKey>::from_pkcs8_pem(sk_pem)
+    pub fn create<K: AsRef<str>>(sk_pem: K) -> crate::Result<Self> {
+        let private_key =
+            <rsa::RsaPrivateKey as rsa::pkcs8::DecodePrivateKey>::from_pkcs8_pem(sk_pem.as_ref())
                 .map_err(|_| crate::CryptoError::InvalidPEMSecretKey)?;
+        let algorithm = super::Algorithm::default();
+
+        Ok(Self {
+            inner: Key::new(algorithm, private_key),
+            algorithm,
+            encoding: crate::SignatureEncoding::default(),
+        })
+    }
+
+    pub fn with_algorithm(mut self, algorithm: super::Algorithm) -> Self {
+        if algorithm != self.algorithm {
+            self.inner = Key::new(algorithm, self.inner.private_key().clone());
+            self.algorithm = algorithm;
+        }
+
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: crate::SignatureEncoding) -> Self {
+        self.encoding = encoding;
+
+        self
+    }
 
-        Ok(Self { inner })
+    pub fn algorithm(&self) -> super::Algorithm {
+        self.algorithm
     }
 
-    pub fn sign_as_base64<P: AsRef<[u8]>>(&mut self, payload: P) -> String {
+    pub fn encoding(&self) -> crate::SignatureEncoding {
+        self.encoding
+    }
+
+    /// Signature in the configured encoding, standard base64 by default.
+    pub fn sign_as_base64<P: AsRef<[u8]>>(&self, payload: P) -> String {
         let payload = payload.as_ref();
-        let signature = rsa::signature::SignerMut::sign(&mut self.inner, payload);
-        let signature_bytes = rsa::signature::SignatureEncoding::to_bytes(&signature);
+        let signature_bytes = match &self.inner {
+            Key::Pkcs1v15(x) => {
+                rsa::signature::SignatureEncoding::to_vec(&rsa::signature::Signer::sign(x, payload))
+            }
+            Key::Pss(x) => rsa::signature::SignatureEncoding::to_vec(
+                &rsa::signature::RandomizedSigner::sign_with_rng(x, &mut rsa::rand_core::OsRng, payload),
+            ),
+        };
+
+        self.encoding.encode(signature_bytes)
+    }
+
+    /// Decrypt a payload produced by
+    /// [`AsymmetricCryptoVerifier::encrypt_as_base64`](crate::AsymmetricCryptoVerifier::encrypt_as_base64)
+    /// with the matching public key.
+    pub fn decrypt_base64<C: AsRef<str>>(&self, ciphertext_base64: C) -> crate::Result<Vec<u8>> {
+        let ciphertext =
+            base64::Engine::decode(&base64::prelude::BASE64_STANDARD, ciphertext_base64.as_ref())
+                .map_err(|_| crate::CryptoError::DecryptionFailed)?;
+
+        self.inner
+            .private_key()
+            .decrypt(rsa::Oaep::new::<sha2::Sha256>(), &ciphertext)
+            .map_err(|_| crate::CryptoError::DecryptionFailed)
+    }
+
+    /// Decrypt a payload produced by
+    /// [`AsymmetricCryptoVerifier::seal_as_base64`](crate::AsymmetricCryptoVerifier::seal_as_base64)
+    /// with the matching public key.
+    pub fn open_base64<C: AsRef<str>>(&self, sealed_base64: C) -> crate::Result<Vec<u8>> {
+        let sealed = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, sealed_base64.as_ref())
+            .map_err(|_| crate::CryptoError::DecryptionFailed)?;
+        let private_key = self.inner.private_key();
+        let key_size = rsa::traits::PublicKeyParts::size(private_key);
+
+        if sealed.len() < key_size + NONCE_SIZE {
+            return Err(crate::CryptoError::DecryptionFailed);
+        }
+
+        let (wrapped_key, sealed) = sealed.split_at(key_size);
+        let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+        let key = zeroize::Zeroizing::new(
+            private_key
+                .decrypt(rsa::Oaep::new::<sha2::Sha256>(), wrapped_key)
+                .map_err(|_| crate::CryptoError::DecryptionFailed)?,
+        );
+        let cipher = <aes_gcm::Aes256Gcm as aes_gcm::KeyInit>::new_from_slice(&key)
+            .map_err(|_| crate::CryptoError::DecryptionFailed)?;
+
+        aes_gcm::aead::Aead::decrypt(&cipher, aes_gcm::Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| crate::CryptoError::DecryptionFailed)
+    }
+}
+
+impl kamu_snap_response::ResponseSigner for Crypto {
+    fn sign_response(
+        &self,
+        profile: &kamu_snap_response::QuirkProfile,
+        method: &str,
+        relative_url: &str,
+        body: &[u8],
+        timestamp: &str,
+    ) -> String {
+        self.sign_as_base64(crate::string_to_sign::response_with(
+            profile,
+            method,
+            relative_url,
+            body,
+            timestamp,
+        ))
+    }
+}
 
-        base64::Engine::encode(&base64::prelude::BASE64_STANDARD, signature_bytes)
+impl std::fmt::Debug for Crypto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsymmetricCryptoSigner")
+            .field("key", &"[REDACTED]")
+            .field("algorithm", &self.algorithm)
+            .field("encoding", &self.encoding)
+            .finish()
     }
 }
@@ -1,32 +1,143 @@
 #[derive(Clone)]
+enum Key {
+    Pkcs1v15(rsa::pkcs1v15::VerifyingKey<sha2::Sha256>),
+    Pss(rsa::pss::VerifyingKey<sha2::Sha256>),
+}
+
+impl Key {
+    fn new(algorithm: super::Algorithm, public_key: rsa::RsaPublicKey) -> Self {
+        match algorithm {
+            super::Algorithm::RsaPkcs1v15Sha256 => {
+                Key::Pkcs1v15(rsa::pkcs1v15::VerifyingKey::new(public_key))
+            }
+            super::Algorithm::RsaPssSha256 => Key::Pss(rsa::pss::VerifyingKey::new(public_key)),
+        }
+    }
+
+    fn public_key(&self) -> &rsa::RsaPublicKey {
+        match self {
+            Key::Pkcs1v15(x) => x.as_ref(),
+            Key::Pss(x) => x.as_ref(),
+        }
+    }
+}
+
+/// RSA verifier, SHA256withRSA over base64 signatures unless configured
+/// otherwise.
+#[derive(Clone)]
 pub struct Crypto {
-    inner: rsa::pkcs1v15::VerifyingKey<rsa::sha2::Sha256>,
+    inner: Key,
+    algorithm: super::Algorithm,
+    encoding: crate::SignatureEncoding,
 }
 
 impl Crypto {
     pub fn create(pk_pem: &str) -> crate::Result<Self> {
-        let inner =
-            <rsa::pkcs1v15::VerifyingKey<rsa::sha2::Sha256> as rsa::pkcs8::DecodePublicKey>::from_public_key_pem(
-                pk_pem,
-            ).map_err(|_| crate::CryptoError::InvalidPEMPublicKey)?;
+        let public_key = <rsa::RsaPublicKey as rsa::pkcs8::DecodePublicKey>::from_public_key_pem(pk_pem)
+            .map_err(|_| crate::CryptoError::InvalidPEMPublicKey)?;
+        let algorithm = super::Algorithm::default();
+
+        Ok(Self {
+            inner: Key::new(algorithm, public_key),
+            algorithm,
+            encoding: crate::SignatureEncoding::default(),
+        })
+    }
+
+    pub fn with_algorithm(mut self, algorithm: super::Algorithm) -> Self {
+        if algorithm != self.algorithm {
+            self.inner = Key::new(algorithm, self.inner.public_key().clone());
+            self.algorithm = algorithm;
+        }
+
+        self
+    }
 
-        Ok(Self { inner })
+    pub fn with_encoding(mut self, encoding: crate::SignatureEncoding) -> Self {
+        self.encoding = encoding;
+
+        self
+    }
+
+    pub fn algorithm(&self) -> super::Algorithm {
+        self.algorithm
     }
 
+    pub fn encoding(&self) -> crate::SignatureEncoding {
+        self.encoding
+    }
+
+    /// Verify a signature in the configured encoding, standard base64 by
+    /// default. Whitespace and line breaks in it are ignored.
     pub fn verify_base64<S, P>(&self, signature_base64: S, payload: P) -> crate::Result<()>
     where
         S: AsRef<str> + std::fmt::Display + std::fmt::Debug,
         P: AsRef<[u8]>,
     {
-        let signature_decoded =
-            base64::Engine::decode(&base64::prelude::BASE64_STANDARD, signature_base64.as_ref())
-                .map_err(|_| crate::CryptoError::BadSignatureFormat)?;
+        let signature_decoded = self.encoding.decode(signature_base64)?;
         let payload = payload.as_ref();
-        let signature = rsa::pkcs1v15::Signature::try_from(signature_decoded.as_slice())
-            .map_err(|_| crate::CryptoError::BadSignatureFormat)?;
-        rsa::signature::Verifier::verify(&self.inner, payload, &signature)
-            .map_err(|_| crate::CryptoError::SignatureVerificationFailedAsymmetric)?;
 
-        Ok(())
+        match &self.inner {
+            Key::Pkcs1v15(x) => {
+                let signature = rsa::pkcs1v15::Signature::try_from(signature_decoded.as_slice())
+                    .map_err(|_| crate::CryptoError::BadSignatureFormat)?;
+
+                rsa::signature::Verifier::verify(x, payload, &signature)
+            }
+            Key::Pss(x) => {
+                let signature = rsa::pss::Signature::try_from(signature_decoded.as_slice())
+                    .map_err(|_| crate::CryptoError::BadSignatureFormat)?;
+
+                rsa::signature::Verifier::verify(x, payload, &signature)
+            }
+        }
+        .map_err(|_| crate::CryptoError::SignatureVerificationFailedAsymmetric)
+    }
+
+    /// Encrypt `plaintext` with RSA-OAEP (SHA-256) so that only the owner of
+    /// the matching private key can read it, e.g. SNAP `cardData`.
+    pub fn encrypt_as_base64<P: AsRef<[u8]>>(&self, plaintext: P) -> crate::Result<String> {
+        let ciphertext = self
+            .inner
+            .public_key()
+            .encrypt(
+                &mut rsa::rand_core::OsRng,
+                rsa::Oaep::new::<sha2::Sha256>(),
+                plaintext.as_ref(),
+            )
+            .map_err(|_| crate::CryptoError::EncryptionFailed)?;
+
+        Ok(base64::Engine::encode(
+            &base64::prelude::BASE64_STANDARD,
+            ciphertext,
+        ))
+    }
+
+    /// Encrypt `plaintext` of any size with a fresh AES-256-GCM key wrapped
+    /// with RSA-OAEP (SHA-256). The output is the wrapped key, the 96-bit
+    /// nonce and the AES-GCM ciphertext (with tag), concatenated and base64
+    /// encoded.
+    pub fn seal_as_base64<P: AsRef<[u8]>>(&self, plaintext: P) -> crate::Result<String> {
+        let mut key = zeroize::Zeroizing::new([0_u8; 32]);
+        rsa::rand_core::RngCore::fill_bytes(&mut rsa::rand_core::OsRng, key.as_mut_slice());
+        let cipher = <aes_gcm::Aes256Gcm as aes_gcm::KeyInit>::new_from_slice(key.as_slice())
+            .map_err(|_| crate::CryptoError::EncryptionFailed)?;
+        let nonce = <aes_gcm::Aes256Gcm as aes_gcm::AeadCore>::generate_nonce(&mut rsa::rand_core::OsRng);
+        let ciphertext = aes_gcm::aead::Aead::encrypt(&cipher, &nonce, plaintext.as_ref())
+            .map_err(|_| crate::CryptoError::EncryptionFailed)?;
+        let wrapped_key = self
+            .inner
+            .public_key()
+            .encrypt(
+                &mut rsa::rand_core::OsRng,
+                rsa::Oaep::new::<sha2::Sha256>(),
+                key.as_slice(),
+            )
+            .map_err(|_| crate::CryptoError::EncryptionFailed)?;
+
+        Ok(base64::Engine::encode(
+            &base64::prelude::BASE64_STANDARD,
+            [wrapped_key.as_slice(), nonce.as_slice(), &ciphertext].concat(),
+        ))
     }
 }
@@ -0,0 +1,163 @@
+//! Signature mismatch diagnostics, behind the `diagnostics` feature. A
+//! [`Report`] exposes the string to sign and body details, so it must only
+//! be logged in non-production environments.
+
+/// Part of the string to sign in which a matching variant differs from the
+/// expected canonicalisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    /// Query string kept in or left out of the `EndpointUrl`
+    EndpointUrl,
+    /// Body hashed as sent or minified
+    BodyHash,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub relative_url: &'a str,
+    /// `None` for asymmetric signatures
+    pub access_token: Option<&'a str>,
+    pub body: &'a [u8],
+    pub timestamp: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub profile: kamu_snap_response::QuirkProfile,
+    pub string_to_sign: String,
+    pub body_hash: String,
+}
+
+impl Candidate {
+    fn new(profile: kamu_snap_response::QuirkProfile, request: &Request<'_>) -> Self {
+        let string_to_sign = match request.access_token {
+            Some(access_token) => crate::string_to_sign::symmetric_with(
+                &profile,
+                request.method,
+                request.relative_url,
+                access_token,
+                request.body,
+                request.timestamp,
+            ),
+            None => crate::string_to_sign::asymmetric_with(
+                &profile,
+                request.method,
+                request.relative_url,
+                request.body,
+                request.timestamp,
+            ),
+        };
+
+        Self {
+            profile,
+            string_to_sign,
+            body_hash: crate::string_to_sign::body_hash_with(&profile, request.body),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// What the configured profile computes
+    pub expected: Candidate,
+    pub minified_body: String,
+    /// First variant the signature verifies against, if any
+    pub matched: Option<Candidate>,
+    /// How the matched variant differs from the expected one
+    pub differences: Vec<Component>,
+}
+
+impl Report {
+    fn build<F>(profile: &kamu_snap_response::QuirkProfile, request: &Request<'_>, verify: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        let expected = Candidate::new(*profile, request);
+        let variants = [
+            (profile.sign_query_string, profile.minify_body),
+            (!profile.sign_query_string, profile.minify_body),
+            (profile.sign_query_string, !profile.minify_body),
+            (!profile.sign_query_string, !profile.minify_body),
+        ];
+        let matched = variants
+            .into_iter()
+            .map(|(sign_query_string, minify_body)| {
+                Candidate::new(
+                    kamu_snap_response::QuirkProfile {
+                        sign_query_string,
+                        minify_body,
+                        ..*profile
+                    },
+                    request,
+                )
+            })
+            .find(|x| verify(&x.string_to_sign));
+        let mut differences = Vec::new();
+
+        if let Some(matched) = &matched {
+            if matched.profile.endpoint_url(request.relative_url)
+                != profile.endpoint_url(request.relative_url)
+            {
+                differences.push(Component::EndpointUrl);
+            }
+
+            if matched.body_hash != expected.body_hash {
+                differences.push(Component::BodyHash);
+            }
+        }
+
+        Self {
+            expected,
+            minified_body: String::from_utf8_lossy(&crate::string_to_sign::minify_body(request.body))
+                .into_owned(),
+            matched,
+            differences,
+        }
+    }
+
+    /// `true` when the signature is valid as expected.
+    pub fn is_verified(&self) -> bool {
+        self.matched.is_some() && self.differences.is_empty()
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "expected string to sign: {}", self.expected.string_to_sign)?;
+        writeln!(f, "expected body hash: {}", self.expected.body_hash)?;
+        writeln!(f, "minified body: {}", self.minified_body)?;
+
+        match &self.matched {
+            Some(_) if self.differences.is_empty() => write!(f, "signature verified"),
+            Some(matched) => write!(
+                f,
+                "signature matches {} with differing {:?}",
+                matched.string_to_sign, self.differences
+            ),
+            None => write!(f, "signature matches no variant, check the key and timestamp"),
+        }
+    }
+}
+
+/// Explain a symmetric signature verification against the variants of
+/// `profile`.
+pub fn symmetric(
+    crypto: &crate::SymmetricCrypto,
+    profile: &kamu_snap_response::QuirkProfile,
+    signature: &str,
+    request: &Request<'_>,
+) -> Report {
+    Report::build(profile, request, |x| crypto.verify(signature, x).is_ok())
+}
+
+/// Explain an asymmetric signature verification against the variants of
+/// `profile`.
+pub fn asymmetric(
+    verifier: &crate::AsymmetricCryptoVerifier,
+    profile: &kamu_snap_response::QuirkProfile,
+    signature: &str,
+    request: &Request<'_>,
+) -> Report {
+    Report::build(profile, request, |x| verifier.verify_base64(signature, x).is_ok())
+}
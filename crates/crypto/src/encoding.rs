@@ -0,0 +1,58 @@
+/// Text encoding of signatures. SNAP mandates standard base64, some partners
+/// deviate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Base64,
+    /// URL safe alphabet, unpadded on output, padding accepted on input
+    Base64Url,
+    /// Lowercase on output, either case accepted on input
+    Hex,
+}
+
+impl Encoding {
+    pub fn encode<B: AsRef<[u8]>>(&self, bytes: B) -> String {
+        let bytes = bytes.as_ref();
+
+        match self {
+            Self::Base64 => base64::Engine::encode(&base64::prelude::BASE64_STANDARD, bytes),
+            Self::Base64Url => base64::Engine::encode(&base64::prelude::BASE64_URL_SAFE_NO_PAD, bytes),
+            Self::Hex => bytes.iter().map(|x| format!("{x:02x}")).collect(),
+        }
+    }
+
+    /// Decode `text`, ignoring any whitespace or line breaks in it.
+    pub fn decode<T: AsRef<str>>(&self, text: T) -> crate::Result<Vec<u8>> {
+        let text = text
+            .as_ref()
+            .chars()
+            .filter(|x| !x.is_ascii_whitespace())
+            .collect::<String>();
+
+        match self {
+            Self::Base64 => base64::Engine::decode(&base64::prelude::BASE64_STANDARD, text)
+                .map_err(|_| crate::CryptoError::BadSignatureFormat),
+            Self::Base64Url => base64::Engine::decode(
+                &base64::prelude::BASE64_URL_SAFE_NO_PAD,
+                text.trim_end_matches('='),
+            )
+            .map_err(|_| crate::CryptoError::BadSignatureFormat),
+            Self::Hex => {
+                if text.len() % 2 != 0 {
+                    return Err(crate::CryptoError::BadSignatureFormat);
+                }
+
+                (0..text.len())
+                    .step_by(2)
+                    .map(|x| {
+                        text.get(x..x + 2)
+                            .and_then(|x| u8::from_str_radix(x, 16).ok())
+                            .ok_or(crate::CryptoError::BadSignatureFormat)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
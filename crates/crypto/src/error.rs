@@ -11,8 +11,16 @@ pub enum Error {
     InvalidPEMPublicKey,
     #[error("Invalid PEM (Secret Key)")]
     InvalidPEMSecretKey,
-    #[error("Invalid secret length")]
-    InvalidSecretLength,
+    #[error("Encryption failed")]
+    EncryptionFailed,
+    #[error("Decryption failed")]
+    DecryptionFailed,
+    #[error("Secret unavailable: {0}")]
+    SecretUnavailable(String),
+    #[error("Response is not signed: missing {0}")]
+    UnsignedResponse(String),
+    #[error("Response signature verification failed")]
+    ResponseSignatureVerificationFailed,
 }
 
 impl From<Error> for kamu_snap_response::ResponseError {
@@ -29,7 +37,13 @@ impl From<Error> for kamu_snap_response::ResponseError {
             }
             Error::InvalidPEMPublicKey => kamu_snap_response::ResponseError::InternalServerError,
             Error::InvalidPEMSecretKey => kamu_snap_response::ResponseError::InternalServerError,
-            Error::InvalidSecretLength => kamu_snap_response::ResponseError::InternalServerError,
+            Error::EncryptionFailed => kamu_snap_response::ResponseError::InternalServerError,
+            Error::DecryptionFailed => kamu_snap_response::ResponseError::BadRequest,
+            Error::SecretUnavailable(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::UnsignedResponse(_) => kamu_snap_response::ResponseError::ExternalServerError,
+            Error::ResponseSignatureVerificationFailed => {
+                kamu_snap_response::ResponseError::ExternalServerError
+            }
         }
     }
 }
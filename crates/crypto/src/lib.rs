@@ -1,9 +1,18 @@
 pub mod asymmetric;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod encoding;
 pub mod error;
+pub mod response;
+pub mod secret;
+pub mod string_to_sign;
 pub mod symmetric;
 
-pub use asymmetric::{AsymmetricCryptoSigner, AsymmetricCryptoVerifier};
+pub use asymmetric::{Algorithm as AsymmetricAlgorithm, AsymmetricCryptoSigner, AsymmetricCryptoVerifier};
+pub use encoding::Encoding as SignatureEncoding;
 pub use error::Error as CryptoError;
-pub use symmetric::Crypto as SymmetricCrypto;
+pub use response::Verifier as ResponseVerifier;
+pub use secret::Secret;
+pub use symmetric::{Algorithm as SymmetricAlgorithm, Crypto as SymmetricCrypto};
 
 pub type Result<T> = core::result::Result<T, CryptoError>;
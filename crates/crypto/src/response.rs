@@ -0,0 +1,83 @@
+//! Verification of signed counterparty responses, independent of the HTTP
+//! client. The `X-SIGNATURE` of a response covers
+//! [`string_to_sign::response`](crate::string_to_sign::response).
+
+#[derive(Clone)]
+enum Key {
+    Asymmetric(Box<crate::AsymmetricCryptoVerifier>),
+    Symmetric(Box<crate::SymmetricCrypto>),
+}
+
+#[derive(Clone)]
+pub struct Verifier {
+    key: Key,
+    profile: kamu_snap_response::QuirkProfile,
+}
+
+impl Verifier {
+    /// Verify responses signed with the counterparty's private key.
+    pub fn asymmetric(verifier: crate::AsymmetricCryptoVerifier) -> Self {
+        Self {
+            key: Key::Asymmetric(Box::new(verifier)),
+            profile: kamu_snap_response::QuirkProfile::STRICT,
+        }
+    }
+
+    /// Verify responses signed with the shared client secret.
+    pub fn symmetric(crypto: crate::SymmetricCrypto) -> Self {
+        Self {
+            key: Key::Symmetric(Box::new(crypto)),
+            profile: kamu_snap_response::QuirkProfile::STRICT,
+        }
+    }
+
+    pub fn with_profile(mut self, profile: kamu_snap_response::QuirkProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Verify the `signature` and `timestamp` headers a counterparty sent with
+    /// `body` in response to `method` on `relative_url`. Missing headers are
+    /// reported as [`UnsignedResponse`](crate::CryptoError::UnsignedResponse).
+    pub fn verify(
+        &self,
+        method: &str,
+        relative_url: &str,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> crate::Result<()> {
+        let timestamp = header(timestamp, kamu_snap_response::header::X_TIMESTAMP)?;
+        let signature = header(signature, kamu_snap_response::header::X_SIGNATURE)?;
+        let string_to_sign =
+            crate::string_to_sign::response_with(&self.profile, method, relative_url, body, timestamp);
+
+        let result = match &self.key {
+            Key::Asymmetric(x) => x.verify_base64(signature, string_to_sign),
+            Key::Symmetric(x) => x.verify(signature, string_to_sign),
+        };
+
+        result.map_err(|_| crate::CryptoError::ResponseSignatureVerificationFailed)
+    }
+}
+
+fn header<'a>(value: Option<&'a str>, name: &str) -> crate::Result<&'a str> {
+    value
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .ok_or_else(|| crate::CryptoError::UnsignedResponse(name.to_owned()))
+}
+
+impl std::fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self.key {
+            Key::Asymmetric(_) => "asymmetric",
+            Key::Symmetric(_) => "symmetric",
+        };
+
+        f.debug_struct("ResponseVerifier")
+            .field("key", &key)
+            .field("profile", &self.profile)
+            .finish()
+    }
+}
@@ -0,0 +1,111 @@
+/// Client secret, private key PEM or any other sensitive string. The value
+/// is wiped from memory on drop and never shows up in `Debug` output.
+///
+/// ```ignore
+/// let client_secret = Secret::from_env_or_file("SNAP_CLIENT_SECRET")?;
+/// let symmetric = SymmetricCrypto::from_slice(&client_secret)?;
+///
+/// let private_key = Secret::from_file("/run/secrets/snap_private_key")?;
+/// let signer = AsymmetricCryptoSigner::create(&private_key)?;
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret {
+    inner: zeroize::Zeroizing<String>,
+}
+
+impl Secret {
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        Self {
+            inner: zeroize::Zeroizing::new(value.into()),
+        }
+    }
+
+    pub fn from_env(name: &str) -> crate::Result<Self> {
+        std::env::var(name)
+            .map(Self::new)
+            .map_err(|x| crate::CryptoError::SecretUnavailable(format!("{name}: {x}")))
+    }
+
+    /// Read a secret file such as a Docker or Kubernetes secret mount. A
+    /// single trailing newline is dropped.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let mut value = std::fs::read_to_string(path)
+            .map(Self::new)
+            .map_err(|x| crate::CryptoError::SecretUnavailable(format!("{}: {x}", path.display())))?;
+
+        if value.inner.ends_with('\n') {
+            value.inner.pop();
+
+            if value.inner.ends_with('\r') {
+                value.inner.pop();
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Read the `name` environment variable, or the file named by the
+    /// `{name}_FILE` one when it is unset, following the Docker secrets
+    /// convention.
+    pub fn from_env_or_file(name: &str) -> crate::Result<Self> {
+        match std::env::var_os(name) {
+            Some(_) => Self::from_env(name),
+            None => {
+                let file = format!("{name}_FILE");
+                let path = std::env::var_os(&file).ok_or_else(|| {
+                    crate::CryptoError::SecretUnavailable(format!("neither {name} nor {file} is set"))
+                })?;
+
+                Self::from_file(path)
+            }
+        }
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.inner
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl AsRef<[u8]> for Secret {
+    fn as_ref(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+}
+
+impl AsRef<str> for Secret {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
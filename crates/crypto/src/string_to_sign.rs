@@ -0,0 +1,121 @@
+//! SNAP string-to-sign canonicalisation. The `*_with` variants apply the
+//! quirks of a [`QuirkProfile`](kamu_snap_response::QuirkProfile), the others
+//! follow the standard.
+
+/// Minify a JSON request body. Bodies that are not JSON are returned as is,
+/// an empty body stays empty.
+pub fn minify_body<B: AsRef<[u8]>>(body: B) -> Vec<u8> {
+    let body = body.as_ref();
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => serde_json::to_vec(&value).unwrap_or_else(|_| body.to_vec()),
+        Err(_) => body.to_vec(),
+    }
+}
+
+/// `Lowercase(HexEncode(SHA-256(minify(body))))`
+pub fn body_hash<B: AsRef<[u8]>>(body: B) -> String {
+    body_hash_with(&kamu_snap_response::QuirkProfile::STRICT, body)
+}
+
+pub fn body_hash_with<B: AsRef<[u8]>>(profile: &kamu_snap_response::QuirkProfile, body: B) -> String {
+    let digest = if profile.minify_body {
+        <sha2::Sha256 as sha2::Digest>::digest(minify_body(body))
+    } else {
+        <sha2::Sha256 as sha2::Digest>::digest(body)
+    };
+
+    digest.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+/// `X-CLIENT-KEY|X-TIMESTAMP`, signed with SHA256withRSA to request an access
+/// token.
+pub fn access_token(client_key: &str, timestamp: &str) -> String {
+    format!("{client_key}|{timestamp}")
+}
+
+/// `HTTPMethod:EndpointUrl:Lowercase(HexEncode(SHA-256(minify(RequestBody)))):TimeStamp`,
+/// signed with SHA256withRSA by services that do not use an access token,
+/// e.g. notifications.
+pub fn asymmetric<B: AsRef<[u8]>>(method: &str, relative_url: &str, body: B, timestamp: &str) -> String {
+    asymmetric_with(
+        &kamu_snap_response::QuirkProfile::STRICT,
+        method,
+        relative_url,
+        body,
+        timestamp,
+    )
+}
+
+pub fn asymmetric_with<B: AsRef<[u8]>>(
+    profile: &kamu_snap_response::QuirkProfile,
+    method: &str,
+    relative_url: &str,
+    body: B,
+    timestamp: &str,
+) -> String {
+    format!(
+        "{}:{}:{}:{timestamp}",
+        method.to_ascii_uppercase(),
+        profile.endpoint_url(relative_url),
+        body_hash_with(profile, body)
+    )
+}
+
+/// `HTTPMethod:EndpointUrl:AccessToken:Lowercase(HexEncode(SHA-256(minify(RequestBody)))):TimeStamp`,
+/// signed with HMAC-SHA512 using the client secret.
+pub fn symmetric<B: AsRef<[u8]>>(
+    method: &str,
+    relative_url: &str,
+    access_token: &str,
+    body: B,
+    timestamp: &str,
+) -> String {
+    symmetric_with(
+        &kamu_snap_response::QuirkProfile::STRICT,
+        method,
+        relative_url,
+        access_token,
+        body,
+        timestamp,
+    )
+}
+
+pub fn symmetric_with<B: AsRef<[u8]>>(
+    profile: &kamu_snap_response::QuirkProfile,
+    method: &str,
+    relative_url: &str,
+    access_token: &str,
+    body: B,
+    timestamp: &str,
+) -> String {
+    format!(
+        "{}:{}:{access_token}:{}:{timestamp}",
+        method.to_ascii_uppercase(),
+        profile.endpoint_url(relative_url),
+        body_hash_with(profile, body)
+    )
+}
+
+/// `HTTPMethod:EndpointUrl:Lowercase(HexEncode(SHA-256(minify(ResponseBody)))):TimeStamp`
+/// of a signed response, with the method and URL of the request it answers
+/// and the `X-TIMESTAMP` of the response.
+pub fn response<B: AsRef<[u8]>>(method: &str, relative_url: &str, body: B, timestamp: &str) -> String {
+    response_with(
+        &kamu_snap_response::QuirkProfile::STRICT,
+        method,
+        relative_url,
+        body,
+        timestamp,
+    )
+}
+
+pub fn response_with<B: AsRef<[u8]>>(
+    profile: &kamu_snap_response::QuirkProfile,
+    method: &str,
+    relative_url: &str,
+    body: B,
+    timestamp: &str,
+) -> String {
+    asymmetric_with(profile, method, relative_url, body, timestamp)
+}
@@ -1,6 +1,43 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    /// SNAP standard
+    #[default]
+    HmacSha512,
+    HmacSha256,
+}
+
+/// HMAC keyed with the client secret, HMAC-SHA512 with base64 output unless
+/// configured otherwise. The keyed state is cached and cloned for each
+/// operation, so one instance can be shared across threads behind `Arc` or
+/// `web::Data`. The secret is zeroized on drop, the keyed state is not as
+/// `hmac` does not support it.
 #[derive(Clone)]
 pub struct Crypto {
-    inner: hmac::Hmac<sha2::Sha512>,
+    key: zeroize::Zeroizing<Vec<u8>>,
+    state: State,
+    encoding: crate::SignatureEncoding,
+}
+
+#[derive(Clone)]
+enum State {
+    HmacSha512(hmac::Hmac<sha2::Sha512>),
+    HmacSha256(hmac::Hmac<sha2::Sha256>),
+}
+
+impl State {
+    fn new(algorithm: Algorithm, key: &[u8]) -> Self {
+        match algorithm {
+            Algorithm::HmacSha512 => Self::HmacSha512(keyed(key)),
+            Algorithm::HmacSha256 => Self::HmacSha256(keyed(key)),
+        }
+    }
+}
+
+/// HMAC takes keys of any length, hashing the ones longer than its block.
+fn keyed<M: hmac::digest::KeyInit>(key: &[u8]) -> M {
+    M::new_from_slice(key).expect("HMAC takes keys of any length")
 }
 
 impl Crypto {
@@ -9,38 +46,106 @@ impl Crypto {
     }
 
     pub fn sign_once<P: AsRef<[u8]>>(secret: &str, payload: P) -> crate::Result<String> {
-        let mut instance = Self::create(secret)?;
-
-        Ok(instance.sign(payload))
+        Ok(Self::create(secret)?.sign(payload))
     }
 
     pub fn from_slice<S: AsRef<[u8]>>(slice_secret: S) -> crate::Result<Self> {
-        let inner = <hmac::Hmac<sha2::Sha512> as hmac::digest::Mac>::new_from_slice(slice_secret.as_ref())
-            .map_err(|_| crate::CryptoError::InvalidSecretLength)?;
+        let key = zeroize::Zeroizing::new(slice_secret.as_ref().to_vec());
+
+        Ok(Self {
+            state: State::new(Algorithm::default(), &key),
+            key,
+            encoding: crate::SignatureEncoding::default(),
+        })
+    }
 
-        Ok(Self { inner })
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.state = State::new(algorithm, &self.key);
+
+        self
     }
 
-    pub fn sign<P: AsRef<[u8]>>(&mut self, payload: P) -> String {
-        hmac::digest::Update::update(&mut self.inner, payload.as_ref());
-        let signature = hmac::digest::Mac::finalize_reset(&mut self.inner)
-            .into_bytes()
-            .to_vec();
+    pub fn with_encoding(mut self, encoding: crate::SignatureEncoding) -> Self {
+        self.encoding = encoding;
+
+        self
+    }
 
-        base64::Engine::encode(&base64::prelude::BASE64_STANDARD, &signature)
+    pub fn algorithm(&self) -> Algorithm {
+        match self.state {
+            State::HmacSha512(_) => Algorithm::HmacSha512,
+            State::HmacSha256(_) => Algorithm::HmacSha256,
+        }
     }
 
-    pub fn verify<S, P>(&mut self, signature: S, payload: P) -> crate::Result<()>
+    pub fn encoding(&self) -> crate::SignatureEncoding {
+        self.encoding
+    }
+
+    pub fn sign<P: AsRef<[u8]>>(&self, payload: P) -> String {
+        let signature = match &self.state {
+            State::HmacSha512(mac) => compute(mac.clone(), payload.as_ref()),
+            State::HmacSha256(mac) => compute(mac.clone(), payload.as_ref()),
+        };
+
+        self.encoding.encode(signature)
+    }
+
+    /// Verify a signature in the configured encoding, whitespace in it is
+    /// ignored.
+    pub fn verify<S, P>(&self, signature: S, payload: P) -> crate::Result<()>
     where
         S: AsRef<str>,
         P: AsRef<[u8]>,
     {
-        let signature = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, signature.as_ref())
-            .map_err(|_| crate::error::Error::BadSignatureFormat)?;
-        hmac::digest::Mac::update(&mut self.inner, payload.as_ref());
-        hmac::digest::Mac::verify_slice_reset(&mut self.inner, signature.as_ref())
-            .map_err(|_| crate::error::Error::SignatureVerificationFailedSymmetric)?;
+        let signature = self.encoding.decode(signature)?;
+        let payload = payload.as_ref();
+
+        match &self.state {
+            State::HmacSha512(mac) => verify(mac.clone(), payload, &signature),
+            State::HmacSha256(mac) => verify(mac.clone(), payload, &signature),
+        }
+    }
+}
+
+fn compute<M: hmac::Mac>(mut mac: M, payload: &[u8]) -> Vec<u8> {
+    hmac::Mac::update(&mut mac, payload);
+
+    hmac::Mac::finalize(mac).into_bytes().to_vec()
+}
+
+fn verify<M: hmac::Mac>(mut mac: M, payload: &[u8], signature: &[u8]) -> crate::Result<()> {
+    hmac::Mac::update(&mut mac, payload);
+
+    hmac::Mac::verify_slice(mac, signature)
+        .map_err(|_| crate::CryptoError::SignatureVerificationFailedSymmetric)
+}
+
+impl kamu_snap_response::ResponseSigner for Crypto {
+    fn sign_response(
+        &self,
+        profile: &kamu_snap_response::QuirkProfile,
+        method: &str,
+        relative_url: &str,
+        body: &[u8],
+        timestamp: &str,
+    ) -> String {
+        self.sign(crate::string_to_sign::response_with(
+            profile,
+            method,
+            relative_url,
+            body,
+            timestamp,
+        ))
+    }
+}
 
-        Ok(())
+impl std::fmt::Debug for Crypto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymmetricCrypto")
+            .field("key", &"[REDACTED]")
+            .field("algorithm", &self.algorithm())
+            .field("encoding", &self.encoding)
+            .finish()
     }
 }
@@ -0,0 +1,143 @@
+//! Candidate variants reported for mismatching signatures.
+#![cfg(feature = "diagnostics")]
+
+const URL: &str = "/v1.0/balance-inquiry?lang=id";
+const BODY: &[u8] = b"{ \"partnerReferenceNo\": \"2020102900000000000001\" }";
+const TOKEN: &str = "token";
+const TIMESTAMP: &str = "2020-01-01T00:00:00+07:00";
+
+fn request(access_token: Option<&'static str>) -> kamu_snap_crypto::diagnostics::Request<'static> {
+    kamu_snap_crypto::diagnostics::Request {
+        method: "POST",
+        relative_url: URL,
+        access_token,
+        body: BODY,
+        timestamp: TIMESTAMP,
+    }
+}
+
+fn crypto() -> kamu_snap_crypto::SymmetricCrypto {
+    kamu_snap_crypto::SymmetricCrypto::create("secret").unwrap()
+}
+
+/// Symmetric signature of a partner signing as `profile` does.
+fn signed_as(profile: kamu_snap_response::QuirkProfile) -> String {
+    crypto().sign(kamu_snap_crypto::string_to_sign::symmetric_with(
+        &profile, "POST", URL, TOKEN, BODY, TIMESTAMP,
+    ))
+}
+
+fn report(signature: &str) -> kamu_snap_crypto::diagnostics::Report {
+    kamu_snap_crypto::diagnostics::symmetric(
+        &crypto(),
+        &kamu_snap_response::QuirkProfile::STRICT,
+        signature,
+        &request(Some(TOKEN)),
+    )
+}
+
+#[test]
+fn verified() {
+    let report = report(&signed_as(kamu_snap_response::QuirkProfile::STRICT));
+
+    assert!(report.is_verified());
+    assert!(report.differences.is_empty());
+    assert_eq!(
+        report.expected.string_to_sign,
+        kamu_snap_crypto::string_to_sign::symmetric("POST", URL, TOKEN, BODY, TIMESTAMP)
+    );
+    assert_eq!(
+        report.minified_body,
+        r#"{"partnerReferenceNo":"2020102900000000000001"}"#
+    );
+    assert!(report.to_string().ends_with("\nsignature verified"));
+}
+
+#[test]
+fn candidate_variants() {
+    use kamu_snap_crypto::diagnostics::Component::*;
+
+    for (profile, differences) in [
+        (kamu_snap_response::QuirkProfile::PATH_ONLY, vec![EndpointUrl]),
+        (kamu_snap_response::QuirkProfile::RAW_BODY, vec![BodyHash]),
+        (
+            kamu_snap_response::QuirkProfile {
+                sign_query_string: false,
+                minify_body: false,
+                ..kamu_snap_response::QuirkProfile::STRICT
+            },
+            vec![EndpointUrl, BodyHash],
+        ),
+    ] {
+        let report = report(&signed_as(profile));
+        let matched = report.matched.as_ref().unwrap();
+
+        assert!(!report.is_verified());
+        assert_eq!(report.differences, differences);
+        assert_eq!(
+            (matched.profile.sign_query_string, matched.profile.minify_body),
+            (profile.sign_query_string, profile.minify_body)
+        );
+        assert_eq!(
+            matched.string_to_sign,
+            kamu_snap_crypto::string_to_sign::symmetric_with(&profile, "POST", URL, TOKEN, BODY, TIMESTAMP)
+        );
+        assert!(report.to_string().ends_with(&format!(
+            "signature matches {} with differing {differences:?}",
+            matched.string_to_sign
+        )));
+    }
+}
+
+#[test]
+fn no_variant() {
+    let signature = kamu_snap_crypto::SymmetricCrypto::create("other-secret")
+        .unwrap()
+        .sign(kamu_snap_crypto::string_to_sign::symmetric(
+            "POST", URL, TOKEN, BODY, TIMESTAMP,
+        ));
+    let report = report(&signature);
+
+    assert!(report.matched.is_none());
+    assert!(!report.is_verified());
+    assert!(
+        report
+            .to_string()
+            .ends_with("signature matches no variant, check the key and timestamp")
+    );
+}
+
+#[test]
+fn asymmetric() {
+    let signer = kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap();
+    let verifier = kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap();
+    let signature = signer.sign_as_base64(kamu_snap_crypto::string_to_sign::asymmetric_with(
+        &kamu_snap_response::QuirkProfile::PATH_ONLY,
+        "POST",
+        URL,
+        BODY,
+        TIMESTAMP,
+    ));
+
+    let report = kamu_snap_crypto::diagnostics::asymmetric(
+        &verifier,
+        &kamu_snap_response::QuirkProfile::STRICT,
+        &signature,
+        &request(None),
+    );
+    assert_eq!(
+        report.differences,
+        [kamu_snap_crypto::diagnostics::Component::EndpointUrl]
+    );
+
+    // Verified as is under the partner's own profile
+    assert!(
+        kamu_snap_crypto::diagnostics::asymmetric(
+            &verifier,
+            &kamu_snap_response::QuirkProfile::PATH_ONLY,
+            &signature,
+            &request(None),
+        )
+        .is_verified()
+    );
+}
@@ -0,0 +1,189 @@
+//! Response signing per quirk profile and its verification.
+
+const METHOD: &str = "POST";
+const URL: &str = "/v1.0/balance-inquiry?lang=id";
+const BODY: &[u8] = b"{ \"responseCode\": \"2001100\",\n  \"responseMessage\": \"Successful\" }";
+const TIMESTAMP: &str = "2025-08-09T10:00:00+07:00";
+
+fn symmetric() -> kamu_snap_crypto::SymmetricCrypto {
+    kamu_snap_crypto::SymmetricCrypto::create("client-secret").unwrap()
+}
+
+#[test]
+fn signs_with_profile() {
+    let crypto = symmetric();
+    let sign = |profile| {
+        kamu_snap_response::ResponseSigner::sign_response(&crypto, &profile, METHOD, URL, BODY, TIMESTAMP)
+    };
+    let strict = sign(kamu_snap_response::QuirkProfile::STRICT);
+    let raw_body = sign(kamu_snap_response::QuirkProfile::RAW_BODY);
+
+    assert_eq!(
+        strict,
+        crypto.sign(kamu_snap_crypto::string_to_sign::response(
+            METHOD, URL, BODY, TIMESTAMP
+        ))
+    );
+    assert_eq!(
+        raw_body,
+        crypto.sign(kamu_snap_crypto::string_to_sign::response_with(
+            &kamu_snap_response::QuirkProfile::RAW_BODY,
+            METHOD,
+            URL,
+            BODY,
+            TIMESTAMP
+        ))
+    );
+    // The body is not minified
+    assert_ne!(raw_body, strict);
+
+    let verifier = kamu_snap_crypto::ResponseVerifier::symmetric(symmetric());
+    verifier
+        .verify(METHOD, URL, Some(TIMESTAMP), Some(&strict), BODY)
+        .unwrap();
+    verifier
+        .with_profile(kamu_snap_response::QuirkProfile::RAW_BODY)
+        .verify(METHOD, URL, Some(TIMESTAMP), Some(&raw_body), BODY)
+        .unwrap();
+}
+
+fn unsigned(result: kamu_snap_crypto::Result<()>) -> String {
+    match result {
+        Err(kamu_snap_crypto::CryptoError::UnsignedResponse(header)) => header,
+        result => panic!("unexpected {result:?}"),
+    }
+}
+
+fn mismatch(result: kamu_snap_crypto::Result<()>) -> bool {
+    matches!(
+        result,
+        Err(kamu_snap_crypto::CryptoError::ResponseSignatureVerificationFailed)
+    )
+}
+
+#[test]
+fn unsigned_response() {
+    let verifier = kamu_snap_crypto::ResponseVerifier::symmetric(symmetric());
+    let signature = kamu_snap_response::ResponseSigner::sign_response(
+        &symmetric(),
+        &kamu_snap_response::QuirkProfile::STRICT,
+        METHOD,
+        URL,
+        BODY,
+        TIMESTAMP,
+    );
+
+    assert_eq!(
+        unsigned(verifier.verify(METHOD, URL, None, Some(&signature), BODY)),
+        "X-TIMESTAMP"
+    );
+    assert_eq!(
+        unsigned(verifier.verify(METHOD, URL, Some(TIMESTAMP), None, BODY)),
+        "X-SIGNATURE"
+    );
+    // Blank headers count as missing
+    assert_eq!(
+        unsigned(verifier.verify(METHOD, URL, Some(TIMESTAMP), Some("  "), BODY)),
+        "X-SIGNATURE"
+    );
+    assert_eq!(
+        kamu_snap_response::ResponseError::from(kamu_snap_crypto::CryptoError::UnsignedResponse(
+            "X-SIGNATURE".to_owned()
+        ))
+        .get_code(11),
+        5001102
+    );
+}
+
+#[test]
+fn asymmetric_mismatch() {
+    let signer = kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap();
+    let verifier = kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap();
+    let signature = kamu_snap_response::ResponseSigner::sign_response(
+        &signer,
+        &kamu_snap_response::QuirkProfile::STRICT,
+        METHOD,
+        URL,
+        BODY,
+        TIMESTAMP,
+    );
+    let verifier = kamu_snap_crypto::ResponseVerifier::asymmetric(verifier);
+
+    verifier
+        .verify(METHOD, URL, Some(TIMESTAMP), Some(&signature), BODY)
+        .unwrap();
+    // Reformatting the body keeps the minified hash
+    verifier
+        .verify(
+            METHOD,
+            URL,
+            Some(TIMESTAMP),
+            Some(&signature),
+            br#"{"responseCode":"2001100","responseMessage":"Successful"}"#,
+        )
+        .unwrap();
+
+    assert!(mismatch(verifier.verify(
+        METHOD,
+        URL,
+        Some(TIMESTAMP),
+        Some(&signature),
+        br#"{"responseCode":"2001100","responseMessage":"Success"}"#,
+    )));
+    assert!(mismatch(verifier.verify(
+        METHOD,
+        URL,
+        Some("2025-08-09T10:00:01+07:00"),
+        Some(&signature),
+        BODY
+    )));
+    assert!(mismatch(verifier.verify(
+        "GET",
+        URL,
+        Some(TIMESTAMP),
+        Some(&signature),
+        BODY
+    )));
+    assert!(mismatch(verifier.verify(
+        METHOD,
+        URL,
+        Some(TIMESTAMP),
+        Some("not base64!"),
+        BODY
+    )));
+    // The query string is signed under the strict profile
+    assert!(mismatch(verifier.verify(
+        METHOD,
+        "/v1.0/balance-inquiry",
+        Some(TIMESTAMP),
+        Some(&signature),
+        BODY
+    )));
+    assert!(mismatch(
+        verifier
+            .with_profile(kamu_snap_response::QuirkProfile::PATH_ONLY)
+            .verify(METHOD, URL, Some(TIMESTAMP), Some(&signature), BODY)
+    ));
+}
+
+#[test]
+fn symmetric_mismatch() {
+    let signature = kamu_snap_response::ResponseSigner::sign_response(
+        &kamu_snap_crypto::SymmetricCrypto::create("other-secret").unwrap(),
+        &kamu_snap_response::QuirkProfile::STRICT,
+        METHOD,
+        URL,
+        BODY,
+        TIMESTAMP,
+    );
+
+    assert!(mismatch(
+        kamu_snap_crypto::ResponseVerifier::symmetric(symmetric()).verify(
+            METHOD,
+            URL,
+            Some(TIMESTAMP),
+            Some(&signature),
+            BODY
+        )
+    ));
+}
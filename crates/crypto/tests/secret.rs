@@ -0,0 +1,97 @@
+//! Secret loading from the environment and files, and redaction.
+
+fn temp_file(name: &str, content: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("kamu-snap-crypto-{}-{name}", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+
+    path
+}
+
+#[test]
+fn from_file() {
+    for (content, expected) in [
+        ("secret\n", "secret"),
+        ("secret\r\n", "secret"),
+        ("secret", "secret"),
+        // Only one trailing newline is dropped
+        ("secret\n\n", "secret\n"),
+        (" secret \n", " secret "),
+    ] {
+        let path = temp_file("from-file", content);
+        assert_eq!(
+            kamu_snap_crypto::Secret::from_file(&path).unwrap().expose(),
+            expected
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    match kamu_snap_crypto::Secret::from_file("/nonexistent/kamu-snap-secret") {
+        Err(kamu_snap_crypto::CryptoError::SecretUnavailable(reason)) => {
+            assert!(reason.starts_with("/nonexistent/kamu-snap-secret: "), "{reason}");
+        }
+        result => panic!("unexpected {result:?}"),
+    }
+}
+
+/// The only test touching the environment, so nothing reads it concurrently.
+#[test]
+fn from_env_or_file() {
+    let name = format!("KAMU_SNAP_TEST_SECRET_{}", std::process::id());
+    let file = format!("{name}_FILE");
+    let path = temp_file("from-env-or-file", "from-file\n");
+
+    match kamu_snap_crypto::Secret::from_env_or_file(&name) {
+        Err(kamu_snap_crypto::CryptoError::SecretUnavailable(reason)) => {
+            assert_eq!(reason, format!("neither {name} nor {file} is set"));
+        }
+        result => panic!("unexpected {result:?}"),
+    }
+
+    // SAFETY: no other thread of this test binary reads the environment
+    unsafe { std::env::set_var(&file, &path) };
+    assert_eq!(
+        kamu_snap_crypto::Secret::from_env_or_file(&name)
+            .unwrap()
+            .expose(),
+        "from-file"
+    );
+
+    // The variable itself takes precedence
+    unsafe { std::env::set_var(&name, "from-env") };
+    assert_eq!(
+        kamu_snap_crypto::Secret::from_env_or_file(&name)
+            .unwrap()
+            .expose(),
+        "from-env"
+    );
+    assert_eq!(
+        kamu_snap_crypto::Secret::from_env(&name).unwrap().expose(),
+        "from-env"
+    );
+
+    unsafe {
+        std::env::remove_var(&name);
+        std::env::remove_var(&file);
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn redacted() {
+    let secret = kamu_snap_crypto::Secret::new("client-secret");
+
+    assert_eq!(format!("{secret:?}"), "Secret([REDACTED])");
+    assert!(!format!("{:?}", Some(&secret)).contains("client-secret"));
+    assert_eq!(secret.expose(), "client-secret");
+
+    let secret: kamu_snap_crypto::Secret = serde_json::from_str(r#""from-config""#).unwrap();
+    assert_eq!(format!("{secret:#?}"), "Secret([REDACTED])");
+    assert_eq!(AsRef::<[u8]>::as_ref(&secret), b"from-config");
+}
+
+#[test]
+fn private_key_zeroized_on_drop() {
+    fn zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+
+    zeroize_on_drop::<rsa::RsaPrivateKey>();
+}
@@ -0,0 +1,102 @@
+//! String-to-sign test vectors per quirk profile. Expected values were
+//! computed independently with Python's `hashlib` and `hmac`.
+
+const BODY: &str = "{ \"partnerReferenceNo\": \"2020102900000000000001\",\n  \"amount\": { \"value\": \"12345.00\", \"currency\": \"IDR\" } }";
+const URL: &str = "/v1.0/balance-inquiry?lang=id";
+const TOKEN: &str = "token";
+const TIMESTAMP: &str = "2020-01-01T00:00:00+07:00";
+const SECRET: &str = "secret";
+
+struct Vector {
+    profile: kamu_snap_response::QuirkProfile,
+    body_hash: &'static str,
+    string_to_sign: &'static str,
+    signature: &'static str,
+}
+
+const VECTORS: [Vector; 4] = [
+    Vector {
+        profile: kamu_snap_response::QuirkProfile::STRICT,
+        body_hash: "c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65",
+        string_to_sign: "POST:/v1.0/balance-inquiry?lang=id:token:\
+            c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65:2020-01-01T00:00:00+07:00",
+        signature: "EkRMtRobhYwuSliYug0RKKA6gibbrq8Y6/Jw/w1jkYoEaQjf60iApB/B0vCrkf5YqmajxvGl6ysep5tG8SBOGw==",
+    },
+    Vector {
+        profile: kamu_snap_response::QuirkProfile::LENIENT,
+        body_hash: "c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65",
+        string_to_sign: "POST:/v1.0/balance-inquiry?lang=id:token:\
+            c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65:2020-01-01T00:00:00+07:00",
+        signature: "EkRMtRobhYwuSliYug0RKKA6gibbrq8Y6/Jw/w1jkYoEaQjf60iApB/B0vCrkf5YqmajxvGl6ysep5tG8SBOGw==",
+    },
+    Vector {
+        profile: kamu_snap_response::QuirkProfile::RAW_BODY,
+        body_hash: "fd491096ff9fa9c77b07a1f2478f5ae7158dae171ad09ceb92d3c30d221e0ac4",
+        string_to_sign: "POST:/v1.0/balance-inquiry?lang=id:token:\
+            fd491096ff9fa9c77b07a1f2478f5ae7158dae171ad09ceb92d3c30d221e0ac4:2020-01-01T00:00:00+07:00",
+        signature: "OLKdCHsQJujzKsyi5IeOna+fnF0P6F4y6290d2fOrFovGd7fe1LZs0LP0/6DNncdpENGktaf7VSvgWLXns14Aw==",
+    },
+    Vector {
+        profile: kamu_snap_response::QuirkProfile::PATH_ONLY,
+        body_hash: "c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65",
+        string_to_sign: "POST:/v1.0/balance-inquiry:token:\
+            c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65:2020-01-01T00:00:00+07:00",
+        signature: "fnHsn2PZMw3Naqkmesaygo35qlpzB81CFl8ctAxbGp2vsNCG+3Cs4aODQd2rBRUYpBBX9ZZPDzAt52WVncr9aw==",
+    },
+];
+
+#[test]
+fn body_hash() {
+    for vector in VECTORS {
+        assert_eq!(
+            kamu_snap_crypto::string_to_sign::body_hash_with(&vector.profile, BODY),
+            vector.body_hash,
+            "{:?}",
+            vector.profile
+        );
+    }
+}
+
+#[test]
+fn symmetric_string_to_sign() {
+    for vector in VECTORS {
+        let string_to_sign = kamu_snap_crypto::string_to_sign::symmetric_with(
+            &vector.profile,
+            "post",
+            URL,
+            TOKEN,
+            BODY,
+            TIMESTAMP,
+        );
+
+        assert_eq!(string_to_sign, vector.string_to_sign, "{:?}", vector.profile);
+    }
+}
+
+#[test]
+fn symmetric_signature() {
+    let crypto = kamu_snap_crypto::SymmetricCrypto::create(SECRET).unwrap();
+
+    for vector in VECTORS {
+        assert_eq!(
+            crypto.sign(vector.string_to_sign),
+            vector.signature,
+            "{:?}",
+            vector.profile
+        );
+        crypto.verify(vector.signature, vector.string_to_sign).unwrap();
+    }
+}
+
+#[test]
+fn strict_is_the_default() {
+    assert_eq!(
+        kamu_snap_crypto::string_to_sign::symmetric("POST", URL, TOKEN, BODY, TIMESTAMP),
+        VECTORS[0].string_to_sign
+    );
+    assert_eq!(
+        kamu_snap_crypto::string_to_sign::asymmetric("POST", URL, BODY, TIMESTAMP),
+        "POST:/v1.0/balance-inquiry?lang=id:\
+            c6cc74edfc719c41da531d023cb1e6dac76e8dc9cc402658c651dfad63b68e65:2020-01-01T00:00:00+07:00"
+    );
+}
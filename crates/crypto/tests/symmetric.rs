@@ -0,0 +1,146 @@
+//! HMAC signing and verification through a shared instance, checked against
+//! the RFC 4231 vectors and the `hmac` crate for every key length class.
+
+const PAYLOAD: &str = "POST:/v1.0/qr/qr-mpm-generate:token:hash:2025-08-09T10:00:00+07:00";
+
+struct Vector {
+    key: &'static [u8],
+    data: &'static str,
+    sha256: &'static str,
+    sha512: &'static str,
+}
+
+const VECTORS: [Vector; 2] = [
+    // RFC 4231 test case 1
+    Vector {
+        key: &[0x0b; 20],
+        data: "Hi There",
+        sha256: "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        sha512: "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+    },
+    // RFC 4231 test case 6, a key longer than either block size
+    Vector {
+        key: &[0xaa; 131],
+        data: "Test Using Larger Than Block-Size Key - Hash Key First",
+        sha256: "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        sha512: "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+    },
+];
+
+fn crypto(key: &[u8], algorithm: kamu_snap_crypto::SymmetricAlgorithm) -> kamu_snap_crypto::SymmetricCrypto {
+    kamu_snap_crypto::SymmetricCrypto::from_slice(key)
+        .unwrap()
+        .with_algorithm(algorithm)
+        .with_encoding(kamu_snap_crypto::SignatureEncoding::Hex)
+}
+
+#[test]
+fn rfc4231_vectors() {
+    for vector in VECTORS {
+        for (algorithm, expected) in [
+            (kamu_snap_crypto::SymmetricAlgorithm::HmacSha256, vector.sha256),
+            (kamu_snap_crypto::SymmetricAlgorithm::HmacSha512, vector.sha512),
+        ] {
+            let crypto = crypto(vector.key, algorithm);
+
+            assert_eq!(crypto.sign(vector.data), expected, "{algorithm:?}");
+            crypto.verify(expected, vector.data).unwrap();
+        }
+    }
+}
+
+#[test]
+fn every_key_length() {
+    // Empty, shorter than, equal to and longer than both block sizes
+    for length in [0, 1, 63, 64, 65, 127, 128, 129, 300] {
+        let key = vec![0x5c; length];
+        let sha256 = {
+            let mut mac = <hmac::Hmac<sha2::Sha256> as hmac::Mac>::new_from_slice(&key).unwrap();
+            hmac::Mac::update(&mut mac, PAYLOAD.as_bytes());
+            hmac::Mac::finalize(mac).into_bytes().to_vec()
+        };
+        let sha512 = {
+            let mut mac = <hmac::Hmac<sha2::Sha512> as hmac::Mac>::new_from_slice(&key).unwrap();
+            hmac::Mac::update(&mut mac, PAYLOAD.as_bytes());
+            hmac::Mac::finalize(mac).into_bytes().to_vec()
+        };
+
+        assert_eq!(
+            crypto(&key, kamu_snap_crypto::SymmetricAlgorithm::HmacSha256).sign(PAYLOAD),
+            kamu_snap_crypto::SignatureEncoding::Hex.encode(sha256),
+            "{length}"
+        );
+        assert_eq!(
+            crypto(&key, kamu_snap_crypto::SymmetricAlgorithm::HmacSha512).sign(PAYLOAD),
+            kamu_snap_crypto::SignatureEncoding::Hex.encode(sha512),
+            "{length}"
+        );
+    }
+}
+
+#[test]
+fn sign_and_verify() {
+    let crypto = kamu_snap_crypto::SymmetricCrypto::create("client-secret").unwrap();
+    let signature = crypto.sign(PAYLOAD);
+
+    // Signing is deterministic and leaves the instance reusable
+    assert_eq!(crypto.sign(PAYLOAD), signature);
+    assert_eq!(
+        kamu_snap_crypto::SymmetricCrypto::sign_once("client-secret", PAYLOAD).unwrap(),
+        signature
+    );
+    crypto.verify(&signature, PAYLOAD).unwrap();
+
+    // Whitespace in the signature is ignored
+    let (head, tail) = signature.split_at(20);
+    crypto.verify(format!("{head}\n {tail}"), PAYLOAD).unwrap();
+
+    assert!(matches!(
+        crypto.verify(&signature, format!("{PAYLOAD} ")),
+        Err(kamu_snap_crypto::CryptoError::SignatureVerificationFailedSymmetric)
+    ));
+    assert!(matches!(
+        kamu_snap_crypto::SymmetricCrypto::create("other-secret")
+            .unwrap()
+            .verify(&signature, PAYLOAD),
+        Err(kamu_snap_crypto::CryptoError::SignatureVerificationFailedSymmetric)
+    ));
+    assert!(
+        crypto
+            .clone()
+            .with_algorithm(kamu_snap_crypto::SymmetricAlgorithm::HmacSha256)
+            .verify(&signature, PAYLOAD)
+            .is_err()
+    );
+}
+
+#[test]
+fn concurrent_use() {
+    let crypto = std::sync::Arc::new(kamu_snap_crypto::SymmetricCrypto::create("client-secret").unwrap());
+    let expected: Vec<_> = (0..8).map(|x| crypto.sign(format!("{PAYLOAD}:{x}"))).collect();
+
+    let threads: Vec<_> = (0..8)
+        .map(|x| {
+            let crypto = crypto.clone();
+
+            std::thread::spawn(move || {
+                let payload = format!("{PAYLOAD}:{x}");
+
+                (0..100)
+                    .map(|_| {
+                        let signature = crypto.sign(&payload);
+                        crypto.verify(&signature, &payload).unwrap();
+
+                        signature
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    for (x, thread) in threads.into_iter().enumerate() {
+        assert!(thread.join().unwrap().iter().all(|y| *y == expected[x]));
+    }
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Inconsistent request")]
+    InconsistentRequest,
+    #[error("Duplicate partnerReferenceNo")]
+    DuplicatePartnerReferenceNo,
+    #[error("Idempotency store failure: {0}")]
+    Store(String),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::InconsistentRequest => kamu_snap_response::ResponseError::InconsistentRequest,
+            Error::DuplicatePartnerReferenceNo => {
+                kamu_snap_response::ResponseError::DuplicatePartnerReferenceNo
+            }
+            Error::Store(_) => kamu_snap_response::ResponseError::InternalServerError,
+        }
+    }
+}
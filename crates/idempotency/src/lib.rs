@@ -0,0 +1,23 @@
+pub mod error;
+pub mod manager;
+pub mod middleware;
+pub mod store;
+
+pub use error::Error as IdempotencyError;
+pub use manager::{Check as IdempotencyCheck, Config as IdempotencyConfig, Manager as IdempotencyManager};
+pub use middleware::Idempotent;
+#[cfg(feature = "sqlite")]
+pub use store::SqliteStore as SqliteIdempotencyStore;
+pub use store::{
+    MemoryStore as MemoryIdempotencyStore, Record as IdempotencyRecord, Store as IdempotencyStore,
+    StoredResponse,
+};
+
+pub type Result<T> = core::result::Result<T, IdempotencyError>;
+
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,84 @@
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// How long a `partnerReferenceNo` is remembered
+    pub retention: std::time::Duration,
+    /// Replay the stored response for an exact duplicate instead of rejecting
+    /// it with `DuplicatePartnerReferenceNo`
+    pub replay: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            retention: std::time::Duration::from_secs(24 * 3600),
+            replay: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Check {
+    /// First time this request is seen, process it and then call
+    /// [`Manager::complete`] or [`Manager::release`] with the key
+    Proceed(String),
+    /// Exact duplicate of a request that already succeeded
+    Replay(crate::StoredResponse),
+}
+
+/// Enforces `partnerReferenceNo` uniqueness per partner. A request is
+/// fingerprinted as its `partnerReferenceNo` plus the hash of its minified
+/// body; only successful responses are kept, so a failed request may be sent
+/// again with the same reference.
+pub struct Manager<S: crate::IdempotencyStore> {
+    store: S,
+    config: Config,
+}
+
+impl<S: crate::IdempotencyStore> Manager<S> {
+    pub fn new(store: S, config: Config) -> Self {
+        Self { store, config }
+    }
+
+    pub fn key(partner_id: &str, partner_reference_no: &str) -> String {
+        format!("{partner_id}:{partner_reference_no}")
+    }
+
+    pub fn fingerprint<B: AsRef<[u8]>>(partner_reference_no: &str, body: B) -> String {
+        format!(
+            "{partner_reference_no}:{}",
+            kamu_snap_crypto::string_to_sign::body_hash(body)
+        )
+    }
+
+    pub fn begin<B: AsRef<[u8]>>(
+        &self,
+        partner_id: &str,
+        partner_reference_no: &str,
+        body: B,
+    ) -> crate::Result<Check> {
+        let key = Self::key(partner_id, partner_reference_no);
+        let fingerprint = Self::fingerprint(partner_reference_no, body);
+        let expired_before = crate::unix_now().saturating_sub(self.config.retention.as_secs());
+
+        let Some(record) = self.store.begin(&key, &fingerprint, expired_before)? else {
+            return Ok(Check::Proceed(key));
+        };
+
+        if record.fingerprint != fingerprint {
+            return Err(crate::IdempotencyError::InconsistentRequest);
+        }
+
+        match record.response {
+            Some(response) if self.config.replay => Ok(Check::Replay(response)),
+            _ => Err(crate::IdempotencyError::DuplicatePartnerReferenceNo),
+        }
+    }
+
+    pub fn complete(&self, key: &str, response: crate::StoredResponse) -> crate::Result<()> {
+        self.store.complete(key, response)
+    }
+
+    pub fn release(&self, key: &str) -> crate::Result<()> {
+        self.store.release(key)
+    }
+}
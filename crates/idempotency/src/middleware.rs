@@ -0,0 +1,211 @@
+//! Actix middleware enforcing `partnerReferenceNo` idempotency on a
+//! transaction endpoint:
+//!
+//! ```ignore
+//! let manager = Arc::new(IdempotencyManager::new(store, IdempotencyConfig::default()));
+//!
+//! web::resource("/v1.0/qr/qr-mpm-generate")
+//!     .wrap(Idempotent::new(manager.clone(), kamu_snap_model::qris::SERVICE_CODE_GENERATE))
+//!     .route(web::post().to(generate))
+//! ```
+//!
+//! Requests without `partnerReferenceNo` in their body pass through
+//! untouched. A replay answers with the status, headers, including any
+//! `X-TIMESTAMP` and `X-SIGNATURE`, and body of the original response.
+
+const FIELD_PARTNER_REFERENCE_NO: &str = "partnerReferenceNo";
+
+/// Response headers not replayed: set per connection, or echoed from the
+/// request being answered
+const TRANSIENT_HEADERS: [&str; 6] = [
+    "connection",
+    "content-length",
+    "date",
+    "transfer-encoding",
+    kamu_snap_response::header::X_EXTERNAL_ID,
+    kamu_snap_response::header::X_REQUEST_ID,
+];
+
+type LocalBoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T>>>;
+
+pub struct Idempotent<S: crate::IdempotencyStore> {
+    manager: std::sync::Arc<crate::IdempotencyManager<S>>,
+    service_code: u8,
+}
+
+impl<S: crate::IdempotencyStore> Idempotent<S> {
+    pub fn new(manager: std::sync::Arc<crate::IdempotencyManager<S>>, service_code: u8) -> Self {
+        Self {
+            manager,
+            service_code,
+        }
+    }
+}
+
+impl<S, N, B> actix_web::dev::Transform<N, actix_web::dev::ServiceRequest> for Idempotent<S>
+where
+    S: crate::IdempotencyStore + 'static,
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = Middleware<S, N>;
+    type InitError = ();
+    type Future = std::future::Ready<core::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: N) -> Self::Future {
+        std::future::ready(Ok(Middleware {
+            manager: self.manager.clone(),
+            service_code: self.service_code,
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub struct Middleware<S: crate::IdempotencyStore, N> {
+    manager: std::sync::Arc<crate::IdempotencyManager<S>>,
+    service_code: u8,
+    service: std::rc::Rc<N>,
+}
+
+impl<S, N, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for Middleware<S, N>
+where
+    S: crate::IdempotencyStore + 'static,
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<core::result::Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, mut request: actix_web::dev::ServiceRequest) -> Self::Future {
+        let manager = self.manager.clone();
+        let service_code = self.service_code;
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let body = request.extract::<actix_web::web::Bytes>().await?;
+            request.set_payload(actix_web::dev::Payload::from(body.clone()));
+
+            let key = match begin(&manager, &request, &body) {
+                Ok(Some(crate::IdempotencyCheck::Proceed(key))) => key,
+                Ok(Some(crate::IdempotencyCheck::Replay(response))) => {
+                    return Ok(replay(request, response));
+                }
+                Ok(None) => return service.call(request).await.map(|x| x.map_into_boxed_body()),
+                Err(error) => return Ok(reject(request, error, service_code)),
+            };
+
+            let response = match service.call(request).await {
+                Ok(response) if response.status().is_success() => response,
+                result => {
+                    release(&manager, &key);
+                    return result.map(|x| x.map_into_boxed_body());
+                }
+            };
+
+            let (request, response) = response.into_parts();
+            let (head, body) = response.into_parts();
+            let body = match actix_web::body::to_bytes(body).await {
+                Ok(body) => body,
+                Err(error) => {
+                    release(&manager, &key);
+                    return Err(actix_web::error::ErrorInternalServerError(error.into()));
+                }
+            };
+
+            let stored = crate::StoredResponse {
+                status: head.status().as_u16(),
+                headers: head
+                    .headers()
+                    .iter()
+                    .filter(|(name, _)| {
+                        !TRANSIENT_HEADERS
+                            .iter()
+                            .any(|x| name.as_str().eq_ignore_ascii_case(x))
+                    })
+                    .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+                    .collect(),
+                body: body.to_vec(),
+            };
+            if let Err(error) = manager.complete(&key, stored) {
+                tracing::error!(%key, %error, "failed to store idempotent response");
+                release(&manager, &key);
+            }
+
+            Ok(actix_web::dev::ServiceResponse::new(
+                request,
+                head.set_body(body).map_into_boxed_body(),
+            ))
+        })
+    }
+}
+
+/// `Ok(None)` when the request carries no `partnerReferenceNo`.
+fn begin<S: crate::IdempotencyStore>(
+    manager: &crate::IdempotencyManager<S>,
+    request: &actix_web::dev::ServiceRequest,
+    body: &[u8],
+) -> kamu_snap_response::Result<Option<crate::IdempotencyCheck>> {
+    let partner_reference_no = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|x| x.get(FIELD_PARTNER_REFERENCE_NO)?.as_str().map(str::to_owned));
+    let Some(partner_reference_no) = partner_reference_no else {
+        return Ok(None);
+    };
+
+    let partner_id = kamu_snap_response::header::get_mandatory(
+        request.headers(),
+        kamu_snap_response::header::X_PARTNER_ID,
+    )?;
+
+    Ok(Some(manager.begin(partner_id, &partner_reference_no, body)?))
+}
+
+/// Release the key of a request that did not succeed. A key that cannot be
+/// released blocks retries with the same `partnerReferenceNo` until it
+/// expires, so the failure is logged.
+fn release<S: crate::IdempotencyStore>(manager: &crate::IdempotencyManager<S>, key: &str) {
+    if let Err(error) = manager.release(key) {
+        tracing::error!(%key, %error, "failed to release idempotency key");
+    }
+}
+
+fn replay(
+    request: actix_web::dev::ServiceRequest,
+    response: crate::StoredResponse,
+) -> actix_web::dev::ServiceResponse<actix_web::body::BoxBody> {
+    let status = actix_web::http::StatusCode::from_u16(response.status).unwrap_or_default();
+    let mut builder = actix_web::HttpResponse::build(status);
+    builder.content_type(actix_web::http::header::ContentType::json());
+
+    for (name, value) in response.headers {
+        builder.insert_header((name, value));
+    }
+
+    request.into_response(builder.body(response.body))
+}
+
+fn reject(
+    request: actix_web::dev::ServiceRequest,
+    error: kamu_snap_response::ResponseError,
+    service_code: u8,
+) -> actix_web::dev::ServiceResponse<actix_web::body::BoxBody> {
+    let response = actix_web::Responder::respond_to(
+        kamu_snap_response::SNAPResponse::<serde_json::Value>::from_error(error, service_code),
+        request.request(),
+    );
+
+    request.into_response(response)
+}
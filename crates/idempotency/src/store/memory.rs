@@ -0,0 +1,92 @@
+/// Expired records are dropped at most once per interval, not on every
+/// request.
+const PURGE_INTERVAL_SECS: u64 = 60;
+
+#[derive(Debug, Default)]
+struct State {
+    records: std::collections::HashMap<String, (crate::IdempotencyRecord, u64)>,
+    purged_at: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Store {
+    state: std::sync::Mutex<State>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop the records created at or before `expired_before`, returning how
+    /// many there were.
+    pub fn purge(&self, expired_before: u64) -> usize {
+        let mut state = self.lock();
+        let before = state.records.len();
+        state
+            .records
+            .retain(|_, (_, created_at)| *created_at > expired_before);
+
+        before - state.records.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().records.is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl crate::IdempotencyStore for Store {
+    fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        expired_before: u64,
+    ) -> crate::Result<Option<crate::IdempotencyRecord>> {
+        let now = crate::unix_now();
+        let mut state = self.lock();
+
+        if now >= state.purged_at + PURGE_INTERVAL_SECS {
+            state
+                .records
+                .retain(|_, (_, created_at)| *created_at > expired_before);
+            state.purged_at = now;
+        }
+
+        match state.records.get(key) {
+            Some((record, created_at)) if *created_at > expired_before => Ok(Some(record.clone())),
+            _ => {
+                let record = crate::IdempotencyRecord {
+                    fingerprint: fingerprint.to_owned(),
+                    response: None,
+                };
+                state.records.insert(key.to_owned(), (record, now));
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn complete(&self, key: &str, response: crate::StoredResponse) -> crate::Result<()> {
+        if let Some((record, _)) = self.lock().records.get_mut(key) {
+            record.response = Some(response);
+        }
+
+        Ok(())
+    }
+
+    fn release(&self, key: &str) -> crate::Result<()> {
+        self.lock().records.remove(key);
+
+        Ok(())
+    }
+}
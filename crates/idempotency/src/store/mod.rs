@@ -0,0 +1,48 @@
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::Store as MemoryStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::Store as SqliteStore;
+
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub status: u16,
+    /// Headers replayed with the body, e.g. `Content-Type`, `X-TIMESTAMP`
+    /// and `X-SIGNATURE`
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub fingerprint: String,
+    /// `None` while the original request is still being processed
+    pub response: Option<StoredResponse>,
+}
+
+pub trait Store: Send + Sync {
+    /// Atomically return the live record of `key`, or claim the key with an
+    /// in-progress record when there is none. Records created at or before
+    /// `expired_before` (seconds since the Unix epoch) are ignored.
+    fn begin(&self, key: &str, fingerprint: &str, expired_before: u64) -> crate::Result<Option<Record>>;
+
+    fn complete(&self, key: &str, response: StoredResponse) -> crate::Result<()>;
+
+    fn release(&self, key: &str) -> crate::Result<()>;
+}
+
+impl<S: Store + ?Sized> Store for std::sync::Arc<S> {
+    fn begin(&self, key: &str, fingerprint: &str, expired_before: u64) -> crate::Result<Option<Record>> {
+        (**self).begin(key, fingerprint, expired_before)
+    }
+
+    fn complete(&self, key: &str, response: StoredResponse) -> crate::Result<()> {
+        (**self).complete(key, response)
+    }
+
+    fn release(&self, key: &str) -> crate::Result<()> {
+        (**self).release(key)
+    }
+}
@@ -0,0 +1,119 @@
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS snap_idempotency (
+    key TEXT PRIMARY KEY,
+    fingerprint TEXT NOT NULL,
+    status INTEGER,
+    headers TEXT,
+    body BLOB,
+    created_at INTEGER NOT NULL
+);
+"#;
+
+fn store_error(error: rusqlite::Error) -> crate::IdempotencyError {
+    crate::IdempotencyError::Store(error.to_string())
+}
+
+pub struct Store {
+    connection: std::sync::Mutex<rusqlite::Connection>,
+}
+
+impl Store {
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open(path).map_err(store_error)?)
+    }
+
+    pub fn open_in_memory() -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open_in_memory().map_err(store_error)?)
+    }
+
+    pub fn from_connection(connection: rusqlite::Connection) -> crate::Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+
+        Ok(Self {
+            connection: std::sync::Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl crate::IdempotencyStore for Store {
+    fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        expired_before: u64,
+    ) -> crate::Result<Option<crate::IdempotencyRecord>> {
+        let mut connection = self.lock();
+        let transaction = connection
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(store_error)?;
+        transaction
+            .execute(
+                "DELETE FROM snap_idempotency WHERE created_at <= ?1",
+                [expired_before],
+            )
+            .map_err(store_error)?;
+
+        let record = rusqlite::OptionalExtension::optional(transaction.query_row(
+            "SELECT fingerprint, status, headers, body FROM snap_idempotency WHERE key = ?1",
+            [key],
+            |row| {
+                let status: Option<u16> = row.get(1)?;
+                let headers: Option<String> = row.get(2)?;
+                let body: Option<Vec<u8>> = row.get(3)?;
+
+                Ok(crate::IdempotencyRecord {
+                    fingerprint: row.get(0)?,
+                    response: status.map(|status| crate::StoredResponse {
+                        status,
+                        headers: headers
+                            .and_then(|x| serde_json::from_str(&x).ok())
+                            .unwrap_or_default(),
+                        body: body.unwrap_or_default(),
+                    }),
+                })
+            },
+        ))
+        .map_err(store_error)?;
+
+        if record.is_none() {
+            transaction
+                .execute(
+                    "INSERT INTO snap_idempotency (key, fingerprint, created_at) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![key, fingerprint, crate::unix_now()],
+                )
+                .map_err(store_error)?;
+        }
+
+        transaction.commit().map_err(store_error)?;
+
+        Ok(record)
+    }
+
+    fn complete(&self, key: &str, response: crate::StoredResponse) -> crate::Result<()> {
+        let headers = serde_json::to_string(&response.headers)
+            .map_err(|x| crate::IdempotencyError::Store(x.to_string()))?;
+
+        self.lock()
+            .execute(
+                "UPDATE snap_idempotency SET status = ?2, headers = ?3, body = ?4 WHERE key = ?1",
+                rusqlite::params![key, response.status, headers, response.body],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn release(&self, key: &str) -> crate::Result<()> {
+        self.lock()
+            .execute("DELETE FROM snap_idempotency WHERE key = ?1", [key])
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! `partnerReferenceNo` replay, in-flight duplicates and release, through the
+//! manager and the middleware.
+
+const PARTNER_ID: &str = "partner-1";
+/// QRIS MPM generate
+const SERVICE_CODE: u8 = 47;
+const PATH: &str = "/v1.0/qr/qr-mpm-generate";
+const BODY: &str = r#"{"partnerReferenceNo":"ref-1","amount":{"value":"10000.00","currency":"IDR"}}"#;
+const OTHER_BODY: &str = r#"{"partnerReferenceNo":"ref-1","amount":{"value":"20000.00","currency":"IDR"}}"#;
+
+type Manager<S> = kamu_snap_idempotency::IdempotencyManager<S>;
+
+fn proceed<S: kamu_snap_idempotency::IdempotencyStore>(manager: &Manager<S>, body: &str) -> String {
+    match manager.begin(PARTNER_ID, "ref-1", body).unwrap() {
+        kamu_snap_idempotency::IdempotencyCheck::Proceed(key) => key,
+        check => panic!("unexpected {check:?}"),
+    }
+}
+
+fn manager_lifecycle<S: kamu_snap_idempotency::IdempotencyStore>(store: S) {
+    let manager = Manager::new(store, Default::default());
+    let key = proceed(&manager, BODY);
+
+    // Still in flight
+    assert!(matches!(
+        manager.begin(PARTNER_ID, "ref-1", BODY),
+        Err(kamu_snap_idempotency::IdempotencyError::DuplicatePartnerReferenceNo)
+    ));
+    assert!(matches!(
+        manager.begin(PARTNER_ID, "ref-1", OTHER_BODY),
+        Err(kamu_snap_idempotency::IdempotencyError::InconsistentRequest)
+    ));
+
+    manager
+        .complete(
+            &key,
+            kamu_snap_idempotency::StoredResponse {
+                status: 200,
+                headers: vec![("x-signature".to_owned(), "signature".to_owned())],
+                body: b"{}".to_vec(),
+            },
+        )
+        .unwrap();
+    match manager.begin(PARTNER_ID, "ref-1", BODY).unwrap() {
+        kamu_snap_idempotency::IdempotencyCheck::Replay(response) => {
+            assert_eq!(
+                (response.status, response.body.as_slice()),
+                (200, b"{}".as_slice())
+            );
+            assert_eq!(
+                response.headers,
+                [("x-signature".to_owned(), "signature".to_owned())]
+            );
+        }
+        check => panic!("unexpected {check:?}"),
+    }
+
+    // Same reference from another partner is unrelated
+    assert!(matches!(
+        manager.begin("partner-2", "ref-1", BODY).unwrap(),
+        kamu_snap_idempotency::IdempotencyCheck::Proceed(_)
+    ));
+}
+
+fn manager_release<S: kamu_snap_idempotency::IdempotencyStore>(store: S) {
+    let manager = Manager::new(store, Default::default());
+    let key = proceed(&manager, BODY);
+
+    manager.release(&key).unwrap();
+    proceed(&manager, OTHER_BODY);
+}
+
+fn manager_expiry<S: kamu_snap_idempotency::IdempotencyStore>(store: S) {
+    let manager = Manager::new(
+        store,
+        kamu_snap_idempotency::IdempotencyConfig {
+            retention: std::time::Duration::ZERO,
+            ..Default::default()
+        },
+    );
+
+    proceed(&manager, BODY);
+    proceed(&manager, OTHER_BODY);
+}
+
+#[test]
+fn memory_store() {
+    manager_lifecycle(kamu_snap_idempotency::MemoryIdempotencyStore::new());
+    manager_release(kamu_snap_idempotency::MemoryIdempotencyStore::new());
+    manager_expiry(kamu_snap_idempotency::MemoryIdempotencyStore::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_store() {
+    manager_lifecycle(kamu_snap_idempotency::SqliteIdempotencyStore::open_in_memory().unwrap());
+    manager_release(kamu_snap_idempotency::SqliteIdempotencyStore::open_in_memory().unwrap());
+    manager_expiry(kamu_snap_idempotency::SqliteIdempotencyStore::open_in_memory().unwrap());
+}
+
+#[test]
+fn memory_store_purge() {
+    let store = kamu_snap_idempotency::MemoryIdempotencyStore::new();
+    kamu_snap_idempotency::IdempotencyStore::begin(&store, "a", "a", 0).unwrap();
+    kamu_snap_idempotency::IdempotencyStore::begin(&store, "b", "b", 0).unwrap();
+
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.purge(0), 0);
+    assert_eq!(store.purge(u64::MAX), 2);
+    assert!(store.is_empty());
+}
+
+/// Counts its calls and answers with the status given in the `status` query
+/// parameter, signed per call.
+async fn handler(
+    query: actix_web::web::Query<std::collections::HashMap<String, u16>>,
+    calls: actix_web::web::Data<std::sync::atomic::AtomicUsize>,
+) -> actix_web::HttpResponse {
+    let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+    let status = query.get("status").copied().unwrap_or(200);
+
+    actix_web::HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap())
+        .insert_header((
+            kamu_snap_response::header::X_SIGNATURE,
+            format!("signature-{call}"),
+        ))
+        .json(serde_json::json!({ "responseCode": format!("{status}4700"), "call": call }))
+}
+
+fn request(body: &str, status: u16) -> actix_web::test::TestRequest {
+    actix_web::test::TestRequest::post()
+        .uri(&format!("{PATH}?status={status}"))
+        .insert_header((kamu_snap_response::header::X_PARTNER_ID, PARTNER_ID))
+        .set_payload(body.to_owned())
+}
+
+macro_rules! service {
+    ($manager:expr, $calls:expr) => {
+        actix_web::test::init_service(
+            actix_web::App::new().app_data($calls.clone()).service(
+                actix_web::web::resource(PATH)
+                    .wrap(kamu_snap_idempotency::Idempotent::new(
+                        $manager.clone(),
+                        SERVICE_CODE,
+                    ))
+                    .route(actix_web::web::post().to(handler)),
+            ),
+        )
+        .await
+    };
+}
+
+macro_rules! call {
+    ($service:expr, $request:expr) => {{
+        let response = actix_web::test::call_service(&$service, $request.to_request()).await;
+        let status = response.status().as_u16();
+
+        (
+            status,
+            actix_web::test::read_body_json::<serde_json::Value, _>(response).await,
+        )
+    }};
+}
+
+#[actix_web::test]
+async fn middleware_replays_success() {
+    let manager = std::sync::Arc::new(Manager::new(
+        kamu_snap_idempotency::MemoryIdempotencyStore::new(),
+        Default::default(),
+    ));
+    let calls = actix_web::web::Data::new(std::sync::atomic::AtomicUsize::new(0));
+    let service = service!(manager, calls);
+
+    let (status, first) = call!(service, request(BODY, 200));
+    assert_eq!((status, first["call"].as_u64()), (200, Some(1)));
+
+    let response = actix_web::test::call_service(&service, request(BODY, 200).to_request()).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get(kamu_snap_response::header::X_SIGNATURE)
+            .unwrap(),
+        "signature-1"
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get(actix_web::http::header::CONTENT_TYPE)
+            .unwrap(),
+        "application/json"
+    );
+    let replayed: serde_json::Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(replayed, first);
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    let (status, rejected) = call!(service, request(OTHER_BODY, 200));
+    assert_eq!(status, 404);
+    assert_eq!(rejected["responseCode"], "4044718");
+}
+
+#[actix_web::test]
+async fn middleware_rejects_in_flight_duplicate() {
+    let manager = std::sync::Arc::new(Manager::new(
+        kamu_snap_idempotency::MemoryIdempotencyStore::new(),
+        Default::default(),
+    ));
+    let calls = actix_web::web::Data::new(std::sync::atomic::AtomicUsize::new(0));
+    let service = service!(manager, calls);
+    // The original request is still being processed
+    proceed(&manager, BODY);
+
+    let (status, body) = call!(service, request(BODY, 200));
+    assert_eq!(status, 409);
+    assert_eq!(body["responseCode"], "4094701");
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn middleware_releases_failure() {
+    let manager = std::sync::Arc::new(Manager::new(
+        kamu_snap_idempotency::MemoryIdempotencyStore::new(),
+        Default::default(),
+    ));
+    let calls = actix_web::web::Data::new(std::sync::atomic::AtomicUsize::new(0));
+    let service = service!(manager, calls);
+
+    let (status, _) = call!(service, request(BODY, 500));
+    assert_eq!(status, 500);
+
+    let (status, body) = call!(service, request(BODY, 200));
+    assert_eq!((status, body["call"].as_u64()), (200, Some(2)));
+}
+
+#[actix_web::test]
+async fn middleware_ignores_requests_without_reference() {
+    let manager = std::sync::Arc::new(Manager::new(
+        kamu_snap_idempotency::MemoryIdempotencyStore::new(),
+        Default::default(),
+    ));
+    let calls = actix_web::web::Data::new(std::sync::atomic::AtomicUsize::new(0));
+    let service = service!(manager, calls);
+
+    for _ in 0..2 {
+        let (status, _) = call!(service, request(r#"{"amount":"1"}"#, 200));
+        assert_eq!(status, 200);
+    }
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
@@ -0,0 +1,227 @@
+/// Limits in minor units (cents) of `currency`, `None` when unlimited.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    pub currency: String,
+    pub per_transaction: Option<i64>,
+    pub merchant_daily: Option<i64>,
+    pub account_daily: Option<i64>,
+}
+
+impl Limits {
+    pub fn new<C: Into<String>>(currency: C) -> Self {
+        Self {
+            currency: currency.into(),
+            per_transaction: None,
+            merchant_daily: None,
+            account_daily: None,
+        }
+    }
+
+    pub fn idr() -> Self {
+        Self::new("IDR")
+    }
+
+    pub fn with_per_transaction(mut self, limit: i64) -> Self {
+        self.per_transaction = Some(limit);
+
+        self
+    }
+
+    pub fn with_merchant_daily(mut self, limit: i64) -> Self {
+        self.merchant_daily = Some(limit);
+
+        self
+    }
+
+    pub fn with_account_daily(mut self, limit: i64) -> Self {
+        self.account_daily = Some(limit);
+
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Transaction<'a> {
+    pub amount: &'a kamu_snap_model::Amount,
+    /// Partner or merchant the transaction is made through
+    pub merchant_id: &'a str,
+    /// Customer account debited or credited, if any
+    pub account_id: Option<&'a str>,
+    /// Amount, in minor units, a single purchase with the customer token
+    /// paying for the transaction may not exceed, as set through
+    /// `kamu_snap_token::CustomerTokenManager::set_limit`
+    pub token_limit: Option<i64>,
+}
+
+impl<'a> Transaction<'a> {
+    pub fn new(amount: &'a kamu_snap_model::Amount, merchant_id: &'a str) -> Self {
+        Self {
+            amount,
+            merchant_id,
+            account_id: None,
+            token_limit: None,
+        }
+    }
+
+    pub fn with_account(mut self, account_id: &'a str) -> Self {
+        self.account_id = Some(account_id);
+
+        self
+    }
+
+    pub fn with_token_limit(mut self, limit: i64) -> Self {
+        self.token_limit = Some(limit);
+
+        self
+    }
+}
+
+/// A transaction counted towards the daily totals, to be released when it
+/// fails or is reversed.
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    pub day: String,
+    pub amount: i64,
+    pub merchant_id: String,
+    pub account_id: Option<String>,
+}
+
+/// Evaluates per-transaction and daily limits, the latter reset at midnight
+/// WIB. Merchants without their own limits get the default ones.
+pub struct Engine<L: crate::Ledger> {
+    ledger: L,
+    default: Limits,
+    merchants: std::collections::HashMap<String, Limits>,
+    purged_before: std::sync::Mutex<String>,
+}
+
+impl<L: crate::Ledger> Engine<L> {
+    pub fn new(ledger: L, default: Limits) -> Self {
+        Self {
+            ledger,
+            default,
+            merchants: std::collections::HashMap::new(),
+            purged_before: std::sync::Mutex::new(String::new()),
+        }
+    }
+
+    pub fn with_merchant<M: Into<String>>(mut self, merchant_id: M, limits: Limits) -> Self {
+        self.merchants.insert(merchant_id.into(), limits);
+
+        self
+    }
+
+    pub fn limits(&self, merchant_id: &str) -> &Limits {
+        self.merchants.get(merchant_id).unwrap_or(&self.default)
+    }
+
+    /// Evaluate `transaction` without counting it.
+    pub fn check(&self, transaction: &Transaction<'_>) -> crate::Result<()> {
+        let (amount, usages) = self.evaluate(transaction)?;
+        let day = crate::wib_day(chrono::Utc::now());
+
+        for usage in usages {
+            let total = self.ledger.total(&day, usage.counter, usage.id)?;
+
+            if usage
+                .limit
+                .is_some_and(|limit| total.saturating_add(amount) > limit)
+            {
+                return Err(usage.counter.exceeded());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate `transaction` and count it towards today's totals.
+    pub fn reserve(&self, transaction: &Transaction<'_>) -> crate::Result<Reservation> {
+        let (amount, usages) = self.evaluate(transaction)?;
+        let day = crate::wib_day(chrono::Utc::now());
+        self.purge(&day)?;
+
+        if let Some(counter) = self.ledger.add(&day, amount, &usages)? {
+            return Err(counter.exceeded());
+        }
+
+        Ok(Reservation {
+            day,
+            amount,
+            merchant_id: transaction.merchant_id.to_owned(),
+            account_id: transaction.account_id.map(str::to_owned),
+        })
+    }
+
+    pub fn release(&self, reservation: &Reservation) -> crate::Result<()> {
+        self.ledger.subtract(
+            &reservation.day,
+            reservation.amount,
+            crate::Counter::Merchant,
+            &reservation.merchant_id,
+        )?;
+
+        if let Some(account_id) = &reservation.account_id {
+            self.ledger.subtract(
+                &reservation.day,
+                reservation.amount,
+                crate::Counter::Account,
+                account_id,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The amount in minor units and the daily totals it counts towards,
+    /// once the per-transaction and token limits are satisfied.
+    fn evaluate<'a>(&self, transaction: &Transaction<'a>) -> crate::Result<(i64, Vec<crate::Usage<'a>>)> {
+        let limits = self.limits(transaction.merchant_id);
+        let amount = transaction
+            .amount
+            .to_minor_units()
+            .map_err(crate::LimitError::Response)?;
+
+        if amount <= 0 || transaction.amount.currency != limits.currency {
+            return Err(crate::LimitError::InvalidAmount);
+        }
+
+        if limits.per_transaction.is_some_and(|limit| amount > limit) {
+            return Err(crate::LimitError::ExceedsTransactionAmountLimit);
+        }
+
+        if transaction.token_limit.is_some_and(|limit| amount > limit) {
+            return Err(crate::LimitError::TokenLimitExceeded);
+        }
+
+        let mut usages = vec![crate::Usage {
+            counter: crate::Counter::Merchant,
+            id: transaction.merchant_id,
+            limit: limits.merchant_daily,
+        }];
+
+        if let Some(account_id) = transaction.account_id {
+            usages.push(crate::Usage {
+                counter: crate::Counter::Account,
+                id: account_id,
+                limit: limits.account_daily,
+            });
+        }
+
+        Ok((amount, usages))
+    }
+
+    /// Drop previous days' totals once per day.
+    fn purge(&self, day: &str) -> crate::Result<()> {
+        let mut purged_before = self
+            .purged_before
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if purged_before.as_str() != day {
+            self.ledger.purge_before(day)?;
+            *purged_before = day.to_owned();
+        }
+
+        Ok(())
+    }
+}
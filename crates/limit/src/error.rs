@@ -0,0 +1,34 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Exceeds Transaction Amount Limit")]
+    ExceedsTransactionAmountLimit,
+    #[error("Merchant Limit Exceed")]
+    MerchantLimitExceed,
+    #[error("Account Limit Exceed")]
+    AccountLimitExceed,
+    #[error("Token Limit Exceeded")]
+    TokenLimitExceeded,
+    #[error("Invalid Amount")]
+    InvalidAmount,
+    #[error("Ledger failure: {0}")]
+    Store(String),
+    #[error("{0}")]
+    Response(kamu_snap_response::ResponseError),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::ExceedsTransactionAmountLimit => {
+                kamu_snap_response::ResponseError::ExceedsTransactionAmountLimit
+            }
+            Error::MerchantLimitExceed => kamu_snap_response::ResponseError::MerchantLimitExceed,
+            Error::AccountLimitExceed => kamu_snap_response::ResponseError::AccountLimitExceed,
+            Error::TokenLimitExceeded => kamu_snap_response::ResponseError::TokenLimitExceeded,
+            Error::InvalidAmount => kamu_snap_response::ResponseError::InvalidAmount,
+            Error::Store(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Response(x) => x,
+        }
+    }
+}
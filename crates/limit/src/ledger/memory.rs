@@ -0,0 +1,76 @@
+type Key = (String, crate::Counter, String);
+
+#[derive(Debug, Default)]
+pub struct Ledger {
+    totals: std::sync::Mutex<std::collections::HashMap<Key, i64>>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, std::collections::HashMap<Key, i64>> {
+        self.totals
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+fn key(day: &str, counter: crate::Counter, id: &str) -> Key {
+    (day.to_owned(), counter, id.to_owned())
+}
+
+impl crate::Ledger for Ledger {
+    fn total(&self, day: &str, counter: crate::Counter, id: &str) -> crate::Result<i64> {
+        Ok(self
+            .lock()
+            .get(&key(day, counter, id))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    fn add(
+        &self,
+        day: &str,
+        amount: i64,
+        usages: &[crate::Usage<'_>],
+    ) -> crate::Result<Option<crate::Counter>> {
+        let mut totals = self.lock();
+
+        for usage in usages {
+            let total = totals
+                .get(&key(day, usage.counter, usage.id))
+                .copied()
+                .unwrap_or_default();
+
+            if usage
+                .limit
+                .is_some_and(|limit| total.saturating_add(amount) > limit)
+            {
+                return Ok(Some(usage.counter));
+            }
+        }
+
+        for usage in usages {
+            let total = totals.entry(key(day, usage.counter, usage.id)).or_default();
+            *total = total.saturating_add(amount);
+        }
+
+        Ok(None)
+    }
+
+    fn subtract(&self, day: &str, amount: i64, counter: crate::Counter, id: &str) -> crate::Result<()> {
+        if let Some(total) = self.lock().get_mut(&key(day, counter, id)) {
+            *total = total.saturating_sub(amount).max(0);
+        }
+
+        Ok(())
+    }
+
+    fn purge_before(&self, day: &str) -> crate::Result<()> {
+        self.lock().retain(|(x, _, _), _| x.as_str() >= day);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::Ledger as MemoryLedger;
+#[cfg(feature = "sqlite")]
+pub use sqlite::Ledger as SqliteLedger;
+
+/// Daily running total a transaction counts towards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Counter {
+    Merchant,
+    Account,
+}
+
+impl Counter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Counter::Merchant => "merchant",
+            Counter::Account => "account",
+        }
+    }
+
+    pub(crate) fn exceeded(&self) -> crate::LimitError {
+        match self {
+            Counter::Merchant => crate::LimitError::MerchantLimitExceed,
+            Counter::Account => crate::LimitError::AccountLimitExceed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Usage<'a> {
+    pub counter: Counter,
+    pub id: &'a str,
+    /// Daily limit in minor units, `None` when unlimited
+    pub limit: Option<i64>,
+}
+
+/// Daily totals in minor units, keyed by WIB day (`YYYY-MM-DD`).
+pub trait Ledger: Send + Sync {
+    fn total(&self, day: &str, counter: Counter, id: &str) -> crate::Result<i64>;
+
+    /// Atomically add `amount` to the total of every usage, unless that
+    /// would take one over its limit. Nothing is added then and the first
+    /// counter over its limit is returned.
+    fn add(&self, day: &str, amount: i64, usages: &[Usage<'_>]) -> crate::Result<Option<Counter>>;
+
+    /// Take `amount` back off a total, never below zero.
+    fn subtract(&self, day: &str, amount: i64, counter: Counter, id: &str) -> crate::Result<()>;
+
+    /// Drop the totals of every day before `day`.
+    fn purge_before(&self, day: &str) -> crate::Result<()>;
+}
+
+impl<L: Ledger + ?Sized> Ledger for std::sync::Arc<L> {
+    fn total(&self, day: &str, counter: Counter, id: &str) -> crate::Result<i64> {
+        (**self).total(day, counter, id)
+    }
+
+    fn add(&self, day: &str, amount: i64, usages: &[Usage<'_>]) -> crate::Result<Option<Counter>> {
+        (**self).add(day, amount, usages)
+    }
+
+    fn subtract(&self, day: &str, amount: i64, counter: Counter, id: &str) -> crate::Result<()> {
+        (**self).subtract(day, amount, counter, id)
+    }
+
+    fn purge_before(&self, day: &str) -> crate::Result<()> {
+        (**self).purge_before(day)
+    }
+}
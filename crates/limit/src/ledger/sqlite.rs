@@ -0,0 +1,118 @@
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS snap_limit_usage (
+    day TEXT NOT NULL,
+    counter TEXT NOT NULL,
+    id TEXT NOT NULL,
+    total INTEGER NOT NULL,
+    PRIMARY KEY (day, counter, id)
+);
+"#;
+
+fn store_error(error: rusqlite::Error) -> crate::LimitError {
+    crate::LimitError::Store(error.to_string())
+}
+
+fn get_total(
+    connection: &rusqlite::Connection,
+    day: &str,
+    counter: crate::Counter,
+    id: &str,
+) -> rusqlite::Result<i64> {
+    rusqlite::OptionalExtension::optional(connection.query_row(
+        "SELECT total FROM snap_limit_usage WHERE day = ?1 AND counter = ?2 AND id = ?3",
+        [day, counter.as_str(), id],
+        |row| row.get(0),
+    ))
+    .map(Option::unwrap_or_default)
+}
+
+pub struct Ledger {
+    connection: std::sync::Mutex<rusqlite::Connection>,
+}
+
+impl Ledger {
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open(path).map_err(store_error)?)
+    }
+
+    pub fn open_in_memory() -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open_in_memory().map_err(store_error)?)
+    }
+
+    pub fn from_connection(connection: rusqlite::Connection) -> crate::Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+
+        Ok(Self {
+            connection: std::sync::Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl crate::Ledger for Ledger {
+    fn total(&self, day: &str, counter: crate::Counter, id: &str) -> crate::Result<i64> {
+        get_total(&self.lock(), day, counter, id).map_err(store_error)
+    }
+
+    fn add(
+        &self,
+        day: &str,
+        amount: i64,
+        usages: &[crate::Usage<'_>],
+    ) -> crate::Result<Option<crate::Counter>> {
+        let mut connection = self.lock();
+        let transaction = connection
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(store_error)?;
+
+        for usage in usages {
+            let total = get_total(&transaction, day, usage.counter, usage.id).map_err(store_error)?;
+
+            if usage
+                .limit
+                .is_some_and(|limit| total.saturating_add(amount) > limit)
+            {
+                return Ok(Some(usage.counter));
+            }
+        }
+
+        for usage in usages {
+            transaction
+                .execute(
+                    "INSERT INTO snap_limit_usage (day, counter, id, total) VALUES (?1, ?2, ?3, ?4) \
+                     ON CONFLICT (day, counter, id) DO UPDATE SET total = total + excluded.total",
+                    rusqlite::params![day, usage.counter.as_str(), usage.id, amount],
+                )
+                .map_err(store_error)?;
+        }
+
+        transaction.commit().map_err(store_error)?;
+
+        Ok(None)
+    }
+
+    fn subtract(&self, day: &str, amount: i64, counter: crate::Counter, id: &str) -> crate::Result<()> {
+        self.lock()
+            .execute(
+                "UPDATE snap_limit_usage SET total = MAX(total - ?4, 0) \
+                 WHERE day = ?1 AND counter = ?2 AND id = ?3",
+                rusqlite::params![day, counter.as_str(), id, amount],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn purge_before(&self, day: &str) -> crate::Result<()> {
+        self.lock()
+            .execute("DELETE FROM snap_limit_usage WHERE day < ?1", [day])
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+pub mod engine;
+pub mod error;
+pub mod ledger;
+
+pub use engine::{Engine as LimitEngine, Limits, Reservation, Transaction};
+pub use error::Error as LimitError;
+#[cfg(feature = "sqlite")]
+pub use ledger::SqliteLedger;
+pub use ledger::{Counter, Ledger, MemoryLedger, Usage};
+
+pub type Result<T> = core::result::Result<T, LimitError>;
+
+/// Calendar day in WIB (UTC+7) as `YYYY-MM-DD`, the boundary daily limits
+/// reset on.
+pub fn wib_day(timestamp: chrono::DateTime<chrono::Utc>) -> String {
+    timestamp
+        .with_timezone(&kamu_snap_response::header::wib())
+        .format("%Y-%m-%d")
+        .to_string()
+}
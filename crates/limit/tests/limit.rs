@@ -0,0 +1,206 @@
+//! Per-transaction and daily limits, reservations and the WIB day boundary.
+
+const MERCHANT_ID: &str = "merchant-1";
+
+type Engine<L> = kamu_snap_limit::LimitEngine<std::sync::Arc<L>>;
+
+fn engine<L: kamu_snap_limit::Ledger>(ledger: &std::sync::Arc<L>) -> Engine<L> {
+    kamu_snap_limit::LimitEngine::new(
+        ledger.clone(),
+        kamu_snap_limit::Limits::idr()
+            .with_per_transaction(100_000)
+            .with_merchant_daily(250_000)
+            .with_account_daily(150_000),
+    )
+}
+
+fn today() -> String {
+    kamu_snap_limit::wib_day(chrono::Utc::now())
+}
+
+fn utc(value: &str) -> chrono::DateTime<chrono::Utc> {
+    value.parse().unwrap()
+}
+
+fn per_transaction<L: kamu_snap_limit::Ledger>(ledger: L) {
+    let ledger = std::sync::Arc::new(ledger);
+    let engine = engine(&ledger);
+    let check = |value: &str, currency: &str| {
+        engine.check(&kamu_snap_limit::Transaction::new(
+            &kamu_snap_model::Amount::new(value, currency),
+            MERCHANT_ID,
+        ))
+    };
+
+    check("1000.00", "IDR").unwrap();
+    assert!(matches!(
+        check("1000.01", "IDR"),
+        Err(kamu_snap_limit::LimitError::ExceedsTransactionAmountLimit)
+    ));
+    assert!(matches!(
+        check("0.00", "IDR"),
+        Err(kamu_snap_limit::LimitError::InvalidAmount)
+    ));
+    assert!(matches!(
+        check("10.00", "USD"),
+        Err(kamu_snap_limit::LimitError::InvalidAmount)
+    ));
+}
+
+fn reserve_release<L: kamu_snap_limit::Ledger>(ledger: L) {
+    let ledger = std::sync::Arc::new(ledger);
+    let engine = engine(&ledger);
+    let amount = kamu_snap_model::Amount::idr("1000.00");
+    let transaction = kamu_snap_limit::Transaction::new(&amount, MERCHANT_ID).with_account("account-1");
+    let merchant_total = || {
+        ledger
+            .total(&today(), kamu_snap_limit::Counter::Merchant, MERCHANT_ID)
+            .unwrap()
+    };
+
+    let reservation = engine.reserve(&transaction).unwrap();
+    assert_eq!(
+        (reservation.day.as_str(), reservation.amount),
+        (today().as_str(), 100_000)
+    );
+    assert_eq!(merchant_total(), 100_000);
+
+    // 2000.00 of the account's 1500.00
+    assert!(matches!(
+        engine.check(&transaction),
+        Err(kamu_snap_limit::LimitError::AccountLimitExceed)
+    ));
+    assert!(matches!(
+        engine.reserve(&transaction),
+        Err(kamu_snap_limit::LimitError::AccountLimitExceed)
+    ));
+    // The rejected reservation counted for neither total
+    assert_eq!(merchant_total(), 100_000);
+
+    engine.release(&reservation).unwrap();
+    assert_eq!(merchant_total(), 0);
+    engine.reserve(&transaction).unwrap();
+}
+
+fn merchant_daily<L: kamu_snap_limit::Ledger>(ledger: L) {
+    let ledger = std::sync::Arc::new(ledger);
+    let engine = engine(&ledger).with_merchant(
+        "merchant-2",
+        kamu_snap_limit::Limits::idr().with_merchant_daily(50_000),
+    );
+    let amount = kamu_snap_model::Amount::idr("1000.00");
+
+    for account_id in ["account-1", "account-2"] {
+        engine
+            .reserve(&kamu_snap_limit::Transaction::new(&amount, MERCHANT_ID).with_account(account_id))
+            .unwrap();
+    }
+    assert!(matches!(
+        engine.reserve(&kamu_snap_limit::Transaction::new(&amount, MERCHANT_ID).with_account("account-3")),
+        Err(kamu_snap_limit::LimitError::MerchantLimitExceed)
+    ));
+    // Up to the limit exactly
+    engine
+        .reserve(&kamu_snap_limit::Transaction::new(
+            &kamu_snap_model::Amount::idr("500.00"),
+            MERCHANT_ID,
+        ))
+        .unwrap();
+
+    // Own limits, no per-transaction limit
+    assert!(matches!(
+        engine.reserve(&kamu_snap_limit::Transaction::new(&amount, "merchant-2")),
+        Err(kamu_snap_limit::LimitError::MerchantLimitExceed)
+    ));
+}
+
+fn token_limit<L: kamu_snap_limit::Ledger>(ledger: L) {
+    let ledger = std::sync::Arc::new(ledger);
+    let engine = kamu_snap_limit::LimitEngine::new(ledger.clone(), kamu_snap_limit::Limits::idr());
+    let at_limit = kamu_snap_model::Amount::idr("1000.00");
+    let over_limit = kamu_snap_model::Amount::idr("1000.01");
+
+    // Up to the limit exactly, on every purchase
+    for _ in 0..2 {
+        engine
+            .reserve(&kamu_snap_limit::Transaction::new(&at_limit, MERCHANT_ID).with_token_limit(100_000))
+            .unwrap();
+    }
+    assert!(matches!(
+        engine
+            .reserve(&kamu_snap_limit::Transaction::new(&over_limit, MERCHANT_ID).with_token_limit(100_000)),
+        Err(kamu_snap_limit::LimitError::TokenLimitExceeded)
+    ));
+    assert_eq!(
+        kamu_snap_response::ResponseError::from(kamu_snap_limit::LimitError::TokenLimitExceeded).get_code(54),
+        4035417
+    );
+    // Rejected purchases are not counted
+    assert_eq!(
+        ledger
+            .total(&today(), kamu_snap_limit::Counter::Merchant, MERCHANT_ID)
+            .unwrap(),
+        200_000
+    );
+}
+
+fn day_rollover<L: kamu_snap_limit::Ledger>(ledger: L) {
+    let ledger = std::sync::Arc::new(ledger);
+    let engine = engine(&ledger);
+    let amount = kamu_snap_model::Amount::idr("1000.00");
+    let transaction = kamu_snap_limit::Transaction::new(&amount, MERCHANT_ID);
+    let yesterday = kamu_snap_limit::wib_day(chrono::Utc::now() - chrono::Duration::days(1));
+
+    // Yesterday's total is at the limit
+    let usage = [kamu_snap_limit::Usage {
+        counter: kamu_snap_limit::Counter::Merchant,
+        id: MERCHANT_ID,
+        limit: None,
+    }];
+    assert_eq!(ledger.add(&yesterday, 250_000, &usage).unwrap(), None);
+
+    // Today starts from zero, and the first reservation purges yesterday
+    engine.reserve(&transaction).unwrap();
+    assert_eq!(
+        ledger
+            .total(&yesterday, kamu_snap_limit::Counter::Merchant, MERCHANT_ID)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn wib_day_boundary() {
+    // Midnight WIB is 17:00 UTC
+    assert_eq!(
+        kamu_snap_limit::wib_day(utc("2026-10-18T16:59:59Z")),
+        "2026-10-18"
+    );
+    assert_eq!(
+        kamu_snap_limit::wib_day(utc("2026-10-18T17:00:00Z")),
+        "2026-10-19"
+    );
+    assert_eq!(
+        kamu_snap_limit::wib_day(utc("2026-12-31T17:00:00Z")),
+        "2027-01-01"
+    );
+}
+
+#[test]
+fn memory_ledger() {
+    per_transaction(kamu_snap_limit::MemoryLedger::new());
+    reserve_release(kamu_snap_limit::MemoryLedger::new());
+    merchant_daily(kamu_snap_limit::MemoryLedger::new());
+    token_limit(kamu_snap_limit::MemoryLedger::new());
+    day_rollover(kamu_snap_limit::MemoryLedger::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_ledger() {
+    per_transaction(kamu_snap_limit::SqliteLedger::open_in_memory().unwrap());
+    reserve_release(kamu_snap_limit::SqliteLedger::open_in_memory().unwrap());
+    merchant_daily(kamu_snap_limit::SqliteLedger::open_in_memory().unwrap());
+    token_limit(kamu_snap_limit::SqliteLedger::open_in_memory().unwrap());
+    day_rollover(kamu_snap_limit::SqliteLedger::open_in_memory().unwrap());
+}
@@ -0,0 +1,45 @@
+pub const SERVICE_CODE_B2B: u8 = 73;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    ClientCredentials,
+}
+
+/// Body of `POST /v1.0/access-token/b2b`.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub grant_type: GrantType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub access_token: String,
+    pub token_type: String,
+    /// Lifetime of the token in seconds, e.g. `"900"`
+    pub expires_in: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn bearer<A: Into<String>>(access_token: A, expires_in: std::time::Duration) -> Self {
+        Self {
+            access_token: access_token.into(),
+            token_type: "Bearer".to_owned(),
+            expires_in: expires_in.as_secs().to_string(),
+            additional_info: None,
+        }
+    }
+
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, SERVICE_CODE_B2B)
+    }
+}
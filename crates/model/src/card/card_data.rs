@@ -0,0 +1,74 @@
+const FIELD_NAME: &str = "cardData";
+
+fn invalid_format() -> kamu_snap_response::ResponseError {
+    kamu_snap_response::ResponseError::InvalidFieldFormat(FIELD_NAME.to_owned())
+}
+
+/// Plain card details carried encrypted in the `cardData` field. The JSON
+/// representation is sealed with AES-GCM under a key wrapped with the
+/// issuer's public key, see
+/// [`AsymmetricCryptoVerifier::seal_as_base64`](kamu_snap_crypto::AsymmetricCryptoVerifier::seal_as_base64).
+#[derive(Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardData {
+    pub bank_card_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_card_type: Option<String>,
+    /// `MMYY`
+    pub expiry_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identification_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identification_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_of_birth: Option<String>,
+}
+
+impl CardData {
+    pub fn encrypt(
+        &self,
+        issuer_key: &kamu_snap_crypto::AsymmetricCryptoVerifier,
+    ) -> kamu_snap_response::Result<String> {
+        let plaintext =
+            serde_json::to_vec(self).map_err(|_| kamu_snap_response::ResponseError::InternalServerError)?;
+
+        Ok(issuer_key.seal_as_base64(plaintext)?)
+    }
+
+    pub fn decrypt<C: AsRef<str>>(
+        card_data: C,
+        issuer_key: &kamu_snap_crypto::AsymmetricCryptoSigner,
+    ) -> kamu_snap_response::Result<Self> {
+        let plaintext = issuer_key.open_base64(card_data).map_err(|_| invalid_format())?;
+
+        serde_json::from_slice(&plaintext).map_err(|_| invalid_format())
+    }
+
+    /// Card number with everything but the first six and last four digits
+    /// masked, suitable for logs.
+    pub fn masked_card_no(&self) -> String {
+        let length = self.bank_card_no.chars().count();
+
+        self.bank_card_no
+            .chars()
+            .enumerate()
+            .map(|(index, x)| if index < 6 || index + 4 >= length { x } else { '*' })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for CardData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CardData")
+            .field("bank_card_no", &self.masked_card_no())
+            .field("bank_card_type", &self.bank_card_type)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,13 @@
+pub mod card_data;
+pub mod otp;
+pub mod otp_verify;
+pub mod registration;
+
+pub use card_data::CardData;
+pub use otp::{Challenge as OtpChallenge, Request as OtpRequest, Response as OtpResponse};
+pub use otp_verify::{Request as OtpVerifyRequest, Response as OtpVerifyResponse};
+pub use registration::{Request as CardRegistrationRequest, Response as CardRegistrationResponse};
+
+pub const SERVICE_CODE_CARD_REGISTRATION: u8 = 1;
+pub const SERVICE_CODE_OTP_VERIFY: u8 = 4;
+pub const SERVICE_CODE_OTP: u8 = 81;
@@ -0,0 +1,108 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journey_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_card_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp_trx_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp_reason_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp_reason_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_store_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    pub charge_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp_reason_code: Option<String>,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp_valid_until: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::card::SERVICE_CODE_OTP)
+    }
+}
+
+/// Issuer side state of an OTP sent to the cardholder.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    otp: String,
+    valid_until: std::time::SystemTime,
+    attempts: u8,
+    max_attempts: u8,
+    consumed: bool,
+}
+
+impl Challenge {
+    pub fn new<O: Into<String>>(otp: O, lifetime: std::time::Duration, max_attempts: u8) -> Self {
+        Self {
+            otp: otp.into(),
+            valid_until: std::time::SystemTime::now() + lifetime,
+            attempts: 0,
+            max_attempts,
+            consumed: false,
+        }
+    }
+
+    pub fn valid_until(&self) -> std::time::SystemTime {
+        self.valid_until
+    }
+
+    /// Whether the OTP has already been verified successfully.
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+
+    /// Check `otp` against the challenge. Every failed attempt counts towards
+    /// `max_attempts`, after which the challenge stays blocked. An OTP can
+    /// only be used once, any call after a successful one is rejected.
+    pub fn verify<O: AsRef<str>>(&mut self, otp: O) -> kamu_snap_response::Result<()> {
+        if self.consumed {
+            return Err(kamu_snap_response::ResponseError::InvalidOTP);
+        }
+
+        if self.attempts >= self.max_attempts {
+            return Err(kamu_snap_response::ResponseError::OTPBlocked);
+        }
+
+        if std::time::SystemTime::now() > self.valid_until {
+            return Err(kamu_snap_response::ResponseError::OTPLifetimeExpired);
+        }
+
+        let expected = self.otp.as_bytes();
+        let actual = otp.as_ref().as_bytes();
+        let matches = expected.len() == actual.len()
+            && expected.iter().zip(actual).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0;
+
+        if !matches {
+            self.attempts += 1;
+
+            return Err(kamu_snap_response::ResponseError::InvalidOTP);
+        }
+
+        self.consumed = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_reference_no: Option<String>,
+    pub charge_token: String,
+    pub otp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_card_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "type")]
+    pub verification_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_card_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charge_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::card::SERVICE_CODE_OTP_VERIFY)
+    }
+}
@@ -0,0 +1,74 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_name: Option<String>,
+    /// Encrypted [`CardData`](crate::card::CardData)
+    pub card_data: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cust_id_merchant: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_bind_and_pay: Option<String>,
+    pub merchant_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_merchant_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journey_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_store_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_otp_flag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "type")]
+    pub registration_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Request {
+    pub fn decrypt_card_data(
+        &self,
+        issuer_key: &kamu_snap_crypto::AsymmetricCryptoSigner,
+    ) -> kamu_snap_response::Result<crate::card::CardData> {
+        crate::card::CardData::decrypt(&self.card_data, issuer_key)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_card_token: Option<String>,
+    /// Used to verify the OTP sent to the cardholder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charge_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_string: Option<String>,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_expiry_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::card::SERVICE_CODE_CARD_REGISTRATION)
+    }
+}
@@ -0,0 +1,112 @@
+// Service codes of SNAP services without models in this crate, the others
+// live next to their models
+pub const SERVICE_CODE_BALANCE_INQUIRY: u8 = 11;
+pub const SERVICE_CODE_INTERNAL_ACCOUNT_INQUIRY: u8 = 15;
+pub const SERVICE_CODE_EXTERNAL_ACCOUNT_INQUIRY: u8 = 16;
+pub const SERVICE_CODE_TRANSFER_STATUS_INQUIRY: u8 = 36;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Amount {
+    /// Decimal amount with two fraction digits, e.g. `"10000.00"`
+    pub value: String,
+    /// ISO 4217 currency code, e.g. `"IDR"`
+    pub currency: String,
+}
+
+impl Amount {
+    pub fn new<V: Into<String>, C: Into<String>>(value: V, currency: C) -> Self {
+        Self {
+            value: value.into(),
+            currency: currency.into(),
+        }
+    }
+
+    pub fn idr<V: Into<String>>(value: V) -> Self {
+        Self::new(value, "IDR")
+    }
+
+    /// Parse `value` into minor units (cents), e.g. `"10000.50"` into
+    /// `1000050`. SNAP amounts always carry two fraction digits.
+    pub fn to_minor_units(&self) -> kamu_snap_response::Result<i64> {
+        let invalid = || kamu_snap_response::ResponseError::InvalidFieldFormat("amount".to_owned());
+        let (integer, fraction) = self.value.split_once('.').ok_or_else(invalid)?;
+
+        if integer.is_empty()
+            || fraction.len() != 2
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|x| x.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        integer
+            .parse::<i64>()
+            .ok()
+            .and_then(|x| x.checked_mul(100))
+            .and_then(|x| x.checked_add(fraction.parse::<i64>().ok()?))
+            .ok_or_else(invalid)
+    }
+
+    pub fn from_minor_units<C: Into<String>>(minor_units: i64, currency: C) -> Self {
+        let sign = if minor_units < 0 { "-" } else { "" };
+        let minor_units = minor_units.unsigned_abs();
+
+        Self::new(
+            format!("{sign}{}.{:02}", minor_units / 100, minor_units % 100),
+            currency,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub enum TransactionStatus {
+    #[serde(rename = "00")]
+    Success,
+    #[serde(rename = "01")]
+    Initiated,
+    #[serde(rename = "02")]
+    Paying,
+    #[serde(rename = "03")]
+    Pending,
+    #[serde(rename = "04")]
+    Refunded,
+    #[serde(rename = "05")]
+    Canceled,
+    #[serde(rename = "06")]
+    Failed,
+    #[serde(rename = "07")]
+    NotFound,
+}
+
+impl TransactionStatus {
+    pub fn get_code(&self) -> &'static str {
+        match self {
+            Self::Success => "00",
+            Self::Initiated => "01",
+            Self::Paying => "02",
+            Self::Pending => "03",
+            Self::Refunded => "04",
+            Self::Canceled => "05",
+            Self::Failed => "06",
+            Self::NotFound => "07",
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            Self::Success => "Success",
+            Self::Initiated => "Initiated",
+            Self::Paying => "Paying",
+            Self::Pending => "Pending",
+            Self::Refunded => "Refunded",
+            Self::Canceled => "Canceled",
+            Self::Failed => "Failed",
+            Self::NotFound => "Not Found",
+        }
+    }
+}
@@ -0,0 +1,73 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    pub customer_number: String,
+    pub amount: crate::Amount,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub customer_number: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    /// Check a top up amount against the `minAmount`/`maxAmount` returned by
+    /// the inquiry.
+    pub fn check_amount(&self, amount: &crate::Amount) -> kamu_snap_response::Result<()> {
+        let value = amount.to_minor_units()?;
+
+        for limit in [&self.min_amount, &self.max_amount].into_iter().flatten() {
+            if limit.currency != amount.currency {
+                return Err(kamu_snap_response::ResponseError::InvalidAmount);
+            }
+        }
+
+        if let Some(min_amount) = &self.min_amount
+            && value < min_amount.to_minor_units()?
+        {
+            return Err(kamu_snap_response::ResponseError::InvalidAmount);
+        }
+
+        if let Some(max_amount) = &self.max_amount
+            && value > max_amount.to_minor_units()?
+        {
+            return Err(kamu_snap_response::ResponseError::ExceedsTransactionAmountLimit);
+        }
+
+        Ok(())
+    }
+
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::emoney::SERVICE_CODE_ACCOUNT_INQUIRY)
+    }
+}
@@ -0,0 +1,13 @@
+pub mod account_inquiry;
+pub mod top_up;
+pub mod top_up_status;
+pub mod wallet;
+
+pub use account_inquiry::{Request as AccountInquiryRequest, Response as AccountInquiryResponse};
+pub use top_up::{Request as TopUpRequest, Response as TopUpResponse};
+pub use top_up_status::{Request as TopUpStatusRequest, Response as TopUpStatusResponse};
+pub use wallet::Account as WalletAccount;
+
+pub const SERVICE_CODE_ACCOUNT_INQUIRY: u8 = 37;
+pub const SERVICE_CODE_TOP_UP: u8 = 38;
+pub const SERVICE_CODE_TOP_UP_STATUS: u8 = 39;
@@ -0,0 +1,44 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub original_partner_reference_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_external_id: Option<String>,
+    /// Service code of the original transaction, `"38"` for top up
+    pub service_code: String,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_reference_no: Option<String>,
+    pub original_partner_reference_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_external_id: Option<String>,
+    pub service_code: String,
+    pub latest_transaction_status: crate::TransactionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_status_desc: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::emoney::SERVICE_CODE_TOP_UP_STATUS)
+    }
+}
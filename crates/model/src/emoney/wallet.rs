@@ -0,0 +1,39 @@
+/// Issuer side view of a customer wallet, used to validate a top up before
+/// it is executed. All amounts are in minor units of `currency`.
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub active: bool,
+    /// ISO 4217 currency code of the wallet, e.g. `"IDR"`
+    pub currency: String,
+    pub balance: i64,
+    pub max_balance: Option<i64>,
+    pub min_top_up: Option<i64>,
+    pub max_top_up: Option<i64>,
+}
+
+impl Account {
+    pub fn check_top_up(&self, amount: &crate::Amount) -> kamu_snap_response::Result<()> {
+        if !self.active {
+            return Err(kamu_snap_response::ResponseError::InactiveCardOrAccountOrCustomer);
+        }
+
+        let value = amount.to_minor_units()?;
+
+        if amount.currency != self.currency || value <= 0 || self.min_top_up.is_some_and(|x| value < x) {
+            return Err(kamu_snap_response::ResponseError::InvalidAmount);
+        }
+
+        if self.max_top_up.is_some_and(|x| value > x) {
+            return Err(kamu_snap_response::ResponseError::ExceedsTransactionAmountLimit);
+        }
+
+        if self
+            .max_balance
+            .is_some_and(|x| self.balance.saturating_add(value) > x)
+        {
+            return Err(kamu_snap_response::ResponseError::AccountLimitExceed);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_date_time: Option<String>,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_date_time: Option<String>,
+    #[serde(flatten)]
+    pub page: crate::paging::PageRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    /// ISO 8601 timestamp
+    pub date_time: String,
+    pub amount: crate::Amount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_of_funds: Vec<crate::history::SourceOfFund>,
+    pub status: crate::history::TransactionStatus,
+    #[serde(rename = "type")]
+    pub transaction_type: crate::history::TransactionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_reference_no: Option<String>,
+    #[serde(default)]
+    pub detail_data: Vec<Transaction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl crate::paging::PageItems for Response {
+    type Item = Transaction;
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.detail_data
+    }
+}
+
+impl crate::paging::Page<Response> {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::history::SERVICE_CODE_LIST)
+    }
+}
@@ -0,0 +1,40 @@
+pub mod detail;
+pub mod list;
+
+pub use detail::{Request as TransactionHistoryDetailRequest, Response as TransactionHistoryDetailResponse};
+pub use list::{
+    Request as TransactionHistoryListRequest, Response as TransactionHistoryListResponse,
+    Transaction as TransactionHistoryItem,
+};
+
+pub const SERVICE_CODE_LIST: u8 = 12;
+pub const SERVICE_CODE_DETAIL: u8 = 13;
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceOfFund {
+    pub source: String,
+    pub amount: crate::Amount,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionStatus {
+    Init,
+    Success,
+    Closed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionType {
+    Payment,
+    Refund,
+    TopUp,
+    SendMoney,
+    ReceiveMoney,
+}
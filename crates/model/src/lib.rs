@@ -0,0 +1,11 @@
+pub mod access_token;
+pub mod card;
+pub mod common;
+pub mod emoney;
+pub mod history;
+pub mod paging;
+pub mod qris;
+pub mod registration;
+
+pub use common::{Amount, TransactionStatus};
+pub use paging::{AsyncPages, Page, PageItems, PageRequest, Pages, Pagination};
@@ -0,0 +1,215 @@
+/// Paging parameters sent by the caller. SNAP uses offset paging
+/// (`pageNumber`, starting at 1) while some providers hand out cursors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl PageRequest {
+    pub fn first(page_size: u32) -> Self {
+        Self {
+            page_size: Some(page_size),
+            page_number: Some(1),
+            cursor: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_data: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl Pagination {
+    /// Work out the request for the page after `current`, given how many
+    /// items it returned. Providers that return neither a cursor nor totals
+    /// are assumed to have more data while pages come back full. A cursor
+    /// repeating the one of `current` ends the walk.
+    pub fn next_request(&self, current: &PageRequest, received: usize) -> Option<PageRequest> {
+        if received == 0 {
+            return None;
+        }
+
+        if let Some(cursor) = &self.next_cursor {
+            if current.cursor.as_ref() == Some(cursor) {
+                return None;
+            }
+
+            return Some(PageRequest {
+                page_size: current.page_size,
+                page_number: None,
+                cursor: Some(cursor.clone()),
+            });
+        }
+
+        let page_number = self.page_number.or(current.page_number).unwrap_or(1);
+        let has_more = match (
+            self.total_page,
+            self.total_data,
+            self.page_size.or(current.page_size),
+        ) {
+            (Some(total_page), _, _) => page_number < total_page,
+            (None, Some(total_data), Some(page_size)) => {
+                (page_number as u64) * (page_size as u64) < total_data
+            }
+            (None, None, Some(page_size)) => received >= page_size as usize,
+            (None, _, None) => false,
+        };
+
+        let page_number = page_number.checked_add(1).filter(|_| has_more)?;
+
+        Some(PageRequest {
+            page_size: current.page_size,
+            page_number: Some(page_number),
+            cursor: None,
+        })
+    }
+}
+
+/// Payload of a paged list response, exposing the items of a single page.
+pub trait PageItems {
+    type Item;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+/// Paged payload flattened into the SNAP envelope next to the service
+/// specific fields of `T`, i.e. `SNAPResponse<Page<T>>`.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Page<T> {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+    #[serde(flatten)]
+    pub payload: T,
+}
+
+impl<T> Page<T> {
+    pub fn new(pagination: Pagination, payload: T) -> Self {
+        Self { pagination, payload }
+    }
+}
+
+/// Lazily walks every item of a paged endpoint, calling `fetch` only when the
+/// items of the previous page have been consumed.
+pub struct Pages<T, F>
+where
+    T: PageItems,
+    F: FnMut(&PageRequest) -> kamu_snap_response::Result<Page<T>>,
+{
+    fetch: F,
+    next: Option<PageRequest>,
+    buffer: std::collections::VecDeque<T::Item>,
+}
+
+impl<T, F> Pages<T, F>
+where
+    T: PageItems,
+    F: FnMut(&PageRequest) -> kamu_snap_response::Result<Page<T>>,
+{
+    pub fn new(first: PageRequest, fetch: F) -> Self {
+        Self {
+            fetch,
+            next: Some(first),
+            buffer: std::collections::VecDeque::new(),
+        }
+    }
+}
+
+impl<T, F> Iterator for Pages<T, F>
+where
+    T: PageItems,
+    F: FnMut(&PageRequest) -> kamu_snap_response::Result<Page<T>>,
+{
+    type Item = kamu_snap_response::Result<T::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buffer.is_empty() {
+            let request = self.next.take()?;
+            let page = match (self.fetch)(&request) {
+                Ok(page) => page,
+                Err(error) => return Some(Err(error)),
+            };
+            let items = page.payload.into_items();
+            self.next = page.pagination.next_request(&request, items.len());
+            self.buffer.extend(items);
+        }
+
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+/// Async counterpart of [`Pages`], for fetchers that return a future such as
+/// a call through the SNAP client.
+pub struct AsyncPages<T, F>
+where
+    T: PageItems,
+{
+    fetch: F,
+    next: Option<PageRequest>,
+    buffer: std::collections::VecDeque<T::Item>,
+}
+
+impl<T, F, Fut> AsyncPages<T, F>
+where
+    T: PageItems,
+    F: FnMut(PageRequest) -> Fut,
+    Fut: std::future::Future<Output = kamu_snap_response::Result<Page<T>>>,
+{
+    pub fn new(first: PageRequest, fetch: F) -> Self {
+        Self {
+            fetch,
+            next: Some(first),
+            buffer: std::collections::VecDeque::new(),
+        }
+    }
+
+    /// Items of the next page, or the items not yet taken by
+    /// [`AsyncPages::next_item`]. The last page may be empty, `None` is
+    /// returned once there are no more pages.
+    pub async fn next_page(&mut self) -> Option<kamu_snap_response::Result<Vec<T::Item>>> {
+        if !self.buffer.is_empty() {
+            return Some(Ok(self.buffer.drain(..).collect()));
+        }
+
+        let request = self.next.take()?;
+        let page = match (self.fetch)(request.clone()).await {
+            Ok(page) => page,
+            Err(error) => return Some(Err(error)),
+        };
+        let items = page.payload.into_items();
+        self.next = page.pagination.next_request(&request, items.len());
+
+        Some(Ok(items))
+    }
+
+    pub async fn next_item(&mut self) -> Option<kamu_snap_response::Result<T::Item>> {
+        while self.buffer.is_empty() {
+            match self.next_page().await? {
+                Ok(items) if items.is_empty() => {}
+                Ok(items) => self.buffer.extend(items),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+
+        self.buffer.pop_front().map(Ok)
+    }
+}
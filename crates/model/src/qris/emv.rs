@@ -0,0 +1,207 @@
+pub const ID_PAYLOAD_FORMAT_INDICATOR: &str = "00";
+pub const ID_POINT_OF_INITIATION_METHOD: &str = "01";
+pub const ID_TRANSACTION_CURRENCY: &str = "53";
+pub const ID_TRANSACTION_AMOUNT: &str = "54";
+pub const ID_COUNTRY_CODE: &str = "58";
+pub const ID_MERCHANT_NAME: &str = "59";
+pub const ID_MERCHANT_CITY: &str = "60";
+pub const ID_POSTAL_CODE: &str = "61";
+pub const ID_ADDITIONAL_DATA_FIELD_TEMPLATE: &str = "62";
+pub const ID_CRC: &str = "63";
+
+const FIELD_NAME: &str = "qrContent";
+
+fn invalid_format() -> kamu_snap_response::ResponseError {
+    kamu_snap_response::ResponseError::InvalidFieldFormat(FIELD_NAME.to_owned())
+}
+
+/// CRC-16/CCITT-FALSE (polynomial `0x1021`, initial value `0xffff`) as
+/// mandated by EMVCo for the `63` data object.
+pub fn crc16_ccitt<D: AsRef<[u8]>>(data: D) -> u16 {
+    data.as_ref().iter().fold(0xffff_u16, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataObject {
+    id: String,
+    value: String,
+}
+
+impl DataObject {
+    pub fn new<I: Into<String>, V: Into<String>>(id: I, value: V) -> kamu_snap_response::Result<Self> {
+        let id = id.into();
+        let value = value.into();
+
+        if id.len() != 2 || !id.bytes().all(|x| x.is_ascii_digit()) || value.chars().count() > 99 {
+            return Err(invalid_format());
+        }
+
+        Ok(Self { id, value })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Merchant account information (`26`-`51`), additional data (`62`),
+    /// language (`64`) and unreserved (`80`-`99`) objects carry nested data
+    /// objects.
+    pub fn is_template(&self) -> bool {
+        matches!(self.id.parse::<u8>(), Ok(26..=51 | 62 | 64 | 80..=99))
+    }
+
+    pub fn children(&self) -> kamu_snap_response::Result<Vec<DataObject>> {
+        if !self.is_template() {
+            return Err(invalid_format());
+        }
+
+        decode(&self.value)
+    }
+
+    fn encode_into(&self, buffer: &mut String) {
+        buffer.push_str(&self.id);
+        buffer.push_str(&format!("{:02}", self.value.chars().count()));
+        buffer.push_str(&self.value);
+    }
+}
+
+fn decode(content: &str) -> kamu_snap_response::Result<Vec<DataObject>> {
+    let chars = content.chars().collect::<Vec<_>>();
+    let mut objects = Vec::new();
+    let mut cursor = 0;
+
+    while cursor < chars.len() {
+        if cursor + 4 > chars.len() {
+            return Err(invalid_format());
+        }
+
+        let id = chars[cursor..cursor + 2].iter().collect::<String>();
+        let length = chars[cursor + 2..cursor + 4]
+            .iter()
+            .collect::<String>()
+            .parse::<usize>()
+            .map_err(|_| invalid_format())?;
+        cursor += 4;
+
+        if cursor + length > chars.len() {
+            return Err(invalid_format());
+        }
+
+        let value = chars[cursor..cursor + length].iter().collect::<String>();
+        cursor += length;
+
+        objects.push(DataObject::new(id, value)?);
+    }
+
+    Ok(objects)
+}
+
+/// EMVCo merchant-presented QR payload, i.e. the content of `qrContent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payload {
+    objects: Vec<DataObject>,
+}
+
+impl Payload {
+    /// Build a payload from its data objects. Any `63` (CRC) object is
+    /// dropped, the checksum is always recomputed by [`Payload::encode`].
+    pub fn new(objects: Vec<DataObject>) -> Self {
+        let objects = objects.into_iter().filter(|x| x.id != ID_CRC).collect();
+
+        Self { objects }
+    }
+
+    pub fn parse<S: AsRef<str>>(qr_content: S) -> kamu_snap_response::Result<Self> {
+        let qr_content = qr_content.as_ref();
+        let mut objects = decode(qr_content)?;
+
+        match objects.first() {
+            Some(first) if first.id == ID_PAYLOAD_FORMAT_INDICATOR && first.value == "01" => {}
+            _ => return Err(invalid_format()),
+        }
+
+        let crc = match objects.pop() {
+            Some(last) if last.id == ID_CRC && last.value.len() == 4 => last.value,
+            _ => return Err(invalid_format()),
+        };
+        let expected = u16::from_str_radix(&crc, 16).map_err(|_| invalid_format())?;
+        let checked = &qr_content[..qr_content.len() - crc.len()];
+
+        if crc16_ccitt(checked) != expected {
+            return Err(invalid_format());
+        }
+
+        Ok(Self { objects })
+    }
+
+    pub fn encode(&self) -> String {
+        let mut buffer = String::new();
+
+        for object in &self.objects {
+            object.encode_into(&mut buffer);
+        }
+
+        buffer.push_str(ID_CRC);
+        buffer.push_str("04");
+        let crc = crc16_ccitt(&buffer);
+        buffer.push_str(&format!("{crc:04X}"));
+
+        buffer
+    }
+
+    pub fn objects(&self) -> &[DataObject] {
+        &self.objects
+    }
+
+    pub fn get(&self, id: &str) -> Option<&DataObject> {
+        self.objects.iter().find(|x| x.id == id)
+    }
+
+    pub fn get_value(&self, id: &str) -> Option<&str> {
+        self.get(id).map(DataObject::value)
+    }
+
+    /// `true` for dynamic (`12`) codes, which are single use and usually
+    /// carry an amount.
+    pub fn is_dynamic(&self) -> bool {
+        self.get_value(ID_POINT_OF_INITIATION_METHOD) == Some("12")
+    }
+
+    pub fn transaction_amount(&self) -> Option<&str> {
+        self.get_value(ID_TRANSACTION_AMOUNT)
+    }
+
+    pub fn merchant_name(&self) -> Option<&str> {
+        self.get_value(ID_MERCHANT_NAME)
+    }
+
+    pub fn merchant_city(&self) -> Option<&str> {
+        self.get_value(ID_MERCHANT_CITY)
+    }
+}
+
+impl std::str::FromStr for Payload {
+    type Err = kamu_snap_response::ResponseError;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
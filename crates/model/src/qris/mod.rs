@@ -0,0 +1,13 @@
+pub mod emv;
+pub mod generate;
+pub mod notify;
+pub mod query;
+
+pub use emv::Payload as QrisPayload;
+pub use generate::{Request as QrisGenerateRequest, Response as QrisGenerateResponse};
+pub use notify::{Request as QrisNotifyRequest, Response as QrisNotifyResponse};
+pub use query::{Request as QrisQueryRequest, Response as QrisQueryResponse};
+
+pub const SERVICE_CODE_GENERATE: u8 = 47;
+pub const SERVICE_CODE_QUERY: u8 = 51;
+pub const SERVICE_CODE_NOTIFY: u8 = 52;
@@ -0,0 +1,44 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub original_reference_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_partner_reference_no: Option<String>,
+    pub latest_transaction_status: crate::TransactionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_status_desc: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_account_name: Option<String>,
+    pub amount: crate::Amount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_store_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+/// The acknowledgement only carries `responseCode` and `responseMessage`
+/// from the SNAP envelope.
+#[derive(Debug, Clone, Default)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::qris::SERVICE_CODE_NOTIFY)
+    }
+}
@@ -0,0 +1,50 @@
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub original_reference_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_external_id: Option<String>,
+    /// Service code of the original transaction, `"47"` for QRIS MPM
+    pub service_code: String,
+    pub merchant_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_merchant_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_store_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub original_reference_no: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_partner_reference_no: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_external_id: Option<String>,
+    pub service_code: String,
+    pub latest_transaction_status: crate::TransactionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_status_desc: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paid_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_amount: Option<crate::Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(self, crate::qris::SERVICE_CODE_QUERY)
+    }
+}
@@ -0,0 +1,62 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GrantType {
+    AuthorizationCode,
+    RefreshToken,
+}
+
+/// Body of `POST /v1.0/access-token/b2b2c`.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub grant_type: GrantType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Request {
+    /// Ensure the credential matching `grantType` is present.
+    pub fn validate(&self) -> kamu_snap_response::Result<()> {
+        match self.grant_type {
+            GrantType::AuthorizationCode if self.auth_code.is_none() => Err(
+                kamu_snap_response::ResponseError::InvalidMandatoryField("authCode".to_owned()),
+            ),
+            GrantType::RefreshToken if self.refresh_token.is_none() => Err(
+                kamu_snap_response::ResponseError::InvalidMandatoryField("refreshToken".to_owned()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub access_token: String,
+    pub token_type: String,
+    /// ISO 8601 timestamp
+    pub access_token_expiry_time: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// ISO 8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token_expiry_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn into_snap_response(self) -> kamu_snap_response::SNAPResponse<Self> {
+        kamu_snap_response::SNAPResponse::from_payload(
+            self,
+            crate::registration::SERVICE_CODE_APPLY_CUSTOMER_TOKEN,
+        )
+    }
+}
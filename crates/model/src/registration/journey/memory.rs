@@ -0,0 +1,67 @@
+#[derive(Debug, Default)]
+pub struct Store {
+    journeys: std::sync::Mutex<std::collections::HashMap<String, (super::Step, u64)>>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, std::collections::HashMap<String, (super::Step, u64)>> {
+        self.journeys
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl super::Store for Store {
+    fn get(&self, journey_id: &str, now: u64) -> kamu_snap_response::Result<Option<super::Step>> {
+        Ok(self
+            .lock()
+            .get(journey_id)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(step, _)| *step))
+    }
+
+    fn put(&self, journey_id: &str, step: super::Step, expires_at: u64) -> kamu_snap_response::Result<()> {
+        self.lock().insert(journey_id.to_owned(), (step, expires_at));
+
+        Ok(())
+    }
+
+    fn replace(
+        &self,
+        journey_id: &str,
+        current: super::Step,
+        next: Option<super::Step>,
+        now: u64,
+        expires_at: u64,
+    ) -> kamu_snap_response::Result<bool> {
+        let mut journeys = self.lock();
+
+        match journeys.get_mut(journey_id) {
+            Some(entry) if entry.0 == current && entry.1 > now => match next {
+                Some(next) => *entry = (next, expires_at),
+                None => {
+                    journeys.remove(journey_id);
+                }
+            },
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn remove(&self, journey_id: &str) -> kamu_snap_response::Result<Option<super::Step>> {
+        Ok(self.lock().remove(journey_id).map(|(step, _)| step))
+    }
+
+    fn purge(&self, now: u64) -> kamu_snap_response::Result<usize> {
+        let mut journeys = self.lock();
+        let before = journeys.len();
+        journeys.retain(|_, (_, expires_at)| *expires_at > now);
+
+        Ok(before - journeys.len())
+    }
+}
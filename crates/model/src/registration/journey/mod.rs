@@ -0,0 +1,187 @@
+pub mod memory;
+
+pub use memory::Store as MemoryStore;
+
+/// Steps of the B2B2C registration journey, in the order they are expected to
+/// be called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    GetAuthCode,
+    AccountBinding,
+    ApplyCustomerToken,
+    AccountUnbinding,
+}
+
+impl Step {
+    /// Whether `next` may be called after `self` within the same journey.
+    /// Customer tokens can be applied repeatedly (refresh), and an account can
+    /// be unbound at any point after it has been bound.
+    pub fn can_transition_to(&self, next: Step) -> bool {
+        matches!(
+            (self, next),
+            (Self::GetAuthCode, Self::AccountBinding)
+                | (Self::AccountBinding, Self::ApplyCustomerToken)
+                | (Self::ApplyCustomerToken, Self::ApplyCustomerToken)
+                | (Self::AccountBinding, Self::AccountUnbinding)
+                | (Self::ApplyCustomerToken, Self::AccountUnbinding)
+        )
+    }
+}
+
+/// Persistence of journey steps. Timestamps are seconds since the Unix epoch,
+/// a journey whose `expires_at` is at or before `now` does not exist anymore.
+pub trait Store: Send + Sync {
+    fn get(&self, journey_id: &str, now: u64) -> kamu_snap_response::Result<Option<Step>>;
+
+    fn put(&self, journey_id: &str, step: Step, expires_at: u64) -> kamu_snap_response::Result<()>;
+
+    /// Atomically move a live journey from `current` to `next`, or remove it
+    /// when `next` is `None`. Returns `false` when the journey is no longer at
+    /// `current`.
+    fn replace(
+        &self,
+        journey_id: &str,
+        current: Step,
+        next: Option<Step>,
+        now: u64,
+        expires_at: u64,
+    ) -> kamu_snap_response::Result<bool>;
+
+    fn remove(&self, journey_id: &str) -> kamu_snap_response::Result<Option<Step>>;
+
+    /// Remove the journeys expired at `now`, returning how many there were.
+    fn purge(&self, now: u64) -> kamu_snap_response::Result<usize>;
+}
+
+impl<S: Store + ?Sized> Store for std::sync::Arc<S> {
+    fn get(&self, journey_id: &str, now: u64) -> kamu_snap_response::Result<Option<Step>> {
+        (**self).get(journey_id, now)
+    }
+
+    fn put(&self, journey_id: &str, step: Step, expires_at: u64) -> kamu_snap_response::Result<()> {
+        (**self).put(journey_id, step, expires_at)
+    }
+
+    fn replace(
+        &self,
+        journey_id: &str,
+        current: Step,
+        next: Option<Step>,
+        now: u64,
+        expires_at: u64,
+    ) -> kamu_snap_response::Result<bool> {
+        (**self).replace(journey_id, current, next, now, expires_at)
+    }
+
+    fn remove(&self, journey_id: &str) -> kamu_snap_response::Result<Option<Step>> {
+        (**self).remove(journey_id)
+    }
+
+    fn purge(&self, now: u64) -> kamu_snap_response::Result<usize> {
+        (**self).purge(now)
+    }
+}
+
+/// Keeps track of the step each `journeyId` is at. A journey expires when it
+/// has not advanced within the TTL, call [`Tracker::purge`] periodically to
+/// drop expired journeys from the store.
+#[derive(Debug)]
+pub struct Tracker<S = MemoryStore> {
+    store: S,
+    ttl: std::time::Duration,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Self::with_store(MemoryStore::new())
+    }
+}
+
+impl<S: Store> Tracker<S> {
+    pub fn with_store(store: S) -> Self {
+        Self {
+            store,
+            ttl: std::time::Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    /// How long a journey lives after it was started or last advanced,
+    /// defaults to one day.
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn ttl(&self) -> std::time::Duration {
+        self.ttl
+    }
+
+    /// Start a new journey after an auth code has been issued for it.
+    pub fn start<J: AsRef<str>>(&self, journey_id: J) -> kamu_snap_response::Result<()> {
+        self.store.put(
+            journey_id.as_ref(),
+            Step::GetAuthCode,
+            self.expires_at(unix_now()),
+        )
+    }
+
+    pub fn current(&self, journey_id: &str) -> kamu_snap_response::Result<Step> {
+        self.store
+            .get(journey_id, unix_now())?
+            .ok_or(kamu_snap_response::ResponseError::JourneyNotFound)
+    }
+
+    /// Move the journey to `next`, rejecting out-of-order calls. A journey is
+    /// forgotten once the account has been unbound.
+    pub fn advance(&self, journey_id: &str, next: Step) -> kamu_snap_response::Result<()> {
+        let now = unix_now();
+        let current = self
+            .store
+            .get(journey_id, now)?
+            .ok_or(kamu_snap_response::ResponseError::JourneyNotFound)?;
+
+        if !current.can_transition_to(next) {
+            return Err(kamu_snap_response::ResponseError::InvalidAPITransition);
+        }
+
+        let next = Some(next).filter(|x| *x != Step::AccountUnbinding);
+
+        match self
+            .store
+            .replace(journey_id, current, next, now, self.expires_at(now))?
+        {
+            true => Ok(()),
+            // Advanced or removed concurrently
+            false => Err(kamu_snap_response::ResponseError::InvalidAPITransition),
+        }
+    }
+
+    pub fn remove(&self, journey_id: &str) -> kamu_snap_response::Result<Option<Step>> {
+        self.store.remove(journey_id)
+    }
+
+    pub fn purge(&self) -> kamu_snap_response::Result<usize> {
+        self.store.purge(unix_now())
+    }
+
+    fn expires_at(&self, now: u64) -> u64 {
+        now.saturating_add(self.ttl.as_secs())
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,21 @@
+pub mod auth_code;
+pub mod binding;
+pub mod customer_token;
+pub mod journey;
+pub mod unbinding;
+
+pub use auth_code::{Request as GetAuthCodeRequest, Response as GetAuthCodeResponse};
+pub use binding::{Request as AccountBindingRequest, Response as AccountBindingResponse};
+pub use customer_token::{
+    GrantType as CustomerTokenGrantType, Request as ApplyCustomerTokenRequest,
+    Response as ApplyCustomerTokenResponse,
+};
+pub use journey::{
+    MemoryStore as MemoryJourneyStore, Step as JourneyStep, Store as JourneyStore, Tracker as JourneyTracker,
+};
+pub use unbinding::{Request as AccountUnbindingRequest, Response as AccountUnbindingResponse};
+
+pub const SERVICE_CODE_ACCOUNT_BINDING: u8 = 7;
+pub const SERVICE_CODE_ACCOUNT_UNBINDING: u8 = 9;
+pub const SERVICE_CODE_GET_AUTH_CODE: u8 = 10;
+pub const SERVICE_CODE_APPLY_CUSTOMER_TOKEN: u8 = 74;
@@ -0,0 +1,77 @@
+//! `cardData` hybrid encryption round trip.
+
+type KeyPair = (
+    kamu_snap_crypto::AsymmetricCryptoSigner,
+    kamu_snap_crypto::AsymmetricCryptoVerifier,
+);
+
+fn key_pair() -> KeyPair {
+    (
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap(),
+        kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap(),
+    )
+}
+
+fn card_data() -> kamu_snap_model::card::CardData {
+    kamu_snap_model::card::CardData {
+        bank_card_no: "5213440000001234".to_owned(),
+        bank_card_type: Some("D".to_owned()),
+        expiry_date: "1229".to_owned(),
+        identification_no: Some("3175091201900001".to_owned()),
+        identification_type: Some("01".to_owned()),
+        full_name: Some("Siti Nurhaliza binti Abdul Rahman Wijayakusuma".to_owned()),
+        email: Some("siti.nurhaliza.wijayakusuma@example.co.id".to_owned()),
+        phone_no: Some("6281234567890".to_owned()),
+        date_of_birth: Some("1990-12-01".to_owned()),
+    }
+}
+
+#[test]
+fn round_trip() {
+    let (signer, verifier) = key_pair();
+    let card_data = card_data();
+    let plaintext = serde_json::to_vec(&card_data).unwrap();
+    // More than RSA-OAEP (SHA-256) can encrypt even with a 2048-bit key
+    assert!(plaintext.len() > 190);
+
+    let encrypted = card_data.encrypt(&verifier).unwrap();
+    let decrypted = kamu_snap_model::card::CardData::decrypt(&encrypted, &signer).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(decrypted).unwrap(),
+        serde_json::to_value(card_data).unwrap()
+    );
+}
+
+#[test]
+fn rejects_tampered() {
+    let (signer, verifier) = key_pair();
+    let encrypted = card_data().encrypt(&verifier).unwrap();
+    let mut sealed = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, &encrypted).unwrap();
+    *sealed.last_mut().unwrap() ^= 1;
+    let tampered = base64::Engine::encode(&base64::prelude::BASE64_STANDARD, sealed);
+
+    assert!(matches!(
+        kamu_snap_model::card::CardData::decrypt(tampered, &signer),
+        Err(kamu_snap_response::ResponseError::InvalidFieldFormat(field)) if field == "cardData"
+    ));
+    assert!(kamu_snap_model::card::CardData::decrypt(&encrypted[..64], &signer).is_err());
+}
+
+#[test]
+fn rejects_other_key() {
+    let (_, verifier) = key_pair();
+    let other =
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::OTHER_PRIVATE_KEY).unwrap();
+    let encrypted = card_data().encrypt(&verifier).unwrap();
+
+    assert!(kamu_snap_model::card::CardData::decrypt(encrypted, &other).is_err());
+}
+
+#[test]
+fn masks_card_number() {
+    let card_data = card_data();
+
+    assert_eq!(card_data.masked_card_no(), "521344******1234");
+    assert!(!format!("{card_data:?}").contains("5213440000001234"));
+}
@@ -0,0 +1,112 @@
+//! E-money top up and inquiry amount limits at their boundaries.
+
+fn account() -> kamu_snap_model::emoney::WalletAccount {
+    kamu_snap_model::emoney::WalletAccount {
+        active: true,
+        currency: "IDR".to_owned(),
+        balance: 900_000,
+        max_balance: Some(2_000_000),
+        min_top_up: Some(1_000),
+        max_top_up: Some(1_000_000),
+    }
+}
+
+fn inquiry() -> kamu_snap_model::emoney::AccountInquiryResponse {
+    kamu_snap_model::emoney::AccountInquiryResponse {
+        reference_no: None,
+        partner_reference_no: None,
+        session_id: None,
+        customer_number: "628123456789".to_owned(),
+        customer_name: None,
+        min_amount: Some(kamu_snap_model::Amount::idr("10.00")),
+        max_amount: Some(kamu_snap_model::Amount::idr("10000.00")),
+        amount: None,
+        fee_amount: None,
+        fee_type: None,
+        additional_info: None,
+    }
+}
+
+#[test]
+fn top_up_limits() {
+    let account = account();
+    let check = |value: &str| account.check_top_up(&kamu_snap_model::Amount::idr(value));
+
+    check("10.00").unwrap();
+    check("10000.00").unwrap();
+    assert!(matches!(
+        check("9.99"),
+        Err(kamu_snap_response::ResponseError::InvalidAmount)
+    ));
+    assert!(matches!(
+        check("10000.01"),
+        Err(kamu_snap_response::ResponseError::ExceedsTransactionAmountLimit)
+    ));
+    assert!(matches!(
+        check("0.00"),
+        Err(kamu_snap_response::ResponseError::InvalidAmount)
+    ));
+}
+
+#[test]
+fn top_up_balance_limit() {
+    let account = kamu_snap_model::emoney::WalletAccount {
+        max_top_up: None,
+        ..account()
+    };
+
+    // Up to max_balance exactly
+    account
+        .check_top_up(&kamu_snap_model::Amount::idr("11000.00"))
+        .unwrap();
+    assert!(matches!(
+        account.check_top_up(&kamu_snap_model::Amount::idr("11000.01")),
+        Err(kamu_snap_response::ResponseError::AccountLimitExceed)
+    ));
+}
+
+#[test]
+fn top_up_currency_mismatch() {
+    assert!(matches!(
+        account().check_top_up(&kamu_snap_model::Amount::new("100.00", "USD")),
+        Err(kamu_snap_response::ResponseError::InvalidAmount)
+    ));
+}
+
+#[test]
+fn top_up_inactive_account() {
+    let account = kamu_snap_model::emoney::WalletAccount {
+        active: false,
+        ..account()
+    };
+
+    assert!(matches!(
+        account.check_top_up(&kamu_snap_model::Amount::idr("100.00")),
+        Err(kamu_snap_response::ResponseError::InactiveCardOrAccountOrCustomer)
+    ));
+}
+
+#[test]
+fn inquiry_amount_limits() {
+    let inquiry = inquiry();
+    let check = |value: &str| inquiry.check_amount(&kamu_snap_model::Amount::idr(value));
+
+    check("10.00").unwrap();
+    check("10000.00").unwrap();
+    assert!(matches!(
+        check("9.99"),
+        Err(kamu_snap_response::ResponseError::InvalidAmount)
+    ));
+    assert!(matches!(
+        check("10000.01"),
+        Err(kamu_snap_response::ResponseError::ExceedsTransactionAmountLimit)
+    ));
+}
+
+#[test]
+fn inquiry_currency_mismatch() {
+    assert!(matches!(
+        inquiry().check_amount(&kamu_snap_model::Amount::new("100.00", "USD")),
+        Err(kamu_snap_response::ResponseError::InvalidAmount)
+    ));
+}
@@ -0,0 +1,91 @@
+//! EMVCo merchant-presented QR test vectors.
+
+/// Sample payload from the EMVCo merchant-presented mode specification.
+const EMVCO: &str = "00020101021229300012D156000000000510A93FO3230Q31280012D15600000001030812345678\
+                     520441115802CN5914BEST TRANSPORT6007BEIJING64200002ZH0104最佳运输0202北京540523.72\
+                     53031565502016233030412340603***0708A60086670902ME91320016A011223344998877070812\
+                     3456786304A13A";
+
+/// Static QRIS payload as printed on a merchant sticker.
+const QRIS: &str = "00020101021126570011ID.DANA.WWW011893600915302259148102090225914810303UMI\
+                    51440014ID.CO.QRIS.WWW0215ID10200176114730303UMI5204581253033605802ID\
+                    5912Warung Sayur6013Kota Surabaya61056013762070703A016304";
+
+fn qris() -> String {
+    let crc = kamu_snap_model::qris::emv::crc16_ccitt(QRIS);
+
+    format!("{QRIS}{crc:04X}")
+}
+
+#[test]
+fn crc16_check_value() {
+    assert_eq!(kamu_snap_model::qris::emv::crc16_ccitt("123456789"), 0x29B1);
+}
+
+#[test]
+fn emvco_sample() {
+    let payload = kamu_snap_model::qris::QrisPayload::parse(EMVCO).unwrap();
+
+    assert!(payload.is_dynamic());
+    assert_eq!(payload.transaction_amount(), Some("23.72"));
+    assert_eq!(payload.merchant_name(), Some("BEST TRANSPORT"));
+    assert_eq!(payload.merchant_city(), Some("BEIJING"));
+    assert_eq!(payload.encode(), EMVCO);
+
+    let language = payload.get("64").unwrap().children().unwrap();
+    assert_eq!(language[1].value(), "最佳运输");
+}
+
+#[test]
+fn round_trip() {
+    let content = qris();
+    let payload = content.parse::<kamu_snap_model::qris::QrisPayload>().unwrap();
+
+    assert!(!payload.is_dynamic());
+    assert_eq!(payload.merchant_name(), Some("Warung Sayur"));
+    assert_eq!(payload.get_value("61"), Some("60137"));
+    assert_eq!(payload.to_string(), content);
+
+    let merchant = payload.get("51").unwrap().children().unwrap();
+    assert_eq!(merchant[0].value(), "ID.CO.QRIS.WWW");
+    assert!(payload.get("59").unwrap().children().is_err());
+
+    let rebuilt = kamu_snap_model::qris::QrisPayload::new(payload.objects().to_vec());
+    assert_eq!(rebuilt.encode(), content);
+}
+
+#[test]
+fn rejects_bad_crc() {
+    let mut content = qris();
+    let last = content.pop().unwrap();
+    content.push(if last == '0' { '1' } else { '0' });
+
+    assert!(kamu_snap_model::qris::QrisPayload::parse(&content).is_err());
+    assert!(kamu_snap_model::qris::QrisPayload::parse(EMVCO.replace("23.72", "23.73")).is_err());
+}
+
+#[test]
+fn rejects_missing_payload_format_indicator() {
+    let content = qris();
+    let without = &content["000201".len()..];
+    let crc = kamu_snap_model::qris::emv::crc16_ccitt(&without[..without.len() - 4]);
+    let without = format!("{}{crc:04X}", &without[..without.len() - 4]);
+
+    assert!(kamu_snap_model::qris::QrisPayload::parse(without).is_err());
+}
+
+#[test]
+fn rejects_crc_not_last() {
+    let content = qris();
+    let moved = format!("{content}5802ID");
+
+    assert!(kamu_snap_model::qris::QrisPayload::parse(moved).is_err());
+}
+
+#[test]
+fn rejects_length_over_99() {
+    assert!(kamu_snap_model::qris::emv::DataObject::new("59", "x".repeat(99)).is_ok());
+    assert!(kamu_snap_model::qris::emv::DataObject::new("59", "x".repeat(100)).is_err());
+    assert!(kamu_snap_model::qris::emv::DataObject::new("5", "x").is_err());
+    assert!(kamu_snap_model::qris::QrisPayload::parse("000201010211").is_err());
+}
@@ -0,0 +1,126 @@
+//! Registration journey transitions and expiry.
+
+use kamu_snap_model::registration::JourneyStore as _;
+
+const STEPS: [kamu_snap_model::registration::JourneyStep; 4] = [
+    kamu_snap_model::registration::JourneyStep::GetAuthCode,
+    kamu_snap_model::registration::JourneyStep::AccountBinding,
+    kamu_snap_model::registration::JourneyStep::ApplyCustomerToken,
+    kamu_snap_model::registration::JourneyStep::AccountUnbinding,
+];
+
+#[test]
+fn transition_table() {
+    use kamu_snap_model::registration::JourneyStep::*;
+
+    let allowed = [
+        (GetAuthCode, AccountBinding),
+        (AccountBinding, ApplyCustomerToken),
+        (AccountBinding, AccountUnbinding),
+        (ApplyCustomerToken, ApplyCustomerToken),
+        (ApplyCustomerToken, AccountUnbinding),
+    ];
+
+    for current in STEPS {
+        for next in STEPS {
+            assert_eq!(
+                current.can_transition_to(next),
+                allowed.contains(&(current, next)),
+                "{current:?} -> {next:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn full_journey() {
+    use kamu_snap_model::registration::JourneyStep::*;
+
+    let tracker = kamu_snap_model::registration::JourneyTracker::new();
+    tracker.start("J1").unwrap();
+    assert_eq!(tracker.current("J1").unwrap(), GetAuthCode);
+
+    tracker.advance("J1", AccountBinding).unwrap();
+    tracker.advance("J1", ApplyCustomerToken).unwrap();
+    tracker.advance("J1", ApplyCustomerToken).unwrap();
+    assert_eq!(tracker.current("J1").unwrap(), ApplyCustomerToken);
+
+    tracker.advance("J1", AccountUnbinding).unwrap();
+    assert!(matches!(
+        tracker.current("J1"),
+        Err(kamu_snap_response::ResponseError::JourneyNotFound)
+    ));
+}
+
+#[test]
+fn rejects_invalid_transition() {
+    use kamu_snap_model::registration::JourneyStep::*;
+
+    let tracker = kamu_snap_model::registration::JourneyTracker::new();
+    tracker.start("J1").unwrap();
+
+    for next in [GetAuthCode, ApplyCustomerToken, AccountUnbinding] {
+        assert!(matches!(
+            tracker.advance("J1", next),
+            Err(kamu_snap_response::ResponseError::InvalidAPITransition)
+        ));
+    }
+    assert_eq!(tracker.current("J1").unwrap(), GetAuthCode);
+}
+
+#[test]
+fn rejects_unknown_journey() {
+    let tracker = kamu_snap_model::registration::JourneyTracker::new();
+
+    assert!(matches!(
+        tracker.advance("J1", kamu_snap_model::registration::JourneyStep::AccountBinding),
+        Err(kamu_snap_response::ResponseError::JourneyNotFound)
+    ));
+    assert_eq!(tracker.remove("J1").unwrap(), None);
+}
+
+#[test]
+fn expires_after_ttl() {
+    let store = std::sync::Arc::new(kamu_snap_model::registration::MemoryJourneyStore::new());
+    let tracker = kamu_snap_model::registration::JourneyTracker::with_store(store.clone())
+        .with_ttl(std::time::Duration::ZERO);
+    tracker.start("J1").unwrap();
+
+    assert!(matches!(
+        tracker.current("J1"),
+        Err(kamu_snap_response::ResponseError::JourneyNotFound)
+    ));
+    assert!(matches!(
+        tracker.advance("J1", kamu_snap_model::registration::JourneyStep::AccountBinding),
+        Err(kamu_snap_response::ResponseError::JourneyNotFound)
+    ));
+    assert_eq!(tracker.purge().unwrap(), 1);
+    assert_eq!(tracker.purge().unwrap(), 0);
+}
+
+#[test]
+fn memory_store() {
+    use kamu_snap_model::registration::JourneyStep::*;
+
+    let store = kamu_snap_model::registration::MemoryJourneyStore::new();
+    store.put("J1", GetAuthCode, 100).unwrap();
+    store.put("J2", GetAuthCode, 200).unwrap();
+
+    assert_eq!(store.get("J1", 99).unwrap(), Some(GetAuthCode));
+    assert_eq!(store.get("J1", 100).unwrap(), None);
+    assert!(!store.replace("J1", AccountBinding, None, 0, 100).unwrap());
+    assert!(
+        !store
+            .replace("J1", GetAuthCode, Some(AccountBinding), 100, 300)
+            .unwrap()
+    );
+    assert!(
+        store
+            .replace("J2", GetAuthCode, Some(AccountBinding), 100, 300)
+            .unwrap()
+    );
+    assert_eq!(store.get("J2", 250).unwrap(), Some(AccountBinding));
+    assert_eq!(store.purge(150).unwrap(), 1);
+    assert!(store.replace("J2", AccountBinding, None, 150, 300).unwrap());
+    assert_eq!(store.get("J2", 150).unwrap(), None);
+}
@@ -0,0 +1,68 @@
+//! OTP challenge attempts, expiry and replay.
+
+const LIFETIME: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[test]
+fn accepts_matching_otp() {
+    let mut challenge = kamu_snap_model::card::OtpChallenge::new("123456", LIFETIME, 3);
+
+    assert!(!challenge.is_consumed());
+    challenge.verify("123456").unwrap();
+    assert!(challenge.is_consumed());
+}
+
+#[test]
+fn rejects_wrong_otp() {
+    let mut challenge = kamu_snap_model::card::OtpChallenge::new("123456", LIFETIME, 5);
+
+    for otp in ["654321", "12345", "1234567", ""] {
+        assert!(matches!(
+            challenge.verify(otp),
+            Err(kamu_snap_response::ResponseError::InvalidOTP)
+        ));
+    }
+    challenge.verify("123456").unwrap();
+}
+
+#[test]
+fn blocks_after_max_attempts() {
+    let mut challenge = kamu_snap_model::card::OtpChallenge::new("123456", LIFETIME, 2);
+
+    assert!(matches!(
+        challenge.verify("000000"),
+        Err(kamu_snap_response::ResponseError::InvalidOTP)
+    ));
+    assert!(matches!(
+        challenge.verify("111111"),
+        Err(kamu_snap_response::ResponseError::InvalidOTP)
+    ));
+    assert!(matches!(
+        challenge.verify("123456"),
+        Err(kamu_snap_response::ResponseError::OTPBlocked)
+    ));
+    assert!(!challenge.is_consumed());
+}
+
+#[test]
+fn rejects_expired_otp() {
+    let mut challenge = kamu_snap_model::card::OtpChallenge::new("123456", std::time::Duration::ZERO, 3);
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    assert!(matches!(
+        challenge.verify("123456"),
+        Err(kamu_snap_response::ResponseError::OTPLifetimeExpired)
+    ));
+}
+
+#[test]
+fn rejects_replay() {
+    let mut challenge = kamu_snap_model::card::OtpChallenge::new("123456", LIFETIME, 3);
+    challenge.verify("123456").unwrap();
+
+    for _ in 0..5 {
+        assert!(matches!(
+            challenge.verify("123456"),
+            Err(kamu_snap_response::ResponseError::InvalidOTP)
+        ));
+    }
+}
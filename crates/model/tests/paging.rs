@@ -0,0 +1,211 @@
+//! Page boundaries of offset and cursor paging.
+
+#[derive(Debug)]
+struct Items(Vec<u32>);
+
+impl kamu_snap_model::PageItems for Items {
+    type Item = u32;
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.0
+    }
+}
+
+fn pagination(
+    page_number: u32,
+    total_page: Option<u32>,
+    total_data: Option<u64>,
+) -> kamu_snap_model::Pagination {
+    kamu_snap_model::Pagination {
+        page_size: Some(10),
+        page_number: Some(page_number),
+        total_page,
+        total_data,
+        next_cursor: None,
+    }
+}
+
+/// Serves `total` items in pages of `request.page_size` without announcing
+/// totals, so the walker only stops on a short or empty page.
+fn serve(total: u32, request: &kamu_snap_model::PageRequest) -> kamu_snap_model::Page<Items> {
+    let size = request.page_size.unwrap();
+    let start = (request.page_number.unwrap() - 1) * size;
+    let items = (start..total.min(start + size)).collect();
+
+    kamu_snap_model::Page::new(
+        kamu_snap_model::Pagination {
+            page_number: request.page_number,
+            ..Default::default()
+        },
+        Items(items),
+    )
+}
+
+#[test]
+fn next_request_by_total_page() {
+    let first = kamu_snap_model::PageRequest::first(10);
+
+    let next = pagination(1, Some(2), None).next_request(&first, 10).unwrap();
+    assert_eq!(next.page_number, Some(2));
+    assert_eq!(next.page_size, Some(10));
+    assert!(pagination(2, Some(2), None).next_request(&next, 10).is_none());
+}
+
+#[test]
+fn next_request_by_total_data() {
+    let first = kamu_snap_model::PageRequest::first(10);
+
+    assert!(pagination(1, None, Some(11)).next_request(&first, 10).is_some());
+    assert!(pagination(2, None, Some(20)).next_request(&first, 10).is_none());
+    assert!(pagination(1, None, Some(10)).next_request(&first, 10).is_none());
+}
+
+#[test]
+fn next_request_by_full_page() {
+    let first = kamu_snap_model::PageRequest::first(10);
+    let pagination = kamu_snap_model::Pagination::default();
+
+    assert_eq!(pagination.next_request(&first, 10).unwrap().page_number, Some(2));
+    assert!(pagination.next_request(&first, 9).is_none());
+    assert!(pagination.next_request(&first, 0).is_none());
+}
+
+#[test]
+fn next_request_by_cursor() {
+    let first = kamu_snap_model::PageRequest::first(10);
+    let pagination = kamu_snap_model::Pagination {
+        next_cursor: Some("c2".to_owned()),
+        ..Default::default()
+    };
+
+    let next = pagination.next_request(&first, 3).unwrap();
+    assert_eq!(next.cursor.as_deref(), Some("c2"));
+    assert_eq!(next.page_number, None);
+    assert!(pagination.next_request(&next, 0).is_none());
+}
+
+#[test]
+fn next_request_stops_on_repeated_cursor() {
+    let pagination = kamu_snap_model::Pagination {
+        next_cursor: Some("c2".to_owned()),
+        ..Default::default()
+    };
+    let current = kamu_snap_model::PageRequest {
+        cursor: Some("c2".to_owned()),
+        ..kamu_snap_model::PageRequest::first(10)
+    };
+
+    assert!(pagination.next_request(&current, 10).is_none());
+}
+
+#[test]
+fn next_request_stops_at_last_page_number() {
+    let last = kamu_snap_model::PageRequest {
+        page_number: Some(u32::MAX),
+        ..kamu_snap_model::PageRequest::first(10)
+    };
+    let pagination = kamu_snap_model::Pagination {
+        page_number: Some(u32::MAX),
+        ..Default::default()
+    };
+
+    assert!(pagination.next_request(&last, 10).is_none());
+}
+
+#[test]
+fn pages_stop_on_empty_last_page() {
+    let mut requests = Vec::new();
+    let items = kamu_snap_model::Pages::new(kamu_snap_model::PageRequest::first(10), |request| {
+        requests.push(request.page_number.unwrap());
+        Ok(serve(20, request))
+    })
+    .collect::<kamu_snap_response::Result<Vec<_>>>()
+    .unwrap();
+
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+    assert_eq!(requests, [1, 2, 3]);
+}
+
+#[test]
+fn pages_stop_on_short_page() {
+    let items = kamu_snap_model::Pages::new(kamu_snap_model::PageRequest::first(10), |request| {
+        Ok(serve(15, request))
+    })
+    .collect::<kamu_snap_response::Result<Vec<_>>>()
+    .unwrap();
+
+    assert_eq!(items, (0..15).collect::<Vec<_>>());
+}
+
+#[test]
+fn pages_stop_after_error() {
+    let mut pages = kamu_snap_model::Pages::new(kamu_snap_model::PageRequest::first(10), |request| {
+        match request.page_number {
+            Some(1) => Ok(serve(30, request)),
+            _ => Err(kamu_snap_response::ResponseError::Timeout),
+        }
+    });
+
+    assert_eq!(pages.by_ref().take(10).count(), 10);
+    assert!(matches!(
+        pages.next(),
+        Some(Err(kamu_snap_response::ResponseError::Timeout))
+    ));
+    assert!(pages.next().is_none());
+}
+
+#[tokio::test]
+async fn async_pages() {
+    let mut pages = kamu_snap_model::AsyncPages::new(kamu_snap_model::PageRequest::first(10), |request| {
+        std::future::ready(Ok(serve(20, &request)))
+    });
+
+    assert_eq!(pages.next_item().await.unwrap().unwrap(), 0);
+    assert_eq!(
+        pages.next_page().await.unwrap().unwrap(),
+        (1..10).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        pages.next_page().await.unwrap().unwrap(),
+        (10..20).collect::<Vec<_>>()
+    );
+    assert!(pages.next_page().await.unwrap().unwrap().is_empty());
+    assert!(pages.next_page().await.is_none());
+    assert!(pages.next_item().await.is_none());
+}
+
+#[tokio::test]
+async fn async_pages_items() {
+    let mut pages = kamu_snap_model::AsyncPages::new(kamu_snap_model::PageRequest::first(10), |request| {
+        std::future::ready(Ok(serve(20, &request)))
+    });
+    let mut items = Vec::new();
+
+    while let Some(item) = pages.next_item().await {
+        items.push(item.unwrap());
+    }
+
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn async_pages_stop_on_repeated_cursor() {
+    let mut requests = 0;
+    let mut pages = kamu_snap_model::AsyncPages::new(kamu_snap_model::PageRequest::first(10), |_| {
+        requests += 1;
+        // Always hands out the same cursor
+        std::future::ready(Ok(kamu_snap_model::Page::new(
+            kamu_snap_model::Pagination {
+                next_cursor: Some("c2".to_owned()),
+                ..Default::default()
+            },
+            Items((0..10).collect()),
+        )))
+    });
+
+    while let Some(items) = pages.next_page().await {
+        items.unwrap();
+    }
+
+    assert_eq!(requests, 2);
+}
@@ -0,0 +1,326 @@
+//! Signed notifications pushed to partners. Notifications are written to an
+//! [`Outbox`](crate::Outbox) first and delivered by [`Dispatcher::run`], which
+//! retries with exponential backoff until the partner acknowledges them or
+//! they end up as dead letters:
+//!
+//! ```ignore
+//! let dispatcher = Arc::new(
+//!     NotificationDispatcher::new(SqliteOutbox::open("outbox.db")?, DispatcherConfig::default())?
+//!         .with_destination(
+//!             "merchant-1",
+//!             NotificationDestination::new(
+//!                 "https://merchant.co.id",
+//!                 "BANK001",
+//!                 "95221",
+//!                 NotificationSigning::Asymmetric(Box::new(signer)),
+//!             ),
+//!         ),
+//! );
+//! tokio::spawn({
+//!     let dispatcher = dispatcher.clone();
+//!     async move { dispatcher.run(Duration::from_secs(1)).await }
+//! });
+//!
+//! dispatcher.enqueue("merchant-1", SERVICE_CODE_NOTIFY, "/v1.0/qr/qr-mpm-notify", &notification)?;
+//! ```
+
+#[derive(Debug, Clone)]
+pub enum Signing {
+    /// SHA256withRSA over the asymmetric string to sign
+    Asymmetric(Box<kamu_snap_crypto::AsymmetricCryptoSigner>),
+    /// HMAC over the symmetric string to sign with the client secret of the
+    /// client, and its cached access token issued by the partner
+    Symmetric(std::sync::Arc<kamu_snap_client::SNAPClient>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Destination {
+    /// Scheme, host and optional path prefix, e.g. `https://merchant.co.id`.
+    /// The prefix is part of the signed `EndpointUrl`.
+    pub base_url: String,
+    /// Sent as `X-PARTNER-ID`
+    pub partner_id: String,
+    /// Sent as `CHANNEL-ID`
+    pub channel_id: String,
+    pub signing: Signing,
+    /// Signing and response quirks of the partner
+    pub profile: kamu_snap_response::QuirkProfile,
+}
+
+impl Destination {
+    pub fn new<B, P, C>(base_url: B, partner_id: P, channel_id: C, signing: Signing) -> Self
+    where
+        B: Into<String>,
+        P: Into<String>,
+        C: Into<String>,
+    {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            partner_id: partner_id.into(),
+            channel_id: channel_id.into(),
+            signing,
+            profile: kamu_snap_response::QuirkProfile::STRICT,
+        }
+    }
+
+    pub fn with_profile(mut self, profile: kamu_snap_response::QuirkProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Decides which failures are retried and when. Notifications are
+    /// delivered as idempotent calls, an outcome in doubt is retried.
+    pub retry: kamu_snap_client::RetryPolicy,
+    pub timeout: std::time::Duration,
+    /// Entries claimed from the outbox per round
+    pub batch_size: usize,
+    /// How long a claimed entry is hidden from other rounds, must exceed
+    /// `timeout`
+    pub lease: std::time::Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            retry: kamu_snap_client::RetryPolicy::default().with_default(
+                kamu_snap_client::retry::ServicePolicy {
+                    operation: kamu_snap_client::retry::Operation::Idempotent,
+                    backoff: kamu_snap_client::retry::Backoff {
+                        initial: std::time::Duration::from_secs(1),
+                        max: std::time::Duration::from_secs(600),
+                        max_attempts: 10,
+                    },
+                },
+            ),
+            timeout: std::time::Duration::from_secs(30),
+            batch_size: 32,
+            lease: std::time::Duration::from_secs(300),
+        }
+    }
+}
+
+pub struct Dispatcher<O: crate::Outbox> {
+    http: reqwest::Client,
+    outbox: O,
+    config: Config,
+    destinations: std::collections::HashMap<String, Destination>,
+}
+
+impl<O: crate::Outbox> Dispatcher<O> {
+    pub fn new(outbox: O, config: Config) -> crate::Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(kamu_snap_client::ClientError::from)?;
+
+        Ok(Self::with_http_client(http, outbox, config))
+    }
+
+    pub fn with_http_client(http: reqwest::Client, outbox: O, config: Config) -> Self {
+        Self {
+            http,
+            outbox,
+            config,
+            destinations: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Register where the notifications enqueued for `partner_id` go.
+    pub fn with_destination<P: Into<String>>(mut self, partner_id: P, destination: Destination) -> Self {
+        self.destinations.insert(partner_id.into(), destination);
+        self
+    }
+
+    pub fn outbox(&self) -> &O {
+        &self.outbox
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Queue `body` for delivery to `path` at the destination of
+    /// `partner_id`, returning its outbox id.
+    pub fn enqueue<B>(&self, partner_id: &str, service_code: u8, path: &str, body: &B) -> crate::Result<u64>
+    where
+        B: serde::Serialize + ?Sized,
+    {
+        if !self.destinations.contains_key(partner_id) {
+            return Err(crate::NotificationError::UnknownDestination(
+                partner_id.to_owned(),
+            ));
+        }
+
+        let body =
+            serde_json::to_vec(body).map_err(|x| crate::NotificationError::InvalidPayload(x.to_string()))?;
+        let message = crate::OutboxMessage {
+            partner_id: partner_id.to_owned(),
+            service_code,
+            path: path.to_owned(),
+            body,
+        };
+
+        self.outbox.enqueue(message, crate::unix_now_millis())
+    }
+
+    /// Make one signed delivery attempt of `message`, with a fresh
+    /// `X-EXTERNAL-ID`, and decode the acknowledgement.
+    pub async fn deliver(
+        &self,
+        message: &crate::OutboxMessage,
+    ) -> crate::Result<kamu_snap_response::SNAPResponse<serde_json::Value>> {
+        let destination = self
+            .destinations
+            .get(&message.partner_id)
+            .ok_or_else(|| crate::NotificationError::UnknownDestination(message.partner_id.clone()))?;
+        let external_id = kamu_snap_client::generate_external_id()?;
+        let timestamp = kamu_snap_response::header::timestamp_now();
+        let relative_url = kamu_snap_client::relative_url(&destination.base_url, &message.path);
+
+        let mut request = self
+            .http
+            .post(format!("{}{}", destination.base_url, message.path))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(kamu_snap_response::header::X_TIMESTAMP, &timestamp)
+            .header(kamu_snap_response::header::X_PARTNER_ID, &destination.partner_id)
+            .header(kamu_snap_response::header::X_EXTERNAL_ID, external_id)
+            .header(kamu_snap_response::header::CHANNEL_ID, &destination.channel_id);
+
+        let signature = match &destination.signing {
+            Signing::Asymmetric(signer) => {
+                signer.sign_as_base64(kamu_snap_crypto::string_to_sign::asymmetric_with(
+                    &destination.profile,
+                    "POST",
+                    &relative_url,
+                    &message.body,
+                    &timestamp,
+                ))
+            }
+            Signing::Symmetric(client) => {
+                let access_token = client.access_token().await?;
+                request = request.header(reqwest::header::AUTHORIZATION, format!("Bearer {access_token}"));
+
+                client
+                    .symmetric()
+                    .sign(kamu_snap_crypto::string_to_sign::symmetric_with(
+                        &destination.profile,
+                        "POST",
+                        &relative_url,
+                        &access_token,
+                        &message.body,
+                        &timestamp,
+                    ))
+            }
+        };
+
+        let response = request
+            .header(kamu_snap_response::header::X_SIGNATURE, signature)
+            .body(message.body.clone())
+            .send()
+            .await
+            .map_err(kamu_snap_client::ClientError::from)?;
+
+        let result = kamu_snap_client::decode_response(
+            response,
+            &reqwest::Method::POST,
+            &relative_url,
+            &destination.profile,
+            None,
+        )
+        .await;
+
+        // Rejected token, the retry requests a new one
+        if let (
+            Signing::Symmetric(client),
+            Err(kamu_snap_client::ClientError::Response(
+                kamu_snap_response::ResponseError::InvalidTokenB2B
+                | kamu_snap_response::ResponseError::TokenNotFoundB2B,
+            )),
+        ) = (&destination.signing, &result)
+        {
+            client.invalidate_access_token().await;
+        }
+
+        Ok(result?)
+    }
+
+    /// Claim the due entries of the outbox and attempt each once, returning
+    /// how many were attempted. Failing to record the outcome of an entry is
+    /// logged and leaves it to be claimed again once its lease expires.
+    pub async fn dispatch(&self) -> crate::Result<usize> {
+        let now = crate::unix_now_millis();
+        let lease_until = now.saturating_add(self.config.lease.as_millis() as u64);
+        let entries = self.outbox.claim(now, lease_until, self.config.batch_size)?;
+
+        for entry in &entries {
+            if let Err(error) = self.attempt(entry).await {
+                tracing::warn!(id = entry.id, %error, "failed to record notification attempt");
+            }
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Deliver a claimed entry, whose attempts already count this one, and
+    /// record the outcome.
+    async fn attempt(&self, entry: &crate::OutboxEntry) -> crate::Result<()> {
+        let max_attempts = self
+            .config
+            .retry
+            .get(entry.message.service_code)
+            .backoff
+            .max_attempts;
+
+        // Claimed again after earlier attempts never recorded, e.g. the
+        // worker crashed while delivering
+        if entry.attempts > max_attempts {
+            let error = entry.last_error.as_deref().unwrap_or("attempts interrupted");
+            tracing::warn!(id = entry.id, error, "notification moved to dead letters");
+
+            return self.outbox.dead_letter(entry.id, entry.attempts, error);
+        }
+
+        let error = match self.deliver(&entry.message).await {
+            Ok(_) => return self.outbox.delivered(entry.id, entry.attempts),
+            Err(error) => error,
+        };
+        let decision = match &error {
+            crate::NotificationError::Delivery(x) => {
+                self.config
+                    .retry
+                    .decide(entry.message.service_code, x, entry.attempts)
+            }
+            _ => kamu_snap_client::RetryDecision::Fail,
+        };
+
+        if let kamu_snap_client::RetryDecision::Retry(delay) = decision {
+            let next_attempt_at = crate::unix_now_millis().saturating_add(delay.as_millis() as u64);
+
+            self.outbox
+                .retry(entry.id, entry.attempts, next_attempt_at, &error.to_string())
+        } else {
+            tracing::warn!(id = entry.id, %error, "notification moved to dead letters");
+
+            self.outbox
+                .dead_letter(entry.id, entry.attempts, &error.to_string())
+        }
+    }
+
+    /// Dispatch forever, polling the outbox every `interval` while it is
+    /// idle.
+    pub async fn run(&self, interval: std::time::Duration) {
+        loop {
+            match self.dispatch().await {
+                Ok(0) => tokio::time::sleep(interval).await,
+                Ok(_) => {}
+                Err(error) => {
+                    tracing::warn!(%error, "failed to dispatch notifications");
+                    tokio::time::sleep(interval).await;
+                }
+            }
+        }
+    }
+}
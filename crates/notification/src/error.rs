@@ -0,0 +1,23 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("No destination configured for partner {0}")]
+    UnknownDestination(String),
+    #[error("Invalid notification payload: {0}")]
+    InvalidPayload(String),
+    #[error("Notification outbox failure: {0}")]
+    Store(String),
+    #[error(transparent)]
+    Delivery(#[from] kamu_snap_client::ClientError),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::UnknownDestination(_) => kamu_snap_response::ResponseError::PartnerNotFound,
+            Error::InvalidPayload(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Store(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Delivery(x) => x.into(),
+        }
+    }
+}
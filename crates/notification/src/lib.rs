@@ -0,0 +1,25 @@
+pub mod dispatcher;
+pub mod error;
+pub mod outbox;
+pub mod receiver;
+
+pub use dispatcher::{
+    Config as DispatcherConfig, Destination as NotificationDestination, Dispatcher as NotificationDispatcher,
+    Signing as NotificationSigning,
+};
+pub use error::Error as NotificationError;
+#[cfg(feature = "sqlite")]
+pub use outbox::SqliteOutbox;
+pub use outbox::{
+    Entry as OutboxEntry, MemoryOutbox, Message as OutboxMessage, Outbox, Status as OutboxStatus,
+};
+pub use receiver::{Notification, Receiver as NotificationReceiver};
+
+pub type Result<T> = core::result::Result<T, NotificationError>;
+
+pub(crate) fn unix_now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,114 @@
+/// Outbox kept in memory, lost on restart.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    entries: std::sync::Mutex<std::collections::BTreeMap<u64, crate::OutboxEntry>>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, std::collections::BTreeMap<u64, crate::OutboxEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn update<F>(&self, id: u64, update: F) -> crate::Result<()>
+    where
+        F: FnOnce(&mut crate::OutboxEntry),
+    {
+        if let Some(entry) = self.lock().get_mut(&id) {
+            update(entry);
+        }
+
+        Ok(())
+    }
+}
+
+impl crate::Outbox for Outbox {
+    fn enqueue(&self, message: crate::OutboxMessage, now: u64) -> crate::Result<u64> {
+        let mut entries = self.lock();
+        let id = entries.last_key_value().map_or(1, |(id, _)| id + 1);
+        entries.insert(
+            id,
+            crate::OutboxEntry {
+                id,
+                message,
+                status: crate::OutboxStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+            },
+        );
+
+        Ok(id)
+    }
+
+    fn claim(&self, now: u64, lease_until: u64, limit: usize) -> crate::Result<Vec<crate::OutboxEntry>> {
+        let mut entries = self.lock();
+        let mut due = entries
+            .values_mut()
+            .filter(|x| x.status == crate::OutboxStatus::Pending && x.next_attempt_at <= now)
+            .collect::<Vec<_>>();
+        due.sort_by_key(|x| (x.next_attempt_at, x.id));
+
+        Ok(due
+            .into_iter()
+            .take(limit)
+            .map(|entry| {
+                entry.attempts += 1;
+                entry.next_attempt_at = lease_until;
+                entry.clone()
+            })
+            .collect())
+    }
+
+    fn delivered(&self, id: u64, attempts: u32) -> crate::Result<()> {
+        self.update(id, |entry| {
+            entry.status = crate::OutboxStatus::Delivered;
+            entry.attempts = attempts;
+            entry.last_error = None;
+        })
+    }
+
+    fn retry(&self, id: u64, attempts: u32, next_attempt_at: u64, error: &str) -> crate::Result<()> {
+        self.update(id, |entry| {
+            entry.attempts = attempts;
+            entry.next_attempt_at = next_attempt_at;
+            entry.last_error = Some(error.to_owned());
+        })
+    }
+
+    fn dead_letter(&self, id: u64, attempts: u32, error: &str) -> crate::Result<()> {
+        self.update(id, |entry| {
+            entry.status = crate::OutboxStatus::DeadLetter;
+            entry.attempts = attempts;
+            entry.last_error = Some(error.to_owned());
+        })
+    }
+
+    fn get(&self, id: u64) -> crate::Result<Option<crate::OutboxEntry>> {
+        Ok(self.lock().get(&id).cloned())
+    }
+
+    fn dead_letters(&self) -> crate::Result<Vec<crate::OutboxEntry>> {
+        Ok(self
+            .lock()
+            .values()
+            .filter(|x| x.status == crate::OutboxStatus::DeadLetter)
+            .cloned()
+            .collect())
+    }
+
+    fn requeue(&self, id: u64, now: u64) -> crate::Result<()> {
+        self.update(id, |entry| {
+            if entry.status == crate::OutboxStatus::DeadLetter {
+                entry.status = crate::OutboxStatus::Pending;
+                entry.attempts = 0;
+                entry.next_attempt_at = now;
+            }
+        })
+    }
+}
@@ -0,0 +1,98 @@
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::Outbox as MemoryOutbox;
+#[cfg(feature = "sqlite")]
+pub use sqlite::Outbox as SqliteOutbox;
+
+/// A notification waiting to be pushed to a partner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// Key of the [`Destination`](crate::NotificationDestination) to push to
+    pub partner_id: String,
+    pub service_code: u8,
+    /// Relative to the base URL of the destination, including any query string
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pending,
+    Delivered,
+    /// Given up on, either rejected for good or out of attempts
+    DeadLetter,
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub id: u64,
+    pub message: Message,
+    pub status: Status,
+    /// Delivery attempts made so far, including one in progress
+    pub attempts: u32,
+    /// Milliseconds since the Unix epoch
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+}
+
+/// Persistent queue of outbound notifications. Times are milliseconds since
+/// the Unix epoch.
+pub trait Outbox: Send + Sync {
+    fn enqueue(&self, message: Message, now: u64) -> crate::Result<u64>;
+
+    /// Atomically take up to `limit` pending entries due at `now`, oldest
+    /// first, count the attempt about to be made and push their next attempt
+    /// to `lease_until` so that an attempt interrupted by a crash is picked up
+    /// again.
+    fn claim(&self, now: u64, lease_until: u64, limit: usize) -> crate::Result<Vec<Entry>>;
+
+    fn delivered(&self, id: u64, attempts: u32) -> crate::Result<()>;
+
+    fn retry(&self, id: u64, attempts: u32, next_attempt_at: u64, error: &str) -> crate::Result<()>;
+
+    fn dead_letter(&self, id: u64, attempts: u32, error: &str) -> crate::Result<()>;
+
+    fn get(&self, id: u64) -> crate::Result<Option<Entry>>;
+
+    fn dead_letters(&self) -> crate::Result<Vec<Entry>>;
+
+    /// Move a dead letter back to pending, due at `now`, with its attempts
+    /// reset.
+    fn requeue(&self, id: u64, now: u64) -> crate::Result<()>;
+}
+
+impl<O: Outbox + ?Sized> Outbox for std::sync::Arc<O> {
+    fn enqueue(&self, message: Message, now: u64) -> crate::Result<u64> {
+        (**self).enqueue(message, now)
+    }
+
+    fn claim(&self, now: u64, lease_until: u64, limit: usize) -> crate::Result<Vec<Entry>> {
+        (**self).claim(now, lease_until, limit)
+    }
+
+    fn delivered(&self, id: u64, attempts: u32) -> crate::Result<()> {
+        (**self).delivered(id, attempts)
+    }
+
+    fn retry(&self, id: u64, attempts: u32, next_attempt_at: u64, error: &str) -> crate::Result<()> {
+        (**self).retry(id, attempts, next_attempt_at, error)
+    }
+
+    fn dead_letter(&self, id: u64, attempts: u32, error: &str) -> crate::Result<()> {
+        (**self).dead_letter(id, attempts, error)
+    }
+
+    fn get(&self, id: u64) -> crate::Result<Option<Entry>> {
+        (**self).get(id)
+    }
+
+    fn dead_letters(&self) -> crate::Result<Vec<Entry>> {
+        (**self).dead_letters()
+    }
+
+    fn requeue(&self, id: u64, now: u64) -> crate::Result<()> {
+        (**self).requeue(id, now)
+    }
+}
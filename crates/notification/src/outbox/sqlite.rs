@@ -0,0 +1,226 @@
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS snap_notification_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    partner_id TEXT NOT NULL,
+    service_code INTEGER NOT NULL,
+    path TEXT NOT NULL,
+    body BLOB NOT NULL,
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    next_attempt_at INTEGER NOT NULL,
+    last_error TEXT
+);
+CREATE INDEX IF NOT EXISTS snap_notification_outbox_due
+    ON snap_notification_outbox (status, next_attempt_at);
+"#;
+
+const COLUMNS: &str =
+    "id, partner_id, service_code, path, body, status, attempts, next_attempt_at, last_error";
+
+fn store_error(error: rusqlite::Error) -> crate::NotificationError {
+    crate::NotificationError::Store(error.to_string())
+}
+
+fn status_to_str(status: crate::OutboxStatus) -> &'static str {
+    match status {
+        crate::OutboxStatus::Pending => "pending",
+        crate::OutboxStatus::Delivered => "delivered",
+        crate::OutboxStatus::DeadLetter => "dead_letter",
+    }
+}
+
+fn status_from_str(value: &str) -> Option<crate::OutboxStatus> {
+    match value {
+        "pending" => Some(crate::OutboxStatus::Pending),
+        "delivered" => Some(crate::OutboxStatus::Delivered),
+        "dead_letter" => Some(crate::OutboxStatus::DeadLetter),
+        _ => None,
+    }
+}
+
+fn entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<crate::OutboxEntry> {
+    let status: String = row.get(5)?;
+
+    Ok(crate::OutboxEntry {
+        id: row.get(0)?,
+        message: crate::OutboxMessage {
+            partner_id: row.get(1)?,
+            service_code: row.get(2)?,
+            path: row.get(3)?,
+            body: row.get(4)?,
+        },
+        status: status_from_str(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                5,
+                rusqlite::types::Type::Text,
+                format!("unknown status {status}").into(),
+            )
+        })?,
+        attempts: row.get(6)?,
+        next_attempt_at: row.get(7)?,
+        last_error: row.get(8)?,
+    })
+}
+
+/// Outbox persisted in SQLite, surviving restarts.
+pub struct Outbox {
+    connection: std::sync::Mutex<rusqlite::Connection>,
+}
+
+impl Outbox {
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open(path).map_err(store_error)?)
+    }
+
+    pub fn open_in_memory() -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open_in_memory().map_err(store_error)?)
+    }
+
+    pub fn from_connection(connection: rusqlite::Connection) -> crate::Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+
+        Ok(Self {
+            connection: std::sync::Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn execute<P: rusqlite::Params>(&self, sql: &str, params: P) -> crate::Result<()> {
+        self.lock().execute(sql, params).map_err(store_error)?;
+
+        Ok(())
+    }
+}
+
+impl crate::Outbox for Outbox {
+    fn enqueue(&self, message: crate::OutboxMessage, now: u64) -> crate::Result<u64> {
+        let connection = self.lock();
+        connection
+            .execute(
+                "INSERT INTO snap_notification_outbox \
+                 (partner_id, service_code, path, body, status, attempts, next_attempt_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6)",
+                rusqlite::params![
+                    message.partner_id,
+                    message.service_code,
+                    message.path,
+                    message.body,
+                    status_to_str(crate::OutboxStatus::Pending),
+                    now,
+                ],
+            )
+            .map_err(store_error)?;
+
+        Ok(connection.last_insert_rowid() as u64)
+    }
+
+    fn claim(&self, now: u64, lease_until: u64, limit: usize) -> crate::Result<Vec<crate::OutboxEntry>> {
+        let mut connection = self.lock();
+        let transaction = connection
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(store_error)?;
+        let entries = transaction
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM snap_notification_outbox \
+                 WHERE status = ?1 AND next_attempt_at <= ?2 \
+                 ORDER BY next_attempt_at, id LIMIT ?3"
+            ))
+            .and_then(|mut statement| {
+                statement
+                    .query_map(
+                        rusqlite::params![status_to_str(crate::OutboxStatus::Pending), now, limit as u64],
+                        entry,
+                    )?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .map_err(store_error)?;
+
+        for entry in &entries {
+            transaction
+                .execute(
+                    "UPDATE snap_notification_outbox \
+                     SET attempts = attempts + 1, next_attempt_at = ?2 WHERE id = ?1",
+                    rusqlite::params![entry.id, lease_until],
+                )
+                .map_err(store_error)?;
+        }
+
+        transaction.commit().map_err(store_error)?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| crate::OutboxEntry {
+                attempts: entry.attempts + 1,
+                next_attempt_at: lease_until,
+                ..entry
+            })
+            .collect())
+    }
+
+    fn delivered(&self, id: u64, attempts: u32) -> crate::Result<()> {
+        self.execute(
+            "UPDATE snap_notification_outbox SET status = ?2, attempts = ?3, last_error = NULL WHERE id = ?1",
+            rusqlite::params![id, status_to_str(crate::OutboxStatus::Delivered), attempts],
+        )
+    }
+
+    fn retry(&self, id: u64, attempts: u32, next_attempt_at: u64, error: &str) -> crate::Result<()> {
+        self.execute(
+            "UPDATE snap_notification_outbox \
+             SET attempts = ?2, next_attempt_at = ?3, last_error = ?4 WHERE id = ?1",
+            rusqlite::params![id, attempts, next_attempt_at, error],
+        )
+    }
+
+    fn dead_letter(&self, id: u64, attempts: u32, error: &str) -> crate::Result<()> {
+        self.execute(
+            "UPDATE snap_notification_outbox SET status = ?2, attempts = ?3, last_error = ?4 WHERE id = ?1",
+            rusqlite::params![
+                id,
+                status_to_str(crate::OutboxStatus::DeadLetter),
+                attempts,
+                error
+            ],
+        )
+    }
+
+    fn get(&self, id: u64) -> crate::Result<Option<crate::OutboxEntry>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            &format!("SELECT {COLUMNS} FROM snap_notification_outbox WHERE id = ?1"),
+            [id],
+            entry,
+        ))
+        .map_err(store_error)
+    }
+
+    fn dead_letters(&self) -> crate::Result<Vec<crate::OutboxEntry>> {
+        self.lock()
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM snap_notification_outbox WHERE status = ?1 ORDER BY id"
+            ))
+            .and_then(|mut statement| {
+                statement
+                    .query_map([status_to_str(crate::OutboxStatus::DeadLetter)], entry)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .map_err(store_error)
+    }
+
+    fn requeue(&self, id: u64, now: u64) -> crate::Result<()> {
+        self.execute(
+            "UPDATE snap_notification_outbox \
+             SET status = ?2, attempts = 0, next_attempt_at = ?3 WHERE id = ?1 AND status = ?4",
+            rusqlite::params![
+                id,
+                status_to_str(crate::OutboxStatus::Pending),
+                now,
+                status_to_str(crate::OutboxStatus::DeadLetter),
+            ],
+        )
+    }
+}
@@ -0,0 +1,196 @@
+//! Verification of notifications a bank pushes to us, signed with its private
+//! key, and of the SNAP acknowledgement it expects back:
+//!
+//! ```ignore
+//! let receiver = NotificationReceiver::new(verifier, SERVICE_CODE_NOTIFY)
+//!     .with_mandatory_fields(["originalReferenceNo", "latestTransactionStatus", "amount"]);
+//!
+//! async fn qris_notify(
+//!     request: HttpRequest,
+//!     body: web::Bytes,
+//!     receiver: web::Data<NotificationReceiver>,
+//! ) -> SNAPResponse<QrisNotifyResponse> {
+//!     let result = receiver
+//!         .receive::<QrisNotifyRequest>(&request, &body)
+//!         .and_then(|x| record_payment(x.payload));
+//!
+//!     receiver.respond(result.map(|_| QrisNotifyResponse::default()))
+//! }
+//! ```
+
+/// A verified notification.
+#[derive(Debug, Clone)]
+pub struct Notification<T> {
+    pub payload: T,
+    pub timestamp: String,
+    pub partner_id: Option<String>,
+    pub external_id: Option<String>,
+    pub channel_id: Option<String>,
+}
+
+/// Verifies the notifications of one bank and service.
+#[derive(Clone)]
+pub struct Receiver {
+    verifier: kamu_snap_crypto::AsymmetricCryptoVerifier,
+    service_code: u8,
+    profile: kamu_snap_response::QuirkProfile,
+    mandatory_fields: Vec<String>,
+    timestamp_skew: std::time::Duration,
+}
+
+impl Receiver {
+    pub fn new(verifier: kamu_snap_crypto::AsymmetricCryptoVerifier, service_code: u8) -> Self {
+        Self {
+            verifier,
+            service_code,
+            profile: kamu_snap_response::QuirkProfile::STRICT,
+            mandatory_fields: Vec::new(),
+            timestamp_skew: kamu_snap_response::header::DEFAULT_TIMESTAMP_SKEW,
+        }
+    }
+
+    /// Canonicalise the string to sign with the quirks of the bank.
+    pub fn with_profile(mut self, profile: kamu_snap_response::QuirkProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Top level payload fields reported as `InvalidMandatoryField` when
+    /// missing or null.
+    pub fn with_mandatory_fields<I, F>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<String>,
+    {
+        self.mandatory_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// How far `X-TIMESTAMP` may be from the current time, 5 minutes by
+    /// default.
+    pub fn with_timestamp_skew(mut self, timestamp_skew: std::time::Duration) -> Self {
+        self.timestamp_skew = timestamp_skew;
+        self
+    }
+
+    pub fn service_code(&self) -> u8 {
+        self.service_code
+    }
+
+    pub fn profile(&self) -> &kamu_snap_response::QuirkProfile {
+        &self.profile
+    }
+
+    /// Verify the `X-TIMESTAMP` and `X-SIGNATURE` of `request` over `body`
+    /// and parse the payload.
+    pub fn receive<T: serde::de::DeserializeOwned>(
+        &self,
+        request: &actix_web::HttpRequest,
+        body: &[u8],
+    ) -> kamu_snap_response::Result<Notification<T>> {
+        let headers = request.headers();
+        let timestamp =
+            kamu_snap_response::header::get_mandatory(headers, kamu_snap_response::header::X_TIMESTAMP)?;
+        let signature =
+            kamu_snap_response::header::get_mandatory(headers, kamu_snap_response::header::X_SIGNATURE)?;
+
+        let relative_url = request
+            .uri()
+            .path_and_query()
+            .map_or_else(|| request.path(), |x| x.as_str());
+        self.verify(
+            request.method().as_str(),
+            relative_url,
+            timestamp,
+            signature,
+            body,
+        )?;
+
+        let optional = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|x| x.to_str().ok())
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .map(str::to_owned)
+        };
+
+        Ok(Notification {
+            payload: parse_payload(body, &self.mandatory_fields)?,
+            timestamp: timestamp.to_owned(),
+            partner_id: optional(kamu_snap_response::header::X_PARTNER_ID),
+            external_id: optional(kamu_snap_response::header::X_EXTERNAL_ID),
+            channel_id: optional(kamu_snap_response::header::CHANNEL_ID),
+        })
+    }
+
+    /// Verify a notification timestamp and signature outside of actix.
+    pub fn verify(
+        &self,
+        method: &str,
+        relative_url: &str,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> kamu_snap_response::Result<()> {
+        kamu_snap_response::header::check_timestamp(timestamp, self.timestamp_skew)?;
+
+        let string_to_sign = kamu_snap_crypto::string_to_sign::asymmetric_with(
+            &self.profile,
+            method,
+            relative_url,
+            body,
+            timestamp,
+        );
+        self.verifier.verify_base64(signature, string_to_sign)?;
+
+        Ok(())
+    }
+
+    /// The successful acknowledgement carrying `payload`.
+    pub fn acknowledge<T>(&self, payload: T) -> kamu_snap_response::SNAPResponse<T>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        kamu_snap_response::SNAPResponse::from_payload(payload, self.service_code)
+    }
+
+    pub fn reject<T>(&self, error: kamu_snap_response::ResponseError) -> kamu_snap_response::SNAPResponse<T>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        kamu_snap_response::SNAPResponse::from_error(error, self.service_code)
+    }
+
+    /// Acknowledge or reject depending on how the notification was handled.
+    pub fn respond<T>(&self, result: kamu_snap_response::Result<T>) -> kamu_snap_response::SNAPResponse<T>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        match result {
+            Ok(payload) => self.acknowledge(payload),
+            Err(error) => self.reject(error),
+        }
+    }
+}
+
+/// Parse a JSON payload, reporting the first missing `mandatory` field, or
+/// `BadRequest` when it does not deserialize.
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    body: &[u8],
+    mandatory: &[String],
+) -> kamu_snap_response::Result<T> {
+    let value = serde_json::from_slice::<serde_json::Value>(body)
+        .map_err(|_| kamu_snap_response::ResponseError::BadRequest)?;
+
+    if let Some(field) = mandatory
+        .iter()
+        .find(|x| value.get(x.as_str()).is_none_or(serde_json::Value::is_null))
+    {
+        return Err(kamu_snap_response::ResponseError::InvalidMandatoryField(
+            field.clone(),
+        ));
+    }
+
+    serde_json::from_value(value).map_err(|_| kamu_snap_response::ResponseError::BadRequest)
+}
@@ -0,0 +1,345 @@
+//! Dispatcher delivery, retries and dead letters against a local HTTP stub.
+
+const PATH: &str = "/v1.0/qr/qr-mpm-notify";
+const TOKEN_PATH: &str = "/v1.0/access-token/b2b";
+const SECRET: &str = "secret";
+/// QRIS MPM notify
+const SERVICE_CODE: u8 = 52;
+
+const TOKEN_1: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2007300","responseMessage":"Successful","accessToken":"token-1","tokenType":"Bearer","expiresIn":"900"}"#,
+);
+const TOKEN_2: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2007300","responseMessage":"Successful","accessToken":"token-2","tokenType":"Bearer","expiresIn":"900"}"#,
+);
+
+const ACKNOWLEDGED: (u16, &str) = (
+    200,
+    r#"{"responseCode":"2005200","responseMessage":"Successful"}"#,
+);
+const GENERAL_ERROR: (u16, &str) = (
+    500,
+    r#"{"responseCode":"5005200","responseMessage":"General Error"}"#,
+);
+const INVALID_FIELD: (u16, &str) = (
+    400,
+    r#"{"responseCode":"4005201","responseMessage":"Invalid Field Format amount"}"#,
+);
+const INVALID_TOKEN: (u16, &str) = (
+    401,
+    r#"{"responseCode":"4015201","responseMessage":"Invalid Token (B2B)"}"#,
+);
+
+fn dispatcher<O: kamu_snap_notification::Outbox>(
+    stub: &kamu_snap_testing::Stub,
+    outbox: O,
+    max_attempts: u32,
+) -> kamu_snap_notification::NotificationDispatcher<O> {
+    let config = kamu_snap_notification::DispatcherConfig {
+        retry: kamu_snap_client::RetryPolicy::default().with_default(
+            kamu_snap_client::retry::ServicePolicy {
+                operation: kamu_snap_client::retry::Operation::Idempotent,
+                backoff: kamu_snap_client::retry::Backoff {
+                    initial: std::time::Duration::ZERO,
+                    max: std::time::Duration::ZERO,
+                    max_attempts,
+                },
+            },
+        ),
+        ..Default::default()
+    };
+    let client = kamu_snap_client::SNAPClient::new(
+        kamu_snap_client::SNAPClientConfig::new(&stub.url, "BANK001", "95221"),
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap(),
+        kamu_snap_crypto::SymmetricCrypto::create(SECRET).unwrap(),
+    )
+    .unwrap();
+    let signing = kamu_snap_notification::NotificationSigning::Symmetric(std::sync::Arc::new(client));
+
+    kamu_snap_notification::NotificationDispatcher::new(outbox, config)
+        .unwrap()
+        .with_destination(
+            "merchant",
+            kamu_snap_notification::NotificationDestination::new(&stub.url, "BANK001", "95221", signing),
+        )
+}
+
+fn enqueue<O: kamu_snap_notification::Outbox>(
+    dispatcher: &kamu_snap_notification::NotificationDispatcher<O>,
+) -> u64 {
+    dispatcher
+        .enqueue(
+            "merchant",
+            SERVICE_CODE,
+            PATH,
+            &serde_json::json!({ "originalReferenceNo": "1", "latestTransactionStatus": "00" }),
+        )
+        .unwrap()
+}
+
+fn status<O: kamu_snap_notification::Outbox>(
+    dispatcher: &kamu_snap_notification::NotificationDispatcher<O>,
+    id: u64,
+) -> (kamu_snap_notification::OutboxStatus, u32) {
+    let entry = dispatcher.outbox().get(id).unwrap().unwrap();
+
+    (entry.status, entry.attempts)
+}
+
+async fn delivers_signed_notification<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, ACKNOWLEDGED]);
+    let dispatcher = dispatcher(&stub, outbox, 3);
+    let id = enqueue(&dispatcher);
+
+    assert_eq!(dispatcher.dispatch().await.unwrap(), 1);
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::Delivered, 1)
+    );
+    assert_eq!(dispatcher.dispatch().await.unwrap(), 0);
+
+    let requests = stub.requests();
+    let request = &requests[1];
+    let string_to_sign = kamu_snap_crypto::string_to_sign::symmetric(
+        "POST",
+        PATH,
+        "token-1",
+        &request.body,
+        &request.headers["X-TIMESTAMP"],
+    );
+
+    assert_eq!(request.headers["AUTHORIZATION"], "Bearer token-1");
+    assert_eq!(request.headers["X-PARTNER-ID"], "BANK001");
+    assert_eq!(request.headers["CHANNEL-ID"], "95221");
+    assert!(
+        kamu_snap_crypto::SymmetricCrypto::create(SECRET)
+            .unwrap()
+            .verify(&request.headers["X-SIGNATURE"], string_to_sign)
+            .is_ok()
+    );
+}
+
+async fn retries_transient_failure<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, GENERAL_ERROR, ACKNOWLEDGED]);
+    let dispatcher = dispatcher(&stub, outbox, 3);
+    let id = enqueue(&dispatcher);
+
+    dispatcher.dispatch().await.unwrap();
+    let entry = dispatcher.outbox().get(id).unwrap().unwrap();
+    assert_eq!(
+        (entry.status, entry.attempts),
+        (kamu_snap_notification::OutboxStatus::Pending, 1)
+    );
+    assert!(entry.last_error.is_some());
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::Delivered, 2)
+    );
+
+    let requests = stub.requests();
+    assert_ne!(
+        requests[1].headers["X-EXTERNAL-ID"],
+        requests[2].headers["X-EXTERNAL-ID"]
+    );
+    assert_eq!(requests[1].body, requests[2].body);
+}
+
+async fn dead_letters_rejection_and_requeues<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, INVALID_FIELD, ACKNOWLEDGED]);
+    let dispatcher = dispatcher(&stub, outbox, 3);
+    let id = enqueue(&dispatcher);
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::DeadLetter, 1)
+    );
+    assert_eq!(dispatcher.outbox().dead_letters().unwrap().len(), 1);
+    assert_eq!(dispatcher.dispatch().await.unwrap(), 0);
+
+    dispatcher.outbox().requeue(id, 0).unwrap();
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::Delivered, 1)
+    );
+    assert!(dispatcher.outbox().dead_letters().unwrap().is_empty());
+}
+
+async fn dead_letters_after_max_attempts<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, GENERAL_ERROR, GENERAL_ERROR]);
+    let dispatcher = dispatcher(&stub, outbox, 2);
+    let id = enqueue(&dispatcher);
+
+    dispatcher.dispatch().await.unwrap();
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::DeadLetter, 2)
+    );
+}
+
+async fn refreshes_rejected_token<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, INVALID_TOKEN, TOKEN_2, ACKNOWLEDGED]);
+    let dispatcher = dispatcher(&stub, outbox, 3);
+    let id = enqueue(&dispatcher);
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::Pending, 1)
+    );
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::Delivered, 2)
+    );
+
+    let requests = stub.requests();
+    let paths: Vec<_> = requests.iter().map(|x| x.path.as_str()).collect();
+    assert_eq!(paths, [TOKEN_PATH, PATH, TOKEN_PATH, PATH]);
+    assert_eq!(requests[1].headers["AUTHORIZATION"], "Bearer token-1");
+    assert_eq!(requests[3].headers["AUTHORIZATION"], "Bearer token-2");
+}
+
+async fn dead_letters_interrupted_attempts<O: kamu_snap_notification::Outbox>(outbox: O) {
+    let stub = kamu_snap_testing::Stub::start(Vec::new());
+    let dispatcher = dispatcher(&stub, outbox, 2);
+    let id = enqueue(&dispatcher);
+
+    // Two workers crashing mid delivery, their leases already expired
+    for _ in 0..2 {
+        dispatcher.outbox().claim(i64::MAX as u64, 0, 1).unwrap();
+    }
+
+    assert_eq!(dispatcher.dispatch().await.unwrap(), 1);
+    assert_eq!(
+        status(&dispatcher, id),
+        (kamu_snap_notification::OutboxStatus::DeadLetter, 3)
+    );
+    assert!(stub.requests().is_empty());
+}
+
+#[tokio::test]
+async fn memory_outbox() {
+    delivers_signed_notification(kamu_snap_notification::MemoryOutbox::new()).await;
+    retries_transient_failure(kamu_snap_notification::MemoryOutbox::new()).await;
+    dead_letters_rejection_and_requeues(kamu_snap_notification::MemoryOutbox::new()).await;
+    dead_letters_after_max_attempts(kamu_snap_notification::MemoryOutbox::new()).await;
+    refreshes_rejected_token(kamu_snap_notification::MemoryOutbox::new()).await;
+    dead_letters_interrupted_attempts(kamu_snap_notification::MemoryOutbox::new()).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_outbox() {
+    let outbox = || kamu_snap_notification::SqliteOutbox::open_in_memory().unwrap();
+
+    delivers_signed_notification(outbox()).await;
+    retries_transient_failure(outbox()).await;
+    dead_letters_rejection_and_requeues(outbox()).await;
+    dead_letters_after_max_attempts(outbox()).await;
+    refreshes_rejected_token(outbox()).await;
+    dead_letters_interrupted_attempts(outbox()).await;
+}
+
+#[tokio::test]
+async fn rejects_unknown_destination() {
+    let stub = kamu_snap_testing::Stub::start(Vec::new());
+    let dispatcher = dispatcher(&stub, kamu_snap_notification::MemoryOutbox::new(), 3);
+
+    assert!(matches!(
+        dispatcher.enqueue("unknown", SERVICE_CODE, PATH, &serde_json::json!({})),
+        Err(kamu_snap_notification::NotificationError::UnknownDestination(_))
+    ));
+}
+
+/// Memory outbox failing to record the delivery of `id`.
+struct FailingOutbox {
+    inner: kamu_snap_notification::MemoryOutbox,
+    id: u64,
+}
+
+impl kamu_snap_notification::Outbox for FailingOutbox {
+    fn enqueue(
+        &self,
+        message: kamu_snap_notification::OutboxMessage,
+        now: u64,
+    ) -> kamu_snap_notification::Result<u64> {
+        self.inner.enqueue(message, now)
+    }
+
+    fn claim(
+        &self,
+        now: u64,
+        lease_until: u64,
+        limit: usize,
+    ) -> kamu_snap_notification::Result<Vec<kamu_snap_notification::OutboxEntry>> {
+        self.inner.claim(now, lease_until, limit)
+    }
+
+    fn delivered(&self, id: u64, attempts: u32) -> kamu_snap_notification::Result<()> {
+        if id == self.id {
+            return Err(kamu_snap_notification::NotificationError::Store(
+                "unavailable".to_owned(),
+            ));
+        }
+
+        self.inner.delivered(id, attempts)
+    }
+
+    fn retry(
+        &self,
+        id: u64,
+        attempts: u32,
+        next_attempt_at: u64,
+        error: &str,
+    ) -> kamu_snap_notification::Result<()> {
+        self.inner.retry(id, attempts, next_attempt_at, error)
+    }
+
+    fn dead_letter(&self, id: u64, attempts: u32, error: &str) -> kamu_snap_notification::Result<()> {
+        self.inner.dead_letter(id, attempts, error)
+    }
+
+    fn get(&self, id: u64) -> kamu_snap_notification::Result<Option<kamu_snap_notification::OutboxEntry>> {
+        self.inner.get(id)
+    }
+
+    fn dead_letters(&self) -> kamu_snap_notification::Result<Vec<kamu_snap_notification::OutboxEntry>> {
+        self.inner.dead_letters()
+    }
+
+    fn requeue(&self, id: u64, now: u64) -> kamu_snap_notification::Result<()> {
+        self.inner.requeue(id, now)
+    }
+}
+
+#[tokio::test]
+async fn continues_after_store_failure() {
+    let stub = kamu_snap_testing::Stub::start(vec![TOKEN_1, ACKNOWLEDGED, ACKNOWLEDGED]);
+    let outbox = FailingOutbox {
+        inner: kamu_snap_notification::MemoryOutbox::new(),
+        id: 1,
+    };
+    let dispatcher = dispatcher(&stub, outbox, 3);
+    let first = enqueue(&dispatcher);
+    let second = enqueue(&dispatcher);
+
+    assert_eq!(first, 1);
+    assert_eq!(dispatcher.dispatch().await.unwrap(), 2);
+    // Left leased, to be claimed again once the lease expires
+    assert_eq!(
+        status(&dispatcher, first),
+        (kamu_snap_notification::OutboxStatus::Pending, 1)
+    );
+    assert_eq!(
+        status(&dispatcher, second),
+        (kamu_snap_notification::OutboxStatus::Delivered, 1)
+    );
+}
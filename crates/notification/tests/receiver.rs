@@ -0,0 +1,153 @@
+//! Receiver verification of signed bank callbacks.
+
+const PATH: &str = "/v1.0/qr/qr-mpm-notify";
+const SERVICE_CODE: u8 = 52;
+const BODY: &str = r#"{"originalReferenceNo":"A123","latestTransactionStatus":"00","amount":{"value":"10000.00","currency":"IDR"}}"#;
+
+#[derive(Debug)]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    original_reference_no: String,
+    latest_transaction_status: String,
+    amount: serde_json::Value,
+}
+
+type KeyPair = (
+    kamu_snap_crypto::AsymmetricCryptoSigner,
+    kamu_snap_crypto::AsymmetricCryptoVerifier,
+);
+
+fn key_pair() -> KeyPair {
+    (
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap(),
+        kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap(),
+    )
+}
+
+fn receiver() -> kamu_snap_notification::NotificationReceiver {
+    kamu_snap_notification::NotificationReceiver::new(key_pair().1, SERVICE_CODE).with_mandatory_fields([
+        "originalReferenceNo",
+        "latestTransactionStatus",
+        "amount",
+    ])
+}
+
+fn sign(body: &str, timestamp: &str) -> String {
+    key_pair()
+        .0
+        .sign_as_base64(kamu_snap_crypto::string_to_sign::asymmetric(
+            "POST", PATH, body, timestamp,
+        ))
+}
+
+fn request(timestamp: &str, signature: &str) -> actix_web::HttpRequest {
+    actix_web::test::TestRequest::post()
+        .uri(PATH)
+        .insert_header((kamu_snap_response::header::X_TIMESTAMP, timestamp))
+        .insert_header((kamu_snap_response::header::X_SIGNATURE, signature))
+        .insert_header((kamu_snap_response::header::X_PARTNER_ID, "bank-1"))
+        .insert_header((
+            kamu_snap_response::header::X_EXTERNAL_ID,
+            "41807553358950093184162180797837",
+        ))
+        .to_http_request()
+}
+
+fn receive(body: &str, timestamp: &str, signature: &str) -> kamu_snap_response::Result<Payload> {
+    receiver()
+        .receive::<Payload>(&request(timestamp, signature), body.as_bytes())
+        .map(|x| x.payload)
+}
+
+#[test]
+fn valid_callback() {
+    let timestamp = kamu_snap_response::header::timestamp_now();
+    let notification = receiver()
+        .receive::<Payload>(&request(&timestamp, &sign(BODY, &timestamp)), BODY.as_bytes())
+        .unwrap();
+
+    assert_eq!(notification.payload.original_reference_no, "A123");
+    assert_eq!(notification.payload.latest_transaction_status, "00");
+    assert_eq!(notification.payload.amount["value"], "10000.00");
+    assert_eq!(notification.timestamp, timestamp);
+    assert_eq!(notification.partner_id.as_deref(), Some("bank-1"));
+    assert_eq!(
+        notification.external_id.as_deref(),
+        Some("41807553358950093184162180797837")
+    );
+    assert_eq!(notification.channel_id, None);
+
+    let acknowledgement = serde_json::to_value(receiver().acknowledge(serde_json::json!({}))).unwrap();
+    assert_eq!(acknowledgement["responseCode"], "2005200");
+}
+
+#[test]
+fn bad_signature() {
+    let timestamp = kamu_snap_response::header::timestamp_now();
+    let tampered = BODY.replace("10000.00", "99999.00");
+
+    assert!(matches!(
+        receive(&tampered, &timestamp, &sign(BODY, &timestamp)),
+        Err(kamu_snap_response::ResponseError::Unathorized(_))
+    ));
+    assert!(matches!(
+        receive(BODY, &timestamp, "c2lnbmF0dXJl"),
+        Err(kamu_snap_response::ResponseError::Unathorized(_))
+    ));
+}
+
+#[test]
+fn stale_timestamp() {
+    // Validly signed, but captured long ago
+    let timestamp = "2020-01-01T00:00:00+07:00";
+
+    match receive(BODY, timestamp, &sign(BODY, timestamp)) {
+        Err(kamu_snap_response::ResponseError::Unathorized(reason)) => {
+            assert_eq!(reason, "Invalid Timestamp");
+        }
+        result => panic!("unexpected {result:?}"),
+    }
+    assert!(matches!(
+        receive(BODY, "yesterday", &sign(BODY, "yesterday")),
+        Err(kamu_snap_response::ResponseError::InvalidFieldFormat(x)) if x == "X-TIMESTAMP"
+    ));
+
+    // Accepted within a wider skew
+    receiver()
+        .with_timestamp_skew(std::time::Duration::from_secs(u32::MAX as u64))
+        .receive::<Payload>(&request(timestamp, &sign(BODY, timestamp)), BODY.as_bytes())
+        .unwrap();
+}
+
+#[test]
+fn missing_field() {
+    let timestamp = kamu_snap_response::header::timestamp_now();
+    let missing = |body: &str| match receive(body, &timestamp, &sign(body, &timestamp)) {
+        Err(kamu_snap_response::ResponseError::InvalidMandatoryField(field)) => field,
+        result => panic!("unexpected {result:?}"),
+    };
+
+    assert_eq!(
+        missing(r#"{"latestTransactionStatus":"00","amount":{}}"#),
+        "originalReferenceNo"
+    );
+    assert_eq!(
+        missing(r#"{"originalReferenceNo":"A123","latestTransactionStatus":"00","amount":null}"#),
+        "amount"
+    );
+
+    // Present but of the wrong type
+    let body = r#"{"originalReferenceNo":1,"latestTransactionStatus":"00","amount":{}}"#;
+    assert!(matches!(
+        receive(body, &timestamp, &sign(body, &timestamp)),
+        Err(kamu_snap_response::ResponseError::BadRequest)
+    ));
+
+    // Missing headers
+    let request = actix_web::test::TestRequest::post().uri(PATH).to_http_request();
+    assert!(matches!(
+        receiver().receive::<Payload>(&request, BODY.as_bytes()),
+        Err(kamu_snap_response::ResponseError::InvalidMandatoryField(x)) if x == "X-TIMESTAMP"
+    ));
+}
@@ -0,0 +1,124 @@
+/// One partner entry of the registry file, e.g. in TOML:
+///
+/// ```toml
+/// [[partners]]
+/// partner_id = "82150823919040624621823174737537"
+/// client_secret = "..."
+/// public_key = """
+/// -----BEGIN PUBLIC KEY-----
+/// ...
+/// -----END PUBLIC KEY-----
+/// """
+/// allowed_services = [47, 51]
+/// channel_ids = ["95221"]
+/// ip_whitelist = ["203.0.113.0/24"]
+/// ```
+#[derive(Debug, Clone)]
+#[derive(serde::Deserialize)]
+pub struct PartnerConfig {
+    /// `X-PARTNER-ID`
+    pub partner_id: String,
+    /// `X-CLIENT-KEY` of the access token request, the partner id when unset
+    #[serde(default)]
+    pub client_key: Option<String>,
+    /// HMAC-SHA512 secret of the symmetric signature
+    pub client_secret: kamu_snap_crypto::Secret,
+    /// PEM encoded public key of the asymmetric signature
+    pub public_key: String,
+    /// `hmac-sha512` (default) or `hmac-sha256`
+    #[serde(default)]
+    pub symmetric_algorithm: kamu_snap_crypto::SymmetricAlgorithm,
+    /// `rsa-pkcs1v15-sha256` (default) or `rsa-pss-sha256`
+    #[serde(default)]
+    pub asymmetric_algorithm: kamu_snap_crypto::AsymmetricAlgorithm,
+    /// `base64` (default), `base64url` or `hex`
+    #[serde(default)]
+    pub signature_encoding: kamu_snap_crypto::SignatureEncoding,
+    /// Quirk profile, by name (e.g. `"raw-body"`) or as a table of flags
+    #[serde(default, deserialize_with = "deserialize_profile")]
+    pub profile: kamu_snap_response::QuirkProfile,
+    /// Service codes the partner may call, every service when unset
+    #[serde(default)]
+    pub allowed_services: Option<Vec<u8>>,
+    /// Accepted `CHANNEL-ID` values, any when empty
+    #[serde(default)]
+    pub channel_ids: Vec<String>,
+    /// Addresses or CIDR ranges the partner calls from, any when empty
+    #[serde(default)]
+    pub ip_whitelist: Vec<String>,
+}
+
+fn deserialize_profile<'de, D>(
+    deserializer: D,
+) -> core::result::Result<kamu_snap_response::QuirkProfile, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Profile {
+        Name(String),
+        Flags(kamu_snap_response::QuirkProfile),
+    }
+
+    match <Profile as serde::Deserialize>::deserialize(deserializer)? {
+        Profile::Name(name) => kamu_snap_response::QuirkProfile::by_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown profile {name}"))),
+        Profile::Flags(profile) => Ok(profile),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[derive(serde::Deserialize)]
+pub struct RegistryConfig {
+    #[serde(default)]
+    pub partners: Vec<PartnerConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Format from the file extension, `None` when unknown or not enabled.
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(Self::Toml),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl RegistryConfig {
+    pub fn parse(content: &str, format: Format) -> crate::Result<Self> {
+        let invalid = |x: String| crate::PartnerError::InvalidConfig(x);
+
+        match format {
+            Format::Json => serde_json::from_str(content).map_err(|x| invalid(x.to_string())),
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::from_str(content).map_err(|x| invalid(x.to_string())),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_yaml::from_str(content).map_err(|x| invalid(x.to_string())),
+        }
+    }
+
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| {
+            crate::PartnerError::InvalidConfig(format!("unsupported format of {}", path.display()))
+        })?;
+        let content = std::fs::read_to_string(path)
+            .map_err(|x| crate::PartnerError::InvalidConfig(format!("{}: {x}", path.display())))?;
+
+        Self::parse(&content, format)
+    }
+}
@@ -0,0 +1,37 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Unknown Client")]
+    UnknownClient,
+    #[error("Service {0:02} is not allowed")]
+    ServiceNotAllowed(u8),
+    #[error("Channel is not allowed")]
+    ChannelNotAllowed,
+    #[error("IP address is not allowed")]
+    IpNotAllowed,
+    #[error("Invalid partner configuration: {0}")]
+    InvalidConfig(String),
+    #[error(transparent)]
+    Crypto(#[from] kamu_snap_crypto::CryptoError),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::UnknownClient => {
+                kamu_snap_response::ResponseError::Unathorized("Unknown Client".to_owned())
+            }
+            Error::ServiceNotAllowed(x) => {
+                kamu_snap_response::ResponseError::FeatureNotAllowed(format!("[Service {x:02}]"))
+            }
+            Error::ChannelNotAllowed => kamu_snap_response::ResponseError::InvalidFieldFormat(
+                kamu_snap_response::header::CHANNEL_ID.to_owned(),
+            ),
+            Error::IpNotAllowed => {
+                kamu_snap_response::ResponseError::Unathorized("Client Forbidden Access API".to_owned())
+            }
+            Error::InvalidConfig(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Crypto(x) => x.into(),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod config;
+pub mod error;
+pub mod partner;
+pub mod registry;
+
+pub use config::{Format as ConfigFormat, PartnerConfig, RegistryConfig};
+pub use error::Error as PartnerError;
+pub use partner::Partner;
+pub use registry::Registry as PartnerRegistry;
+
+pub type Result<T> = core::result::Result<T, PartnerError>;
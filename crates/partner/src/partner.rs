@@ -0,0 +1,153 @@
+/// IPv4 or IPv6 address range, a single address being a full length prefix.
+#[derive(Debug, Clone, Copy)]
+struct Network {
+    address: std::net::IpAddr,
+    prefix: u8,
+}
+
+impl Network {
+    fn parse(value: &str) -> crate::Result<Self> {
+        let invalid = || crate::PartnerError::InvalidConfig(format!("invalid IP whitelist entry {value}"));
+        let (address, prefix) = value.split_once('/').map_or((value, None), |(x, y)| (x, Some(y)));
+        let address = address
+            .trim()
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| invalid())?;
+        let length = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(x) => x.trim().parse::<u8>().map_err(|_| invalid())?,
+            None => length,
+        };
+
+        if prefix > length {
+            return Err(invalid());
+        }
+
+        Ok(Self { address, prefix })
+    }
+
+    fn contains(&self, address: std::net::IpAddr) -> bool {
+        let address = match (self.address, address) {
+            (std::net::IpAddr::V6(_), std::net::IpAddr::V4(x)) => std::net::IpAddr::V6(x.to_ipv6_mapped()),
+            (std::net::IpAddr::V4(_), std::net::IpAddr::V6(x)) => match x.to_ipv4_mapped() {
+                Some(x) => std::net::IpAddr::V4(x),
+                None => return false,
+            },
+            _ => address,
+        };
+
+        match (self.address, address) {
+            (std::net::IpAddr::V4(x), std::net::IpAddr::V4(y)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or_default();
+
+                u32::from(x) & mask == u32::from(y) & mask
+            }
+            (std::net::IpAddr::V6(x), std::net::IpAddr::V6(y)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix as u32)
+                    .unwrap_or_default();
+
+                u128::from(x) & mask == u128::from(y) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A registered partner with its signature keys built.
+#[derive(Clone)]
+pub struct Partner {
+    partner_id: String,
+    client_key: String,
+    symmetric: kamu_snap_crypto::SymmetricCrypto,
+    verifier: kamu_snap_crypto::AsymmetricCryptoVerifier,
+    profile: kamu_snap_response::QuirkProfile,
+    allowed_services: Option<std::collections::HashSet<u8>>,
+    channel_ids: std::collections::HashSet<String>,
+    ip_whitelist: Vec<Network>,
+}
+
+impl Partner {
+    pub fn from_config(config: &crate::PartnerConfig) -> crate::Result<Self> {
+        Ok(Self {
+            partner_id: config.partner_id.clone(),
+            client_key: config
+                .client_key
+                .clone()
+                .unwrap_or_else(|| config.partner_id.clone()),
+            symmetric: kamu_snap_crypto::SymmetricCrypto::from_slice(&config.client_secret)?
+                .with_algorithm(config.symmetric_algorithm)
+                .with_encoding(config.signature_encoding),
+            verifier: kamu_snap_crypto::AsymmetricCryptoVerifier::create(&config.public_key)?
+                .with_algorithm(config.asymmetric_algorithm)
+                .with_encoding(config.signature_encoding),
+            profile: config.profile,
+            allowed_services: config
+                .allowed_services
+                .as_ref()
+                .map(|x| x.iter().copied().collect()),
+            channel_ids: config.channel_ids.iter().cloned().collect(),
+            ip_whitelist: config
+                .ip_whitelist
+                .iter()
+                .map(|x| Network::parse(x))
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+
+    pub fn partner_id(&self) -> &str {
+        &self.partner_id
+    }
+
+    pub fn client_key(&self) -> &str {
+        &self.client_key
+    }
+
+    pub fn symmetric(&self) -> &kamu_snap_crypto::SymmetricCrypto {
+        &self.symmetric
+    }
+
+    pub fn verifier(&self) -> &kamu_snap_crypto::AsymmetricCryptoVerifier {
+        &self.verifier
+    }
+
+    pub fn profile(&self) -> &kamu_snap_response::QuirkProfile {
+        &self.profile
+    }
+
+    pub fn check_service(&self, service_code: u8) -> crate::Result<()> {
+        match &self.allowed_services {
+            Some(x) if !x.contains(&service_code) => {
+                Err(crate::PartnerError::ServiceNotAllowed(service_code))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_channel(&self, channel_id: &str) -> crate::Result<()> {
+        if !self.channel_ids.is_empty() && !self.channel_ids.contains(channel_id) {
+            return Err(crate::PartnerError::ChannelNotAllowed);
+        }
+
+        Ok(())
+    }
+
+    pub fn check_ip(&self, address: std::net::IpAddr) -> crate::Result<()> {
+        if !self.ip_whitelist.is_empty() && !self.ip_whitelist.iter().any(|x| x.contains(address)) {
+            return Err(crate::PartnerError::IpNotAllowed);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Partner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Partner")
+            .field("partner_id", &self.partner_id)
+            .field("client_key", &self.client_key)
+            .field("allowed_services", &self.allowed_services)
+            .field("channel_ids", &self.channel_ids)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,182 @@
+#[derive(Debug, Default)]
+struct Partners {
+    by_partner_id: std::collections::HashMap<String, std::sync::Arc<crate::Partner>>,
+    by_client_key: std::collections::HashMap<String, std::sync::Arc<crate::Partner>>,
+}
+
+impl Partners {
+    fn build(config: &crate::RegistryConfig) -> crate::Result<Self> {
+        let mut partners = Self::default();
+
+        for partner_config in &config.partners {
+            let partner = std::sync::Arc::new(crate::Partner::from_config(partner_config)?);
+
+            if partners
+                .by_partner_id
+                .insert(partner.partner_id().to_owned(), partner.clone())
+                .is_some()
+            {
+                return Err(crate::PartnerError::InvalidConfig(format!(
+                    "duplicate partner {}",
+                    partner.partner_id()
+                )));
+            }
+
+            if partners
+                .by_client_key
+                .insert(partner.client_key().to_owned(), partner.clone())
+                .is_some()
+            {
+                return Err(crate::PartnerError::InvalidConfig(format!(
+                    "duplicate client key {}",
+                    partner.client_key()
+                )));
+            }
+        }
+
+        Ok(partners)
+    }
+}
+
+/// Partners known to the service, optionally backed by a configuration file
+/// that can be reloaded without restarting.
+pub struct Registry {
+    path: Option<std::path::PathBuf>,
+    modified: std::sync::Mutex<Option<std::time::SystemTime>>,
+    partners: std::sync::RwLock<std::sync::Arc<Partners>>,
+}
+
+impl Registry {
+    pub fn from_config(config: &crate::RegistryConfig) -> crate::Result<Self> {
+        Ok(Self {
+            path: None,
+            modified: std::sync::Mutex::new(None),
+            partners: std::sync::RwLock::new(std::sync::Arc::new(Partners::build(config)?)),
+        })
+    }
+
+    /// Load the registry from a `.json`, `.toml` or `.yaml` file.
+    pub fn load<P: Into<std::path::PathBuf>>(path: P) -> crate::Result<Self> {
+        let path = path.into();
+        let modified = modified_at(&path);
+        let partners = Partners::build(&crate::RegistryConfig::load(&path)?)?;
+
+        Ok(Self {
+            path: Some(path),
+            modified: std::sync::Mutex::new(modified),
+            partners: std::sync::RwLock::new(std::sync::Arc::new(partners)),
+        })
+    }
+
+    /// Reload the file when it changed since the last load, returning whether
+    /// it did. A file that fails to load leaves the current partners in
+    /// place.
+    pub fn reload(&self) -> crate::Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let mut modified = self
+            .modified
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let current = modified_at(path);
+
+        if current.is_some() && current == *modified {
+            return Ok(false);
+        }
+
+        let partners = Partners::build(&crate::RegistryConfig::load(path)?)?;
+        *self
+            .partners
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = std::sync::Arc::new(partners);
+        *modified = current;
+
+        Ok(true)
+    }
+
+    /// Poll the file every `interval` on a background thread, which stops
+    /// once the registry is dropped.
+    pub fn watch(
+        registry: &std::sync::Arc<Self>,
+        interval: std::time::Duration,
+    ) -> std::thread::JoinHandle<()> {
+        let registry = std::sync::Arc::downgrade(registry);
+
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+
+                let Some(registry) = registry.upgrade() else {
+                    break;
+                };
+
+                match registry.reload() {
+                    Ok(true) => tracing::info!("partner registry reloaded"),
+                    Ok(false) => {}
+                    Err(error) => tracing::error!("partner registry reload failed: {}", error),
+                }
+            }
+        })
+    }
+
+    fn snapshot(&self) -> std::sync::Arc<Partners> {
+        self.partners
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn get(&self, partner_id: &str) -> crate::Result<std::sync::Arc<crate::Partner>> {
+        self.snapshot()
+            .by_partner_id
+            .get(partner_id)
+            .cloned()
+            .ok_or(crate::PartnerError::UnknownClient)
+    }
+
+    pub fn get_by_client_key(&self, client_key: &str) -> crate::Result<std::sync::Arc<crate::Partner>> {
+        self.snapshot()
+            .by_client_key
+            .get(client_key)
+            .cloned()
+            .ok_or(crate::PartnerError::UnknownClient)
+    }
+
+    /// Resolve the partner of a transaction request from its `X-PARTNER-ID`
+    /// and `CHANNEL-ID`, checking it calls from a whitelisted `address` (the
+    /// peer address, or the client address forwarded by a trusted proxy) and
+    /// may call `service_code`.
+    pub fn authorize(
+        &self,
+        partner_id: &str,
+        channel_id: &str,
+        address: std::net::IpAddr,
+        service_code: u8,
+    ) -> crate::Result<std::sync::Arc<crate::Partner>> {
+        let partner = self.get(partner_id)?;
+        partner.check_ip(address)?;
+        partner.check_channel(channel_id)?;
+        partner.check_service(service_code)?;
+
+        Ok(partner)
+    }
+}
+
+impl kamu_snap_token::PartnerKeys for Registry {
+    fn get_verifier(&self, client_key: &str) -> Option<kamu_snap_crypto::AsymmetricCryptoVerifier> {
+        self.get_by_client_key(client_key)
+            .ok()
+            .map(|x| x.verifier().clone())
+    }
+}
+
+impl kamu_snap_response::PartnerProfiles for Registry {
+    fn get_profile(&self, partner_id: &str) -> Option<kamu_snap_response::QuirkProfile> {
+        self.get(partner_id).ok().map(|x| *x.profile())
+    }
+}
+
+fn modified_at(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|x| x.modified()).ok()
+}
@@ -0,0 +1,249 @@
+//! Registry file parsing, partner lookup and authorization, IP whitelists and
+//! reload.
+
+/// QRIS MPM generate
+const SERVICE_CODE: u8 = 47;
+
+fn partner(partner_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "partner_id": partner_id,
+        "client_secret": "secret",
+        "public_key": kamu_snap_testing::PUBLIC_KEY,
+    })
+}
+
+fn registry(
+    partners: Vec<serde_json::Value>,
+) -> kamu_snap_partner::Result<kamu_snap_partner::PartnerRegistry> {
+    let config = kamu_snap_partner::RegistryConfig::parse(
+        &serde_json::json!({ "partners": partners }).to_string(),
+        kamu_snap_partner::ConfigFormat::Json,
+    )?;
+
+    kamu_snap_partner::PartnerRegistry::from_config(&config)
+}
+
+fn ip(value: &str) -> std::net::IpAddr {
+    value.parse().unwrap()
+}
+
+fn whitelisted(ip_whitelist: &[&str]) -> std::sync::Arc<kamu_snap_partner::Partner> {
+    let mut partner = partner("partner-1");
+    partner["ip_whitelist"] = serde_json::json!(ip_whitelist);
+
+    registry(vec![partner]).unwrap().get("partner-1").unwrap()
+}
+
+#[test]
+fn parse_json() {
+    let mut config = partner("partner-1");
+    config["client_key"] = "client-1".into();
+    config["allowed_services"] = serde_json::json!([SERVICE_CODE]);
+    config["channel_ids"] = serde_json::json!(["95221"]);
+    config["profile"] = "raw-body".into();
+    let registry = registry(vec![config, partner("partner-2")]).unwrap();
+
+    let partner = registry.get("partner-1").unwrap();
+    assert_eq!(partner.client_key(), "client-1");
+    assert_eq!(
+        partner.profile(),
+        &kamu_snap_response::QuirkProfile::by_name("raw-body").unwrap()
+    );
+    assert_eq!(
+        kamu_snap_response::PartnerProfiles::get_profile(&registry, "partner-2"),
+        Some(kamu_snap_response::QuirkProfile::STRICT)
+    );
+    assert_eq!(
+        registry.get_by_client_key("client-1").unwrap().partner_id(),
+        "partner-1"
+    );
+    assert!(matches!(
+        registry.get_by_client_key("partner-1"),
+        Err(kamu_snap_partner::PartnerError::UnknownClient)
+    ));
+    // The client key defaults to the partner id
+    assert_eq!(
+        registry.get_by_client_key("partner-2").unwrap().partner_id(),
+        "partner-2"
+    );
+
+    assert!(matches!(
+        kamu_snap_partner::RegistryConfig::parse(
+            r#"{"partners":[{"partner_id":"partner-1"}]}"#,
+            kamu_snap_partner::ConfigFormat::Json,
+        ),
+        Err(kamu_snap_partner::PartnerError::InvalidConfig(_))
+    ));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn parse_toml() {
+    let content = format!(
+        r#"
+[[partners]]
+partner_id = "partner-1"
+client_secret = "secret"
+public_key = """
+{}"""
+allowed_services = [47, 51]
+channel_ids = ["95221"]
+ip_whitelist = ["203.0.113.0/24"]
+
+[partners.profile]
+minify_body = false
+"#,
+        kamu_snap_testing::PUBLIC_KEY
+    );
+    let config =
+        kamu_snap_partner::RegistryConfig::parse(&content, kamu_snap_partner::ConfigFormat::Toml).unwrap();
+
+    assert_eq!(config.partners.len(), 1);
+    assert_eq!(config.partners[0].allowed_services, Some(vec![47, 51]));
+    assert!(!config.partners[0].profile.minify_body);
+
+    let registry = kamu_snap_partner::PartnerRegistry::from_config(&config).unwrap();
+    registry
+        .authorize("partner-1", "95221", ip("203.0.113.7"), SERVICE_CODE)
+        .unwrap();
+}
+
+#[test]
+fn duplicates() {
+    let duplicate = |partners| match registry(partners) {
+        Err(kamu_snap_partner::PartnerError::InvalidConfig(reason)) => reason,
+        result => panic!("unexpected {:?}", result.map(|_| ())),
+    };
+
+    assert_eq!(
+        duplicate(vec![partner("partner-1"), partner("partner-1")]),
+        "duplicate partner partner-1"
+    );
+
+    let mut other = partner("partner-2");
+    other["client_key"] = "client-1".into();
+    let mut colliding = partner("partner-3");
+    colliding["client_key"] = "client-1".into();
+    assert_eq!(duplicate(vec![other, colliding]), "duplicate client key client-1");
+
+    // An explicit client key taking another partner's default one
+    let mut explicit = partner("partner-2");
+    explicit["client_key"] = "partner-1".into();
+    assert_eq!(
+        duplicate(vec![partner("partner-1"), explicit]),
+        "duplicate client key partner-1"
+    );
+}
+
+#[test]
+fn authorize() {
+    let mut config = partner("partner-1");
+    config["allowed_services"] = serde_json::json!([SERVICE_CODE]);
+    config["channel_ids"] = serde_json::json!(["95221"]);
+    config["ip_whitelist"] = serde_json::json!(["203.0.113.0/24"]);
+    let registry = registry(vec![config]).unwrap();
+    let authorize = |partner_id, channel_id, address, service_code| {
+        registry.authorize(partner_id, channel_id, ip(address), service_code)
+    };
+
+    authorize("partner-1", "95221", "203.0.113.7", SERVICE_CODE).unwrap();
+    assert!(matches!(
+        authorize("partner-2", "95221", "203.0.113.7", SERVICE_CODE),
+        Err(kamu_snap_partner::PartnerError::UnknownClient)
+    ));
+    assert!(matches!(
+        authorize("partner-1", "95221", "198.51.100.7", SERVICE_CODE),
+        Err(kamu_snap_partner::PartnerError::IpNotAllowed)
+    ));
+    assert!(matches!(
+        authorize("partner-1", "95222", "203.0.113.7", SERVICE_CODE),
+        Err(kamu_snap_partner::PartnerError::ChannelNotAllowed)
+    ));
+    assert!(matches!(
+        authorize("partner-1", "95221", "203.0.113.7", 51),
+        Err(kamu_snap_partner::PartnerError::ServiceNotAllowed(51))
+    ));
+}
+
+#[test]
+fn ip_whitelist() {
+    let partner = whitelisted(&["203.0.113.0/24", "198.51.100.10", "2001:db8::/32"]);
+
+    // IPv4 range and single address
+    partner.check_ip(ip("203.0.113.0")).unwrap();
+    partner.check_ip(ip("203.0.113.255")).unwrap();
+    partner.check_ip(ip("198.51.100.10")).unwrap();
+    assert!(partner.check_ip(ip("203.0.114.1")).is_err());
+    assert!(partner.check_ip(ip("198.51.100.11")).is_err());
+
+    // IPv6 range
+    partner.check_ip(ip("2001:db8:ffff::1")).unwrap();
+    assert!(partner.check_ip(ip("2001:db9::1")).is_err());
+
+    // IPv4-mapped IPv6 addresses match IPv4 entries
+    partner.check_ip(ip("::ffff:203.0.113.7")).unwrap();
+    assert!(partner.check_ip(ip("::ffff:192.0.2.1")).is_err());
+
+    // And IPv4 addresses match mapped IPv6 entries
+    let partner = whitelisted(&["::ffff:192.0.2.0/120"]);
+    partner.check_ip(ip("192.0.2.9")).unwrap();
+    assert!(partner.check_ip(ip("192.0.3.9")).is_err());
+
+    // Zero length prefixes match every address of the family
+    let partner = whitelisted(&["0.0.0.0/0"]);
+    partner.check_ip(ip("192.0.2.1")).unwrap();
+    assert!(partner.check_ip(ip("2001:db8::1")).is_err());
+
+    // No whitelist allows any address
+    whitelisted(&[]).check_ip(ip("2001:db8::1")).unwrap();
+
+    for entry in ["203.0.113.0/33", "2001:db8::/129", "203.0.113", "203.0.113.0/x"] {
+        let mut config = self::partner("partner-1");
+        config["ip_whitelist"] = serde_json::json!([entry]);
+        assert!(
+            matches!(
+                registry(vec![config]),
+                Err(kamu_snap_partner::PartnerError::InvalidConfig(_))
+            ),
+            "{entry}"
+        );
+    }
+}
+
+#[test]
+fn reload() {
+    let path = std::env::temp_dir().join(format!("kamu-snap-partner-{}.json", std::process::id()));
+    let write = |partners: Vec<serde_json::Value>, modified: std::time::SystemTime| {
+        std::fs::write(&path, serde_json::json!({ "partners": partners }).to_string()).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    };
+    let started = std::time::SystemTime::now();
+
+    write(vec![partner("partner-1")], started);
+    let registry = kamu_snap_partner::PartnerRegistry::load(&path).unwrap();
+    registry.get("partner-1").unwrap();
+    assert!(!registry.reload().unwrap());
+
+    write(
+        vec![partner("partner-2")],
+        started + std::time::Duration::from_secs(1),
+    );
+    assert!(registry.reload().unwrap());
+    assert!(registry.get("partner-1").is_err());
+    registry.get("partner-2").unwrap();
+
+    // A broken file keeps the loaded partners
+    write(
+        vec![partner("partner-3"), partner("partner-3")],
+        started + std::time::Duration::from_secs(2),
+    );
+    assert!(registry.reload().is_err());
+    registry.get("partner-2").unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+}
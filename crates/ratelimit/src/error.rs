@@ -0,0 +1,29 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Too Many Requests")]
+    TooManyRequests { retry_after: std::time::Duration },
+    #[error("Activity Count Limit Exceeded")]
+    ActivityCountLimitExceeded { retry_after: std::time::Duration },
+}
+
+impl Error {
+    /// Time until the rejected request would be admitted.
+    pub fn retry_after(&self) -> std::time::Duration {
+        match self {
+            Error::TooManyRequests { retry_after } => *retry_after,
+            Error::ActivityCountLimitExceeded { retry_after } => *retry_after,
+        }
+    }
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::TooManyRequests { .. } => kamu_snap_response::ResponseError::TooManyRequests,
+            Error::ActivityCountLimitExceeded { .. } => {
+                kamu_snap_response::ResponseError::ActivityCountLimitExceeded
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub mod error;
+pub mod limiter;
+pub mod middleware;
+pub mod policy;
+
+pub use error::Error as RateLimitError;
+pub use limiter::Limiter as RateLimiter;
+pub use middleware::RateLimited;
+pub use policy::{
+    CustomerKey, Mode as RateLimitMode, Policy as RateLimitPolicy, Rule as RateLimitRule,
+    Scope as RateLimitScope, Violation,
+};
+
+pub type Result<T> = core::result::Result<T, RateLimitError>;
@@ -0,0 +1,202 @@
+/// Stale counters are swept after this many checks.
+const PURGE_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    TokenBucket {
+        tokens: f64,
+        updated_at: std::time::Instant,
+    },
+    FixedWindow {
+        count: u32,
+        started_at: std::time::Instant,
+    },
+}
+
+impl State {
+    fn new(mode: &crate::RateLimitMode, now: std::time::Instant) -> Self {
+        match mode {
+            crate::RateLimitMode::TokenBucket { capacity, .. } => State::TokenBucket {
+                tokens: *capacity as f64,
+                updated_at: now,
+            },
+            crate::RateLimitMode::FixedWindow { .. } => State::FixedWindow {
+                count: 0,
+                started_at: now,
+            },
+        }
+    }
+
+    /// The state after admitting one more request, or how long until one
+    /// would be admitted.
+    fn admit(
+        self,
+        mode: &crate::RateLimitMode,
+        now: std::time::Instant,
+    ) -> core::result::Result<Self, std::time::Duration> {
+        match (self, mode) {
+            (
+                State::TokenBucket { tokens, updated_at },
+                crate::RateLimitMode::TokenBucket {
+                    capacity,
+                    refill_every,
+                },
+            ) => {
+                let refilled = now.duration_since(updated_at).as_secs_f64() / refill_every.as_secs_f64();
+                let tokens = (tokens + refilled).min(*capacity as f64);
+
+                if tokens >= 1.0 {
+                    Ok(State::TokenBucket {
+                        tokens: tokens - 1.0,
+                        updated_at: now,
+                    })
+                } else {
+                    Err(refill_every.mul_f64(1.0 - tokens))
+                }
+            }
+            (
+                State::FixedWindow { count, started_at },
+                crate::RateLimitMode::FixedWindow { limit, window },
+            ) => {
+                let (count, started_at) = if now.duration_since(started_at) >= *window {
+                    (0, now)
+                } else {
+                    (count, started_at)
+                };
+
+                if count < *limit {
+                    Ok(State::FixedWindow {
+                        count: count + 1,
+                        started_at,
+                    })
+                } else {
+                    Err((started_at + *window).saturating_duration_since(now))
+                }
+            }
+            _ => State::new(mode, now).admit(mode, now),
+        }
+    }
+
+    /// `true` once the counter is back to its initial state.
+    fn is_idle(&self, mode: &crate::RateLimitMode, now: std::time::Instant) -> bool {
+        match (self, mode) {
+            (
+                State::TokenBucket { tokens, updated_at },
+                crate::RateLimitMode::TokenBucket {
+                    capacity,
+                    refill_every,
+                },
+            ) => {
+                let missing = *capacity as f64 - tokens;
+
+                now.duration_since(*updated_at) >= refill_every.mul_f64(missing.max(0.0))
+            }
+            (State::FixedWindow { started_at, .. }, crate::RateLimitMode::FixedWindow { window, .. }) => {
+                now.duration_since(*started_at) >= *window
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    service_code: u8,
+    rule: usize,
+    partner_id: String,
+    customer: Option<String>,
+}
+
+/// In-process rate limiter applying a [`crate::RateLimitPolicy`].
+#[derive(Debug)]
+pub struct Limiter {
+    policy: crate::RateLimitPolicy,
+    states: std::sync::Mutex<(u64, std::collections::HashMap<Key, State>)>,
+}
+
+impl Limiter {
+    pub fn new(policy: crate::RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            states: std::sync::Mutex::new((0, std::collections::HashMap::new())),
+        }
+    }
+
+    pub fn policy(&self) -> &crate::RateLimitPolicy {
+        &self.policy
+    }
+
+    /// Count one request of `partner_id` to `service_code`. `customer`
+    /// resolves the customer of [`crate::RateLimitScope::Customer`] rules.
+    /// Either every rule admits the request or none of them counts it.
+    pub fn check<F>(&self, service_code: u8, partner_id: &str, customer: F) -> crate::Result<()>
+    where
+        F: Fn(&crate::CustomerKey) -> Option<String>,
+    {
+        let rules = self.policy.get(service_code);
+        let now = std::time::Instant::now();
+        let mut guard = self
+            .states
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let (checks, states) = &mut *guard;
+
+        *checks += 1;
+        if *checks % PURGE_INTERVAL == 0 {
+            Self::purge(&self.policy, states, now);
+        }
+
+        let mut admitted = Vec::with_capacity(rules.len());
+
+        for (index, rule) in rules.iter().enumerate() {
+            let customer = match &rule.scope {
+                crate::RateLimitScope::Partner => None,
+                crate::RateLimitScope::Customer(key) => match customer(key) {
+                    Some(customer) => Some(customer),
+                    None => continue,
+                },
+            };
+            let key = Key {
+                service_code,
+                rule: index,
+                partner_id: partner_id.to_owned(),
+                customer,
+            };
+            let state = states
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| State::new(&rule.mode, now));
+
+            match state.admit(&rule.mode, now) {
+                Ok(state) => admitted.push((key, state)),
+                Err(retry_after) => {
+                    return Err(match rule.violation {
+                        crate::Violation::TooManyRequests => {
+                            crate::RateLimitError::TooManyRequests { retry_after }
+                        }
+                        crate::Violation::ActivityCountLimitExceeded => {
+                            crate::RateLimitError::ActivityCountLimitExceeded { retry_after }
+                        }
+                    });
+                }
+            }
+        }
+
+        states.extend(admitted);
+
+        Ok(())
+    }
+
+    fn purge(
+        policy: &crate::RateLimitPolicy,
+        states: &mut std::collections::HashMap<Key, State>,
+        now: std::time::Instant,
+    ) {
+        states.retain(|key, state| {
+            policy
+                .get(key.service_code)
+                .get(key.rule)
+                .is_some_and(|rule| !state.is_idle(&rule.mode, now))
+        });
+    }
+}
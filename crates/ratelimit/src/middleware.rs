@@ -0,0 +1,168 @@
+//! Actix middleware applying a [`crate::RateLimiter`] to an endpoint:
+//!
+//! ```ignore
+//! let limiter = Arc::new(RateLimiter::new(
+//!     RateLimitPolicy::default()
+//!         .with_default(RateLimitRule::partner(RateLimitMode::per_second(50)))
+//!         .with_service(
+//!             kamu_snap_model::emoney::SERVICE_CODE_TOP_UP,
+//!             RateLimitRule::customer(RateLimitMode::per_day(10), CustomerKey::BodyField("customerNumber".into())),
+//!         ),
+//! ));
+//!
+//! web::resource("/v1.0/emoney/topup")
+//!     .wrap(RateLimited::new(limiter.clone(), kamu_snap_model::emoney::SERVICE_CODE_TOP_UP))
+//!     .route(web::post().to(top_up))
+//! ```
+//!
+//! The partner is identified by `X-PARTNER-ID`, or `X-CLIENT-KEY` on the
+//! access token endpoints. Rejected requests get the SNAP error envelope and
+//! a `Retry-After` header.
+//!
+//! Only actix is provided. Other stacks, e.g. tower services, call
+//! [`RateLimiter::check`](crate::RateLimiter::check) themselves.
+
+type LocalBoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T>>>;
+
+pub struct RateLimited {
+    limiter: std::sync::Arc<crate::RateLimiter>,
+    service_code: u8,
+}
+
+impl RateLimited {
+    pub fn new(limiter: std::sync::Arc<crate::RateLimiter>, service_code: u8) -> Self {
+        Self {
+            limiter,
+            service_code,
+        }
+    }
+}
+
+impl<N, B> actix_web::dev::Transform<N, actix_web::dev::ServiceRequest> for RateLimited
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = Middleware<N>;
+    type InitError = ();
+    type Future = std::future::Ready<core::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: N) -> Self::Future {
+        std::future::ready(Ok(Middleware {
+            limiter: self.limiter.clone(),
+            service_code: self.service_code,
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub struct Middleware<N> {
+    limiter: std::sync::Arc<crate::RateLimiter>,
+    service_code: u8,
+    service: std::rc::Rc<N>,
+}
+
+impl<N, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for Middleware<N>
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<core::result::Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, mut request: actix_web::dev::ServiceRequest) -> Self::Future {
+        let limiter = self.limiter.clone();
+        let service_code = self.service_code;
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let body = if limiter.policy().needs_body(service_code) {
+                let body = request.extract::<actix_web::web::Bytes>().await?;
+                request.set_payload(actix_web::dev::Payload::from(body.clone()));
+
+                serde_json::from_slice::<serde_json::Value>(&body).ok()
+            } else {
+                None
+            };
+
+            match check(&limiter, service_code, &request, body.as_ref()) {
+                Ok(()) => service.call(request).await.map(|x| x.map_into_boxed_body()),
+                Err(error) => Ok(reject(request, error, service_code)),
+            }
+        })
+    }
+}
+
+fn check(
+    limiter: &crate::RateLimiter,
+    service_code: u8,
+    request: &actix_web::dev::ServiceRequest,
+    body: Option<&serde_json::Value>,
+) -> core::result::Result<(), Rejection> {
+    let headers = request.headers();
+    let partner_id = [
+        kamu_snap_response::header::X_PARTNER_ID,
+        kamu_snap_response::header::X_CLIENT_KEY,
+    ]
+    .into_iter()
+    .find_map(|x| headers.get(x)?.to_str().ok())
+    .ok_or_else(|| {
+        Rejection::Invalid(kamu_snap_response::ResponseError::InvalidMandatoryField(
+            kamu_snap_response::header::X_PARTNER_ID.to_owned(),
+        ))
+    })?;
+
+    limiter
+        .check(service_code, partner_id, |key| match key {
+            crate::CustomerKey::Header(name) => headers.get(name)?.to_str().ok().map(str::to_owned),
+            crate::CustomerKey::BodyField(name) => body?.get(name)?.as_str().map(str::to_owned),
+        })
+        .map_err(Rejection::Limited)
+}
+
+enum Rejection {
+    Invalid(kamu_snap_response::ResponseError),
+    Limited(crate::RateLimitError),
+}
+
+fn reject(
+    request: actix_web::dev::ServiceRequest,
+    rejection: Rejection,
+    service_code: u8,
+) -> actix_web::dev::ServiceResponse<actix_web::body::BoxBody> {
+    let (error, retry_after) = match rejection {
+        Rejection::Invalid(error) => (error, None),
+        Rejection::Limited(error) => (
+            kamu_snap_response::ResponseError::from(error.clone()),
+            Some(error.retry_after()),
+        ),
+    };
+    let mut response = actix_web::Responder::respond_to(
+        kamu_snap_response::SNAPResponse::<serde_json::Value>::from_error(error, service_code),
+        request.request(),
+    );
+
+    if let Some(retry_after) = retry_after {
+        // Whole seconds, rounded up so a client honouring it is admitted
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response.headers_mut().insert(
+            actix_web::http::header::RETRY_AFTER,
+            actix_web::http::header::HeaderValue::from(seconds.max(1)),
+        );
+    }
+
+    request.into_response(response)
+}
@@ -0,0 +1,121 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Allows bursts of up to `capacity` requests, refilled by one token every
+    /// `refill_every`
+    TokenBucket {
+        capacity: u32,
+        refill_every: std::time::Duration,
+    },
+    /// At most `limit` requests per `window`, counted from the first request
+    /// of the window
+    FixedWindow { limit: u32, window: std::time::Duration },
+}
+
+impl Mode {
+    pub fn per_second(limit: u32) -> Self {
+        Self::FixedWindow {
+            limit,
+            window: std::time::Duration::from_secs(1),
+        }
+    }
+
+    pub fn per_day(limit: u32) -> Self {
+        Self::FixedWindow {
+            limit,
+            window: std::time::Duration::from_secs(24 * 3600),
+        }
+    }
+}
+
+/// Where the customer or account a request acts on is read from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CustomerKey {
+    /// Request header, e.g. `X-DEVICE-ID` or `X-Authorization-Customer`
+    Header(String),
+    /// Top level string field of the JSON body, e.g. `customerNumber`
+    BodyField(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// One counter per partner and service code
+    Partner,
+    /// One counter per partner, service code and customer. Requests without
+    /// the customer key are not counted.
+    Customer(CustomerKey),
+}
+
+/// The response a request over the limit gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// Transport level throttling, HTTP 429
+    TooManyRequests,
+    /// Business level transaction frequency limit, HTTP 403
+    ActivityCountLimitExceeded,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub mode: Mode,
+    pub scope: Scope,
+    pub violation: Violation,
+}
+
+impl Rule {
+    /// Partner wide throttling answered with `TooManyRequests`.
+    pub fn partner(mode: Mode) -> Self {
+        Self {
+            mode,
+            scope: Scope::Partner,
+            violation: Violation::TooManyRequests,
+        }
+    }
+
+    /// Per customer frequency limit answered with `ActivityCountLimitExceeded`.
+    pub fn customer(mode: Mode, key: CustomerKey) -> Self {
+        Self {
+            mode,
+            scope: Scope::Customer(key),
+            violation: Violation::ActivityCountLimitExceeded,
+        }
+    }
+
+    pub fn with_violation(mut self, violation: Violation) -> Self {
+        self.violation = violation;
+
+        self
+    }
+}
+
+/// Per service code rate limit rules. Services without an explicit entry use
+/// the default rules, which are empty unless set.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    default: Vec<Rule>,
+    services: std::collections::HashMap<u8, Vec<Rule>>,
+}
+
+impl Policy {
+    pub fn with_default(mut self, rule: Rule) -> Self {
+        self.default.push(rule);
+
+        self
+    }
+
+    pub fn with_service(mut self, service_code: u8, rule: Rule) -> Self {
+        self.services.entry(service_code).or_default().push(rule);
+
+        self
+    }
+
+    pub fn get(&self, service_code: u8) -> &[Rule] {
+        self.services.get(&service_code).unwrap_or(&self.default)
+    }
+
+    /// `true` when a rule of `service_code` reads the customer from the body.
+    pub fn needs_body(&self, service_code: u8) -> bool {
+        self.get(service_code)
+            .iter()
+            .any(|x| matches!(x.scope, Scope::Customer(CustomerKey::BodyField(_))))
+    }
+}
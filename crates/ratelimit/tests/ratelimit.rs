@@ -0,0 +1,237 @@
+//! Token bucket and fixed window counting, multi-rule admission and the
+//! rejection envelope of the middleware.
+
+const PARTNER_ID: &str = "partner-1";
+/// E-money top up
+const SERVICE_CODE: u8 = 38;
+const PATH: &str = "/v1.0/emoney/topup";
+
+fn device(name: &str) -> impl Fn(&kamu_snap_ratelimit::CustomerKey) -> Option<String> + '_ {
+    move |_| Some(name.to_owned())
+}
+
+fn no_customer(_: &kamu_snap_ratelimit::CustomerKey) -> Option<String> {
+    None
+}
+
+fn limiter(rule: kamu_snap_ratelimit::RateLimitRule) -> kamu_snap_ratelimit::RateLimiter {
+    kamu_snap_ratelimit::RateLimiter::new(
+        kamu_snap_ratelimit::RateLimitPolicy::default().with_service(SERVICE_CODE, rule),
+    )
+}
+
+#[test]
+fn token_bucket_refills() {
+    let refill_every = std::time::Duration::from_millis(100);
+    let limiter = limiter(kamu_snap_ratelimit::RateLimitRule::partner(
+        kamu_snap_ratelimit::RateLimitMode::TokenBucket {
+            capacity: 2,
+            refill_every,
+        },
+    ));
+
+    // Burst up to the capacity
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    let error = limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap_err();
+    assert!(matches!(
+        error,
+        kamu_snap_ratelimit::RateLimitError::TooManyRequests { .. }
+    ));
+    assert!(error.retry_after() <= refill_every);
+
+    // Other partners have their own bucket
+    limiter.check(SERVICE_CODE, "partner-2", no_customer).unwrap();
+
+    std::thread::sleep(refill_every);
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    assert!(limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).is_err());
+}
+
+#[test]
+fn fixed_window_resets() {
+    let window = std::time::Duration::from_millis(100);
+    let limiter = limiter(kamu_snap_ratelimit::RateLimitRule::partner(
+        kamu_snap_ratelimit::RateLimitMode::FixedWindow { limit: 2, window },
+    ));
+
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    let error = limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap_err();
+    assert!(error.retry_after() <= window);
+
+    std::thread::sleep(window);
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    assert!(limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).is_err());
+}
+
+#[test]
+fn rejected_request_counts_for_no_rule() {
+    let limiter = kamu_snap_ratelimit::RateLimiter::new(
+        kamu_snap_ratelimit::RateLimitPolicy::default()
+            .with_service(
+                SERVICE_CODE,
+                kamu_snap_ratelimit::RateLimitRule::partner(kamu_snap_ratelimit::RateLimitMode::per_day(3)),
+            )
+            .with_service(
+                SERVICE_CODE,
+                kamu_snap_ratelimit::RateLimitRule::customer(
+                    kamu_snap_ratelimit::RateLimitMode::per_day(1),
+                    kamu_snap_ratelimit::CustomerKey::Header("X-DEVICE-ID".to_owned()),
+                ),
+            ),
+    );
+
+    limiter
+        .check(SERVICE_CODE, PARTNER_ID, device("device-1"))
+        .unwrap();
+    assert!(matches!(
+        limiter.check(SERVICE_CODE, PARTNER_ID, device("device-1")),
+        Err(kamu_snap_ratelimit::RateLimitError::ActivityCountLimitExceeded { .. })
+    ));
+
+    // The rejected request left the partner rule at 1 of 3
+    limiter
+        .check(SERVICE_CODE, PARTNER_ID, device("device-2"))
+        .unwrap();
+    limiter
+        .check(SERVICE_CODE, PARTNER_ID, device("device-3"))
+        .unwrap();
+    assert!(matches!(
+        limiter.check(SERVICE_CODE, PARTNER_ID, device("device-4")),
+        Err(kamu_snap_ratelimit::RateLimitError::TooManyRequests { .. })
+    ));
+}
+
+#[test]
+fn customer_rule_skips_requests_without_customer() {
+    let limiter = limiter(kamu_snap_ratelimit::RateLimitRule::customer(
+        kamu_snap_ratelimit::RateLimitMode::per_day(1),
+        kamu_snap_ratelimit::CustomerKey::BodyField("customerNumber".to_owned()),
+    ));
+
+    for _ in 0..3 {
+        limiter.check(SERVICE_CODE, PARTNER_ID, no_customer).unwrap();
+    }
+    // Services without rules are not limited
+    for _ in 0..3 {
+        limiter
+            .check(SERVICE_CODE + 1, PARTNER_ID, device("device-1"))
+            .unwrap();
+    }
+}
+
+async fn handle() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok().json(serde_json::json!({"responseCode": "2003800"}))
+}
+
+macro_rules! service {
+    ($rule:expr) => {
+        actix_web::test::init_service(
+            actix_web::App::new().service(
+                actix_web::web::resource(PATH)
+                    .wrap(kamu_snap_ratelimit::RateLimited::new(
+                        std::sync::Arc::new(limiter($rule)),
+                        SERVICE_CODE,
+                    ))
+                    .route(actix_web::web::post().to(handle)),
+            ),
+        )
+        .await
+    };
+}
+
+fn request(body: &str) -> actix_web::test::TestRequest {
+    actix_web::test::TestRequest::post()
+        .uri(PATH)
+        .insert_header((kamu_snap_response::header::X_PARTNER_ID, PARTNER_ID))
+        .insert_header(actix_web::http::header::ContentType::json())
+        .set_payload(body.to_owned())
+}
+
+#[actix_web::test]
+async fn middleware_throttles_partner() {
+    let service = service!(kamu_snap_ratelimit::RateLimitRule::partner(
+        kamu_snap_ratelimit::RateLimitMode::TokenBucket {
+            capacity: 1,
+            refill_every: std::time::Duration::from_secs(30),
+        },
+    ));
+
+    let response = actix_web::test::call_service(&service, request("{}").to_request()).await;
+    assert_eq!(response.status(), 200);
+
+    let response = actix_web::test::call_service(&service, request("{}").to_request()).await;
+    assert_eq!(response.status(), 429);
+    // Just under 30s left, rounded up
+    assert_eq!(
+        response
+            .headers()
+            .get(actix_web::http::header::RETRY_AFTER)
+            .unwrap(),
+        "30"
+    );
+    let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(body["responseCode"], "4293800");
+}
+
+#[actix_web::test]
+async fn middleware_limits_customer_from_body() {
+    let service = service!(kamu_snap_ratelimit::RateLimitRule::customer(
+        kamu_snap_ratelimit::RateLimitMode::FixedWindow {
+            limit: 1,
+            window: std::time::Duration::from_secs(60),
+        },
+        kamu_snap_ratelimit::CustomerKey::BodyField("customerNumber".to_owned()),
+    ));
+    let body = r#"{"customerNumber":"628123456789"}"#;
+
+    let response = actix_web::test::call_service(&service, request(body).to_request()).await;
+    assert_eq!(response.status(), 200);
+    // The handler still gets the body
+    let body_json: serde_json::Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(body_json["responseCode"], "2003800");
+
+    let response = actix_web::test::call_service(&service, request(body).to_request()).await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(
+        response
+            .headers()
+            .get(actix_web::http::header::RETRY_AFTER)
+            .unwrap(),
+        "60"
+    );
+    let body_json: serde_json::Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(body_json["responseCode"], "4033804");
+
+    // Another customer is admitted
+    let response = actix_web::test::call_service(
+        &service,
+        request(r#"{"customerNumber":"628987654321"}"#).to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+}
+
+#[actix_web::test]
+async fn middleware_requires_partner() {
+    let service = service!(kamu_snap_ratelimit::RateLimitRule::partner(
+        kamu_snap_ratelimit::RateLimitMode::per_second(10)
+    ));
+
+    let response = actix_web::test::call_service(
+        &service,
+        actix_web::test::TestRequest::post().uri(PATH).to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+    assert!(
+        response
+            .headers()
+            .get(actix_web::http::header::RETRY_AFTER)
+            .is_none()
+    );
+    let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(body["responseCode"], "4003802");
+}
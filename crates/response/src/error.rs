@@ -431,4 +431,91 @@ impl Error {
 
         http_status_code + service_code + case_code
     }
+
+    /// Reverse of [`Error::get_code`], e.g. to decode the `responseCode` of a
+    /// SNAP response. Variants carrying a reason get it from
+    /// `response_message`. Returns `None` for success and unknown codes.
+    pub fn from_code(response_code: u32, response_message: &str) -> Option<Self> {
+        let http_status_code = response_code / 10_000;
+        let case_code = response_code % 100;
+        let reason = |prefix: &str, suffix: &str| {
+            let message = response_message.trim();
+            let message = message.strip_prefix(prefix).unwrap_or(message);
+            let message = message.strip_suffix(suffix).unwrap_or(message);
+
+            message.trim().to_owned()
+        };
+
+        let error = match (http_status_code, case_code) {
+            (400, 00) => Self::BadRequest,
+            (400, 01) => Self::InvalidFieldFormat(reason("Invalid Field Format", "")),
+            (400, 02) => Self::InvalidMandatoryField(reason("Invalid Mandatory Field", "")),
+            (401, 00) => Self::Unathorized(reason("Unauthorized.", "")),
+            (401, 01) => Self::InvalidTokenB2B,
+            (401, 02) => Self::InvalidCustomerToken,
+            (401, 03) => Self::TokenNotFoundB2B,
+            (401, 04) => Self::CustomerTokenNotFound,
+            (403, 00) => Self::TransactionExpired,
+            (403, 01) => Self::FeatureNotAllowed(reason("Feature Not Allowed", "")),
+            (403, 02) => Self::ExceedsTransactionAmountLimit,
+            (403, 03) => Self::SuspectedFraud,
+            (403, 04) => Self::ActivityCountLimitExceeded,
+            (403, 05) => Self::DoNotHonor,
+            (403, 06) => Self::FeatureNotAllowedAtThisTime(reason("Feature Not Allowed At This Time.", "")),
+            (403, 07) => Self::CardBlocked,
+            (403, 08) => Self::CardExpired,
+            (403, 09) => Self::DormantAccount,
+            (403, 10) => Self::NeedToSetTokenLimit,
+            (403, 11) => Self::OTPBlocked,
+            (403, 12) => Self::OTPLifetimeExpired,
+            (403, 13) => Self::OTPSentToCardholder,
+            (403, 14) => Self::InsufficientFunds,
+            (403, 15) => Self::TransactionNotPermitted(reason("Transaction Not Permitted.", "")),
+            (403, 16) => Self::SuspendTransaction,
+            (403, 17) => Self::TokenLimitExceeded,
+            (403, 18) => Self::InactiveCardOrAccountOrCustomer,
+            (403, 19) => Self::MerchantBlacklisted,
+            (403, 20) => Self::MerchantLimitExceed,
+            (403, 21) => Self::SetLimitNotAllowed,
+            (403, 22) => Self::TokenLimitInvalid,
+            (403, 23) => Self::AccountLimitExceed,
+            (404, 00) => Self::InvalidTransactionStatus,
+            (404, 01) => Self::TransactionNotFound,
+            (404, 02) => Self::InvalidRouting,
+            (404, 03) => Self::BankNotSupportedBySwitch,
+            (404, 04) => Self::TransactionCancelled,
+            (404, 05) => Self::MerchantNotRegisteredForCardRegistrationServices,
+            (404, 06) => Self::NeedToRequestOTP,
+            (404, 07) => Self::JourneyNotFound,
+            (404, 08) => Self::InvalidMerchant,
+            (404, 09) => Self::NoIssuer,
+            (404, 10) => Self::InvalidAPITransition,
+            (404, 11) => Self::InvalidCardOrAccountOrCustomerOrVirtualAccount(reason(
+                "Invalid Card/Account/Customer",
+                "/Virtual Account",
+            )),
+            (404, 12) => {
+                Self::InvalidBillOrVirtualAccountWithReason(reason("Invalid Bill/Virtual Account", ""))
+            }
+            (404, 13) => Self::InvalidAmount,
+            (404, 14) => Self::PaidBill,
+            (404, 15) => Self::InvalidOTP,
+            (404, 16) => Self::PartnerNotFound,
+            (404, 17) => Self::InvalidTerminal,
+            (404, 18) => Self::InconsistentRequest,
+            (404, 19) => Self::InvalidBillOrVirtualAccount,
+            (405, 00) => Self::RequestedFunctionIsNotSupported,
+            (405, 01) => Self::RequestedOperationIsNotAllowed,
+            (409, 00) => Self::Conflict,
+            (409, 01) => Self::DuplicatePartnerReferenceNo,
+            (429, 00) => Self::TooManyRequests,
+            (500, 00) => Self::GeneralError,
+            (500, 01) => Self::InternalServerError,
+            (500, 02) => Self::ExternalServerError,
+            (504, 00) => Self::Timeout,
+            _ => return None,
+        };
+
+        Some(error)
+    }
 }
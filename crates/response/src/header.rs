@@ -0,0 +1,60 @@
+pub const AUTHORIZATION: &str = "Authorization";
+pub const AUTHORIZATION_CUSTOMER: &str = "X-Authorization-Customer";
+pub const CHANNEL_ID: &str = "CHANNEL-ID";
+pub const X_CLIENT_KEY: &str = "X-CLIENT-KEY";
+pub const X_DEVICE_ID: &str = "X-DEVICE-ID";
+pub const X_EXTERNAL_ID: &str = "X-EXTERNAL-ID";
+pub const X_IP_ADDRESS: &str = "X-IP-ADDRESS";
+pub const X_PARTNER_ID: &str = "X-PARTNER-ID";
+pub const X_REQUEST_ID: &str = "X-REQUEST-ID";
+pub const X_SIGNATURE: &str = "X-SIGNATURE";
+pub const X_TIMESTAMP: &str = "X-TIMESTAMP";
+
+/// Default tolerance of [`check_timestamp`] between the sender's clock and
+/// ours.
+pub const DEFAULT_TIMESTAMP_SKEW: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Value of a mandatory SNAP header, reported as
+/// [`InvalidMandatoryField`](crate::ResponseError::InvalidMandatoryField) when
+/// missing, empty or not valid ASCII.
+pub fn get_mandatory<'a>(
+    headers: &'a actix_web::http::header::HeaderMap,
+    name: &str,
+) -> crate::Result<&'a str> {
+    headers
+        .get(name)
+        .and_then(|x| x.to_str().ok())
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .ok_or_else(|| crate::ResponseError::InvalidMandatoryField(name.to_owned()))
+}
+
+/// WIB (UTC+7), the time zone SNAP timestamps, operating hours and daily
+/// limits are expressed in.
+pub fn wib() -> chrono::FixedOffset {
+    chrono::FixedOffset::east_opt(7 * 3600).expect("WIB offset is valid")
+}
+
+/// Current time in the `X-TIMESTAMP` format, ISO 8601 in WIB (UTC+7), e.g.
+/// `2020-01-01T00:00:00+07:00`.
+pub fn timestamp_now() -> String {
+    chrono::Utc::now()
+        .with_timezone(&wib())
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+/// Check an `X-TIMESTAMP` value: ISO 8601, reported as
+/// [`InvalidFieldFormat`](crate::ResponseError::InvalidFieldFormat)
+/// otherwise, and no further than `skew` from the current time, reported as
+/// [`Unathorized`](crate::ResponseError::Unathorized) otherwise so that a
+/// captured request cannot be replayed later.
+pub fn check_timestamp(value: &str, skew: std::time::Duration) -> crate::Result<()> {
+    let timestamp = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|_| crate::ResponseError::InvalidFieldFormat(X_TIMESTAMP.to_owned()))?;
+    let drift = (chrono::Utc::now() - timestamp.to_utc()).abs();
+
+    match drift.to_std() {
+        Ok(drift) if drift <= skew => Ok(()),
+        _ => Err(crate::ResponseError::Unathorized("Invalid Timestamp".to_owned())),
+    }
+}
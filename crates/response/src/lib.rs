@@ -3,11 +3,16 @@
 
 pub mod category;
 pub mod error;
+pub mod header;
+pub mod middleware;
+pub mod profile;
 
 mod macros;
 
 pub use category::Category as ResponseCategory;
 pub use error::Error as ResponseError;
+pub use middleware::{PartnerProfiles, ResponseHeaders, Signer as ResponseSigner};
+pub use profile::Profile as QuirkProfile;
 
 pub type Result<T> = core::result::Result<T, ResponseError>;
 
@@ -51,6 +56,35 @@ impl SNAPResponseCommon {
         }
     }
 
+    pub fn response_code(&self) -> &str {
+        &self.response_code
+    }
+
+    pub fn response_message(&self) -> &str {
+        &self.response_message
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.response_code.starts_with('2')
+    }
+
+    /// The error this envelope reports, `None` on success. Codes unknown to
+    /// [`ResponseError::from_code`] fall back on their HTTP status class.
+    pub fn to_error(&self) -> Option<crate::ResponseError> {
+        if self.is_success() {
+            return None;
+        }
+
+        let response_code = self.response_code.parse::<u32>().unwrap_or_default();
+
+        let fallback = match response_code / 1_000_000 {
+            4 => crate::ResponseError::BadRequest,
+            _ => crate::ResponseError::GeneralError,
+        };
+
+        Some(crate::ResponseError::from_code(response_code, &self.response_message).unwrap_or(fallback))
+    }
+
     pub fn http_code(&self) -> Option<actix_web::http::StatusCode> {
         self.http_code
     }
@@ -80,6 +114,14 @@ where
         self.payload.as_ref()
     }
 
+    pub fn get_common(&self) -> Option<&SNAPResponseCommon> {
+        self.common.as_ref()
+    }
+
+    pub fn into_payload(self) -> Option<T> {
+        self.payload
+    }
+
     pub fn from_payload(payload: T, service_code: u8) -> Self {
         let common = SNAPResponseCommon::success(service_code, 0);
 
@@ -97,6 +139,19 @@ where
             payload: None,
         }
     }
+
+    /// Deserialize a response body sent by a counterparty with the quirks of
+    /// `profile`.
+    pub fn from_slice_with(body: &[u8], profile: &crate::QuirkProfile) -> serde_json::Result<Self> {
+        if *profile == crate::QuirkProfile::STRICT {
+            return serde_json::from_slice(body);
+        }
+
+        let mut value = serde_json::from_slice::<serde_json::Value>(body)?;
+        profile.normalize(&mut value);
+
+        serde_json::from_value(value)
+    }
 }
 
 impl<'de, T> serde::Deserialize<'de> for SNAPResponse<T>
@@ -152,8 +207,7 @@ where
                         let http_code =
                             actix_web::http::StatusCode::from_u16((response_code / 10_000) as u16)
                                 .map_err(|x| serde::de::Error::custom(x.to_string()))?;
-                        let service_code =
-                            (response_code - ((http_code.as_u16() as u32) * 10_000) / 100) as u8;
+                        let service_code = ((response_code % 10_000) / 100) as u8;
                         common_response.http_code = Some(http_code);
                         common_response.service_code = Some(service_code);
                         deserialized_response.common = Some(common_response);
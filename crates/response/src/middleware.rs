@@ -0,0 +1,203 @@
+//! Actix middleware adding the standard SNAP response headers: `X-TIMESTAMP`,
+//! the `X-EXTERNAL-ID` and `X-REQUEST-ID` of the request echoed back and,
+//! with a [`Signer`], an `X-SIGNATURE` over the serialised body:
+//!
+//! ```ignore
+//! App::new()
+//!     .wrap(
+//!         ResponseHeaders::new()
+//!             .with_signer(Arc::new(signer))
+//!             .with_partner_profiles(registry.clone()),
+//!     )
+//!     .service(web::resource("/v1.0/balance-inquiry").route(web::post().to(balance_inquiry)))
+//! ```
+//!
+//! The string to sign follows the quirk profile of the calling partner, by
+//! `X-PARTNER-ID`, or the default one. Headers already set by the handler are
+//! left untouched.
+
+type LocalBoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T>>>;
+
+/// Signs serialised response bodies. `kamu-snap-crypto` implements it for its
+/// asymmetric and symmetric signers.
+pub trait Signer: Send + Sync {
+    /// Sign `body`, sent at `timestamp` in response to `method` on
+    /// `relative_url`, building the string to sign as `profile` does.
+    fn sign_response(
+        &self,
+        profile: &crate::QuirkProfile,
+        method: &str,
+        relative_url: &str,
+        body: &[u8],
+        timestamp: &str,
+    ) -> String;
+}
+
+impl<S: Signer + ?Sized> Signer for std::sync::Arc<S> {
+    fn sign_response(
+        &self,
+        profile: &crate::QuirkProfile,
+        method: &str,
+        relative_url: &str,
+        body: &[u8],
+        timestamp: &str,
+    ) -> String {
+        (**self).sign_response(profile, method, relative_url, body, timestamp)
+    }
+}
+
+/// Quirk profile of each partner, e.g. the partner registry.
+pub trait PartnerProfiles: Send + Sync {
+    fn get_profile(&self, partner_id: &str) -> Option<crate::QuirkProfile>;
+}
+
+impl<P: PartnerProfiles + ?Sized> PartnerProfiles for std::sync::Arc<P> {
+    fn get_profile(&self, partner_id: &str) -> Option<crate::QuirkProfile> {
+        (**self).get_profile(partner_id)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ResponseHeaders {
+    signer: Option<std::sync::Arc<dyn Signer>>,
+    profile: crate::QuirkProfile,
+    partner_profiles: Option<std::sync::Arc<dyn PartnerProfiles>>,
+}
+
+impl ResponseHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_signer(mut self, signer: std::sync::Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Profile of unknown partners and requests without `X-PARTNER-ID`,
+    /// [`QuirkProfile::STRICT`](crate::QuirkProfile::STRICT) by default.
+    pub fn with_profile(mut self, profile: crate::QuirkProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn with_partner_profiles(mut self, partner_profiles: std::sync::Arc<dyn PartnerProfiles>) -> Self {
+        self.partner_profiles = Some(partner_profiles);
+        self
+    }
+}
+
+impl<N, B> actix_web::dev::Transform<N, actix_web::dev::ServiceRequest> for ResponseHeaders
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = Middleware<N>;
+    type InitError = ();
+    type Future = std::future::Ready<core::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: N) -> Self::Future {
+        std::future::ready(Ok(Middleware {
+            signer: self.signer.clone(),
+            profile: self.profile,
+            partner_profiles: self.partner_profiles.clone(),
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub struct Middleware<N> {
+    signer: Option<std::sync::Arc<dyn Signer>>,
+    profile: crate::QuirkProfile,
+    partner_profiles: Option<std::sync::Arc<dyn PartnerProfiles>>,
+    service: std::rc::Rc<N>,
+}
+
+impl<N, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for Middleware<N>
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<core::result::Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: actix_web::dev::ServiceRequest) -> Self::Future {
+        let signer = self.signer.clone();
+        let service = self.service.clone();
+        let method = request.method().clone();
+        let relative_url = request
+            .uri()
+            .path_and_query()
+            .map_or_else(|| request.path().to_owned(), |x| x.as_str().to_owned());
+        let echoed = [crate::header::X_EXTERNAL_ID, crate::header::X_REQUEST_ID]
+            .into_iter()
+            .filter_map(|name| Some((header_name(name), request.headers().get(name)?.clone())))
+            .collect::<Vec<_>>();
+        let profile = self
+            .partner_profiles
+            .as_ref()
+            .zip(request.headers().get(crate::header::X_PARTNER_ID))
+            .and_then(|(profiles, partner_id)| profiles.get_profile(partner_id.to_str().ok()?))
+            .unwrap_or(self.profile);
+
+        Box::pin(async move {
+            let mut response = service.call(request).await?;
+            let headers = response.headers_mut();
+
+            if !headers.contains_key(crate::header::X_TIMESTAMP) {
+                let timestamp =
+                    actix_web::http::header::HeaderValue::from_str(&crate::header::timestamp_now())
+                        .expect("timestamp is a valid header value");
+                headers.insert(header_name(crate::header::X_TIMESTAMP), timestamp);
+            }
+
+            for (name, value) in echoed {
+                if !headers.contains_key(&name) {
+                    headers.insert(name, value);
+                }
+            }
+
+            let Some(signer) = signer.filter(|_| !headers.contains_key(crate::header::X_SIGNATURE)) else {
+                return Ok(response.map_into_boxed_body());
+            };
+
+            let (request, response) = response.into_parts();
+            let (mut head, body) = response.into_parts();
+            let body = actix_web::body::to_bytes(body)
+                .await
+                .map_err(|x| actix_web::error::ErrorInternalServerError(x.into()))?;
+            let timestamp = head
+                .headers()
+                .get(crate::header::X_TIMESTAMP)
+                .and_then(|x| x.to_str().ok())
+                .unwrap_or_default();
+            let signature = signer.sign_response(&profile, method.as_str(), &relative_url, &body, timestamp);
+            let signature = actix_web::http::header::HeaderValue::from_str(&signature)
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+            head.headers_mut()
+                .insert(header_name(crate::header::X_SIGNATURE), signature);
+
+            Ok(actix_web::dev::ServiceResponse::new(
+                request,
+                head.set_body(body).map_into_boxed_body(),
+            ))
+        })
+    }
+}
+
+fn header_name(name: &'static str) -> actix_web::http::header::HeaderName {
+    actix_web::http::header::HeaderName::from_bytes(name.as_bytes()).expect("SNAP header names are valid")
+}
@@ -0,0 +1,90 @@
+//! Bank quirk profiles. Providers implement SNAP with small deviations in
+//! how the string to sign is canonicalised and how the response envelope is
+//! shaped; a [`Profile`] captures them per counterparty, [`Profile::STRICT`]
+//! being the standard.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Keep the query string in the `EndpointUrl` of the string to sign
+    pub sign_query_string: bool,
+    /// Minify a JSON body before hashing it, instead of hashing it as sent
+    pub minify_body: bool,
+    /// Accept `responseCode` as a JSON number
+    pub numeric_response_code: bool,
+    /// Accept a response without `responseMessage`
+    pub optional_response_message: bool,
+}
+
+impl Profile {
+    /// SNAP as specified.
+    pub const STRICT: Self = Self {
+        sign_query_string: true,
+        minify_body: true,
+        numeric_response_code: false,
+        optional_response_message: false,
+    };
+
+    /// Standard signing, tolerant response parsing.
+    pub const LENIENT: Self = Self {
+        numeric_response_code: true,
+        optional_response_message: true,
+        ..Self::STRICT
+    };
+
+    /// Body hashed byte for byte as sent.
+    pub const RAW_BODY: Self = Self {
+        minify_body: false,
+        ..Self::STRICT
+    };
+
+    /// Query string left out of the string to sign.
+    pub const PATH_ONLY: Self = Self {
+        sign_query_string: false,
+        ..Self::STRICT
+    };
+
+    /// `strict`, `lenient`, `raw-body` or `path-only`.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "strict" => Some(Self::STRICT),
+            "lenient" => Some(Self::LENIENT),
+            "raw-body" => Some(Self::RAW_BODY),
+            "path-only" => Some(Self::PATH_ONLY),
+            _ => None,
+        }
+    }
+
+    /// The `EndpointUrl` to sign for `relative_url`.
+    pub fn endpoint_url<'a>(&self, relative_url: &'a str) -> &'a str {
+        if self.sign_query_string {
+            relative_url
+        } else {
+            relative_url.split_once('?').map_or(relative_url, |(x, _)| x)
+        }
+    }
+
+    /// Rewrite a response envelope into its strict SNAP shape.
+    pub fn normalize(&self, value: &mut serde_json::Value) {
+        let Some(object) = value.as_object_mut() else {
+            return;
+        };
+
+        if self.numeric_response_code
+            && let Some(code) = object.get("responseCode").and_then(serde_json::Value::as_u64)
+        {
+            object.insert("responseCode".to_owned(), code.to_string().into());
+        }
+
+        if self.optional_response_message && !object.contains_key("responseMessage") {
+            object.insert("responseMessage".to_owned(), String::new().into());
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
@@ -0,0 +1,162 @@
+//! Standard response headers and response signing by the middleware.
+
+const PATH: &str = "/v1.0/balance-inquiry";
+const BODY: &str = r#"{"responseCode":"2001100","responseMessage":"Successful"}"#;
+
+/// Signs as `<method> <endpoint url> <body> <timestamp>`, showing what the
+/// middleware passed in.
+struct Signer;
+
+impl kamu_snap_response::ResponseSigner for Signer {
+    fn sign_response(
+        &self,
+        profile: &kamu_snap_response::QuirkProfile,
+        method: &str,
+        relative_url: &str,
+        body: &[u8],
+        timestamp: &str,
+    ) -> String {
+        format!(
+            "{method} {} {} {timestamp}",
+            profile.endpoint_url(relative_url),
+            String::from_utf8_lossy(body)
+        )
+    }
+}
+
+struct Profiles;
+
+impl kamu_snap_response::PartnerProfiles for Profiles {
+    fn get_profile(&self, partner_id: &str) -> Option<kamu_snap_response::QuirkProfile> {
+        (partner_id == "BANK001").then_some(kamu_snap_response::QuirkProfile::PATH_ONLY)
+    }
+}
+
+async fn handle() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok().body(BODY)
+}
+
+/// Sets every header the middleware would add itself.
+async fn handle_with_headers() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok()
+        .insert_header((
+            kamu_snap_response::header::X_TIMESTAMP,
+            "2024-01-01T07:00:00+07:00",
+        ))
+        .insert_header((kamu_snap_response::header::X_EXTERNAL_ID, "handler"))
+        .insert_header((kamu_snap_response::header::X_SIGNATURE, "handler"))
+        .body(BODY)
+}
+
+macro_rules! service {
+    ($middleware:expr) => {
+        service!($middleware, handle)
+    };
+    ($middleware:expr, $handle:expr) => {
+        actix_web::test::init_service(
+            actix_web::App::new()
+                .wrap($middleware)
+                .service(actix_web::web::resource(PATH).route(actix_web::web::post().to($handle))),
+        )
+        .await
+    };
+}
+
+fn header<'a>(response: &'a actix_web::dev::ServiceResponse, name: &str) -> Option<&'a str> {
+    response.headers().get(name).map(|x| x.to_str().unwrap())
+}
+
+#[actix_web::test]
+async fn echoes_request_headers() {
+    let service = service!(kamu_snap_response::ResponseHeaders::new());
+    let request = actix_web::test::TestRequest::post()
+        .uri(PATH)
+        .insert_header((kamu_snap_response::header::X_EXTERNAL_ID, "41807553358950093184"))
+        .insert_header((kamu_snap_response::header::X_REQUEST_ID, "request-1"))
+        .to_request();
+
+    let response = actix_web::test::call_service(&service, request).await;
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_EXTERNAL_ID),
+        Some("41807553358950093184")
+    );
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_REQUEST_ID),
+        Some("request-1")
+    );
+    assert!(header(&response, kamu_snap_response::header::X_TIMESTAMP).is_some());
+    assert_eq!(header(&response, kamu_snap_response::header::X_SIGNATURE), None);
+    assert_eq!(actix_web::test::read_body(response).await, BODY);
+}
+
+#[actix_web::test]
+async fn signs_body() {
+    let service =
+        service!(kamu_snap_response::ResponseHeaders::new().with_signer(std::sync::Arc::new(Signer)));
+    let request = actix_web::test::TestRequest::post()
+        .uri(&format!("{PATH}?lang=id"))
+        .to_request();
+
+    let response = actix_web::test::call_service(&service, request).await;
+    let timestamp = header(&response, kamu_snap_response::header::X_TIMESTAMP).unwrap();
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_SIGNATURE).unwrap(),
+        format!("POST {PATH}?lang=id {BODY} {timestamp}")
+    );
+    // The body survives signing
+    assert_eq!(actix_web::test::read_body(response).await, BODY);
+}
+
+#[actix_web::test]
+async fn keeps_handler_headers() {
+    let service = service!(
+        kamu_snap_response::ResponseHeaders::new().with_signer(std::sync::Arc::new(Signer)),
+        handle_with_headers
+    );
+    let request = actix_web::test::TestRequest::post()
+        .uri(PATH)
+        .insert_header((kamu_snap_response::header::X_EXTERNAL_ID, "41807553358950093184"))
+        .to_request();
+
+    let response = actix_web::test::call_service(&service, request).await;
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_TIMESTAMP),
+        Some("2024-01-01T07:00:00+07:00")
+    );
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_EXTERNAL_ID),
+        Some("handler")
+    );
+    // Not signed again
+    assert_eq!(
+        header(&response, kamu_snap_response::header::X_SIGNATURE),
+        Some("handler")
+    );
+}
+
+#[actix_web::test]
+async fn signs_with_partner_profile() {
+    let service = service!(
+        kamu_snap_response::ResponseHeaders::new()
+            .with_signer(std::sync::Arc::new(Signer))
+            .with_partner_profiles(std::sync::Arc::new(Profiles))
+    );
+    let sign = async |partner_id: &str| {
+        let request = actix_web::test::TestRequest::post()
+            .uri(&format!("{PATH}?lang=id"))
+            .insert_header((kamu_snap_response::header::X_PARTNER_ID, partner_id))
+            .to_request();
+        let response = actix_web::test::call_service(&service, request).await;
+        let timestamp = header(&response, kamu_snap_response::header::X_TIMESTAMP).unwrap();
+        let signature = header(&response, kamu_snap_response::header::X_SIGNATURE).unwrap();
+
+        signature.replace(timestamp, "<timestamp>")
+    };
+
+    assert_eq!(sign("BANK001").await, format!("POST {PATH} {BODY} <timestamp>"));
+    // Unknown partners get the default profile
+    assert_eq!(
+        sign("BANK002").await,
+        format!("POST {PATH}?lang=id {BODY} <timestamp>")
+    );
+}
@@ -0,0 +1,81 @@
+//! Response envelope test vectors per quirk profile.
+
+#[derive(Debug, Clone, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    reference_no: String,
+}
+
+const STANDARD: &str = r#"{"responseCode":"2001100","responseMessage":"Successful","referenceNo":"1"}"#;
+const NUMERIC_CODE: &str = r#"{"responseCode":2001100,"responseMessage":"Successful","referenceNo":"1"}"#;
+const NO_MESSAGE: &str = r#"{"responseCode":"4011101","referenceNo":"1"}"#;
+
+fn parse(
+    body: &str,
+    profile: &kamu_snap_response::QuirkProfile,
+) -> serde_json::Result<kamu_snap_response::SNAPResponse<Payload>> {
+    kamu_snap_response::SNAPResponse::from_slice_with(body.as_bytes(), profile)
+}
+
+#[test]
+fn strict() {
+    let profile = kamu_snap_response::QuirkProfile::STRICT;
+    let response = parse(STANDARD, &profile).unwrap();
+    let common = response.get_common().unwrap();
+
+    assert_eq!(common.response_code(), "2001100");
+    assert_eq!(common.service_code(), Some(11));
+    assert_eq!(response.get_payload().unwrap().reference_no, "1");
+    assert!(parse(NUMERIC_CODE, &profile).is_err());
+    assert!(parse(NO_MESSAGE, &profile).is_err());
+}
+
+#[test]
+fn lenient() {
+    let profile = kamu_snap_response::QuirkProfile::LENIENT;
+    let response = parse(NUMERIC_CODE, &profile).unwrap();
+    let common = response.get_common().unwrap();
+
+    assert_eq!(common.response_code(), "2001100");
+    assert!(common.is_success());
+    assert_eq!(response.get_payload().unwrap().reference_no, "1");
+
+    let response = parse(NO_MESSAGE, &profile).unwrap();
+    let common = response.get_common().unwrap();
+
+    assert_eq!(common.response_message(), "");
+    assert!(matches!(
+        common.to_error(),
+        Some(kamu_snap_response::ResponseError::InvalidTokenB2B)
+    ));
+}
+
+#[test]
+fn signing_only_profiles_parse_strictly() {
+    for profile in [
+        kamu_snap_response::QuirkProfile::RAW_BODY,
+        kamu_snap_response::QuirkProfile::PATH_ONLY,
+    ] {
+        assert!(parse(STANDARD, &profile).is_ok());
+        assert!(parse(NUMERIC_CODE, &profile).is_err());
+        assert!(parse(NO_MESSAGE, &profile).is_err());
+    }
+}
+
+#[test]
+fn by_name() {
+    assert_eq!(
+        kamu_snap_response::QuirkProfile::by_name("strict"),
+        Some(kamu_snap_response::QuirkProfile::STRICT)
+    );
+    assert_eq!(
+        kamu_snap_response::QuirkProfile::by_name("raw-body"),
+        Some(kamu_snap_response::QuirkProfile::RAW_BODY)
+    );
+    assert_eq!(kamu_snap_response::QuirkProfile::by_name("unknown"), None);
+    assert_eq!(
+        kamu_snap_response::QuirkProfile::PATH_ONLY.endpoint_url("/v1.0/balance-inquiry?lang=id"),
+        "/v1.0/balance-inquiry"
+    );
+}
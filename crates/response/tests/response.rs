@@ -0,0 +1,32 @@
+//! Response envelope deserialisation.
+
+#[derive(Debug, Clone, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    reference_no: String,
+}
+
+#[test]
+fn service_code() {
+    let parse = |body: &str| {
+        let response: kamu_snap_response::SNAPResponse<Payload> = serde_json::from_str(body).unwrap();
+        let common = response.get_common().unwrap();
+
+        (common.http_code(), common.service_code())
+    };
+
+    assert_eq!(
+        parse(r#"{"responseCode":"2001100","responseMessage":"Successful","referenceNo":"1"}"#),
+        (Some(actix_web::http::StatusCode::OK), Some(11))
+    );
+    // HTTP status and service code differ
+    assert_eq!(
+        parse(r#"{"responseCode":"4011101","responseMessage":"Invalid Token (B2B)","referenceNo":"1"}"#),
+        (Some(actix_web::http::StatusCode::UNAUTHORIZED), Some(11))
+    );
+    assert_eq!(
+        parse(r#"{"responseCode":"5004701","responseMessage":"General Error","referenceNo":"1"}"#),
+        (Some(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR), Some(47))
+    );
+}
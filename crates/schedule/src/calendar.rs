@@ -0,0 +1,62 @@
+/// Non-working days, e.g. Indonesian public holidays and collective leave
+/// (cuti bersama), on top of weekends.
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    holidays: std::collections::BTreeMap<chrono::NaiveDate, String>,
+}
+
+impl Calendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One holiday per line, `YYYY-MM-DD` followed by its name, e.g.
+    /// `2025-08-17 Hari Kemerdekaan`. Blank lines and lines starting with
+    /// `#` are ignored.
+    pub fn parse(content: &str) -> crate::Result<Self> {
+        let mut calendar = Self::new();
+
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (date, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|x| crate::ScheduleError::InvalidCalendar(format!("line {}: {x}", number + 1)))?;
+            calendar = calendar.with_holiday(date, name.trim());
+        }
+
+        Ok(calendar)
+    }
+
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|x| crate::ScheduleError::InvalidCalendar(x.to_string()))?;
+
+        Self::parse(&content)
+    }
+
+    pub fn with_holiday<N: Into<String>>(mut self, date: chrono::NaiveDate, name: N) -> Self {
+        self.holidays.insert(date, name.into());
+
+        self
+    }
+
+    pub fn holiday(&self, date: chrono::NaiveDate) -> Option<&str> {
+        self.holidays.get(&date).map(String::as_str)
+    }
+
+    pub fn is_weekend(date: chrono::NaiveDate) -> bool {
+        matches!(
+            chrono::Datelike::weekday(&date),
+            chrono::Weekday::Sat | chrono::Weekday::Sun
+        )
+    }
+
+    pub fn is_business_day(&self, date: chrono::NaiveDate) -> bool {
+        !Self::is_weekend(date) && self.holiday(date).is_none()
+    }
+}
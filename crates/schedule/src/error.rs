@@ -0,0 +1,19 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Feature Not Allowed At This Time. {0}")]
+    NotAllowedAtThisTime(String),
+    #[error("Invalid calendar: {0}")]
+    InvalidCalendar(String),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::NotAllowedAtThisTime(x) => {
+                kamu_snap_response::ResponseError::FeatureNotAllowedAtThisTime(x)
+            }
+            Error::InvalidCalendar(_) => kamu_snap_response::ResponseError::InternalServerError,
+        }
+    }
+}
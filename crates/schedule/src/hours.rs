@@ -0,0 +1,80 @@
+/// Daily operating window in WIB, e.g. the RTGS and SKN cut-off times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatingHours {
+    pub open: chrono::NaiveTime,
+    /// Exclusive. A close before `open` spans midnight, a close equal to
+    /// `open` means the whole day.
+    pub close: chrono::NaiveTime,
+    pub on_weekends: bool,
+    pub on_holidays: bool,
+}
+
+impl OperatingHours {
+    /// Window on business days only.
+    pub fn new(open: chrono::NaiveTime, close: chrono::NaiveTime) -> Self {
+        Self {
+            open,
+            close,
+            on_weekends: false,
+            on_holidays: false,
+        }
+    }
+
+    /// Shorthand for [`OperatingHours::new`] from `HH:MM` pairs, panics on an
+    /// invalid time.
+    pub fn between(open: (u32, u32), close: (u32, u32)) -> Self {
+        let time = |(hour, minute)| chrono::NaiveTime::from_hms_opt(hour, minute, 0).expect("valid time");
+
+        Self::new(time(open), time(close))
+    }
+
+    /// Open every day, all day.
+    pub fn always() -> Self {
+        Self::new(chrono::NaiveTime::MIN, chrono::NaiveTime::MIN)
+            .with_weekends()
+            .with_holidays()
+    }
+
+    pub fn with_weekends(mut self) -> Self {
+        self.on_weekends = true;
+
+        self
+    }
+
+    pub fn with_holidays(mut self) -> Self {
+        self.on_holidays = true;
+
+        self
+    }
+
+    pub fn contains(&self, time: chrono::NaiveTime) -> bool {
+        match self.open.cmp(&self.close) {
+            std::cmp::Ordering::Equal => true,
+            std::cmp::Ordering::Less => self.open <= time && time < self.close,
+            std::cmp::Ordering::Greater => self.open <= time || time < self.close,
+        }
+    }
+
+    /// Business day the window around `at` belongs to: the previous day past
+    /// midnight in a window spanning it.
+    pub fn opened_on(&self, at: chrono::NaiveDateTime) -> chrono::NaiveDate {
+        let date = at.date();
+
+        if self.open > self.close && at.time() < self.close {
+            date.pred_opt().unwrap_or(date)
+        } else {
+            date
+        }
+    }
+}
+
+impl std::fmt::Display for OperatingHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{} WIB",
+            self.open.format("%H:%M"),
+            self.close.format("%H:%M")
+        )
+    }
+}
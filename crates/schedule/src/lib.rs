@@ -0,0 +1,13 @@
+pub mod calendar;
+pub mod error;
+pub mod hours;
+pub mod middleware;
+pub mod schedule;
+
+pub use calendar::Calendar;
+pub use error::Error as ScheduleError;
+pub use hours::OperatingHours;
+pub use middleware::Scheduled;
+pub use schedule::{Maintenance, Schedule};
+
+pub type Result<T> = core::result::Result<T, ScheduleError>;
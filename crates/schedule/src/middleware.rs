@@ -0,0 +1,93 @@
+//! Actix middleware rejecting requests outside a service's operating hours
+//! with `FeatureNotAllowedAtThisTime`:
+//!
+//! ```ignore
+//! let schedule = Arc::new(
+//!     Schedule::new(Calendar::load("holidays.txt")?)
+//!         .with_service(SERVICE_CODE_RTGS, OperatingHours::between((8, 0), (15, 0))),
+//! );
+//!
+//! web::resource("/v1.0/transfer-interbank")
+//!     .wrap(Scheduled::new(schedule.clone(), SERVICE_CODE_RTGS))
+//!     .route(web::post().to(transfer))
+//! ```
+
+type LocalBoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T>>>;
+
+pub struct Scheduled {
+    schedule: std::sync::Arc<crate::Schedule>,
+    service_code: u8,
+}
+
+impl Scheduled {
+    pub fn new(schedule: std::sync::Arc<crate::Schedule>, service_code: u8) -> Self {
+        Self {
+            schedule,
+            service_code,
+        }
+    }
+}
+
+impl<N, B> actix_web::dev::Transform<N, actix_web::dev::ServiceRequest> for Scheduled
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = Middleware<N>;
+    type InitError = ();
+    type Future = std::future::Ready<core::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: N) -> Self::Future {
+        std::future::ready(Ok(Middleware {
+            schedule: self.schedule.clone(),
+            service_code: self.service_code,
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub struct Middleware<N> {
+    schedule: std::sync::Arc<crate::Schedule>,
+    service_code: u8,
+    service: std::rc::Rc<N>,
+}
+
+impl<N, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for Middleware<N>
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<core::result::Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: actix_web::dev::ServiceRequest) -> Self::Future {
+        if let Err(error) = self.schedule.check_now(self.service_code) {
+            let response = actix_web::Responder::respond_to(
+                kamu_snap_response::SNAPResponse::<serde_json::Value>::from_error(
+                    error.into(),
+                    self.service_code,
+                ),
+                request.request(),
+            );
+
+            return Box::pin(std::future::ready(Ok(request.into_response(response))));
+        }
+
+        let service = self.service.clone();
+
+        Box::pin(async move { service.call(request).await.map(|x| x.map_into_boxed_body()) })
+    }
+}
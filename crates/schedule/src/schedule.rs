@@ -0,0 +1,127 @@
+#[derive(Debug, Clone)]
+pub struct Maintenance {
+    pub start: chrono::DateTime<chrono::FixedOffset>,
+    pub end: chrono::DateTime<chrono::FixedOffset>,
+    pub reason: String,
+    /// Affected service codes, every service when `None`
+    pub services: Option<Vec<u8>>,
+}
+
+impl Maintenance {
+    pub fn new<R: Into<String>>(
+        start: chrono::DateTime<chrono::FixedOffset>,
+        end: chrono::DateTime<chrono::FixedOffset>,
+        reason: R,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            reason: reason.into(),
+            services: None,
+        }
+    }
+
+    pub fn for_services(mut self, services: Vec<u8>) -> Self {
+        self.services = Some(services);
+
+        self
+    }
+
+    fn applies(&self, service_code: u8, at: chrono::DateTime<chrono::Utc>) -> bool {
+        self.start <= at && at < self.end && self.services.as_ref().is_none_or(|x| x.contains(&service_code))
+    }
+}
+
+/// Per service operating hours, holidays and maintenance windows. Services
+/// without their own hours use the default ones and are always open when
+/// there is none.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    calendar: crate::Calendar,
+    default: Option<crate::OperatingHours>,
+    services: std::collections::HashMap<u8, crate::OperatingHours>,
+    maintenance: Vec<Maintenance>,
+}
+
+impl Schedule {
+    pub fn new(calendar: crate::Calendar) -> Self {
+        Self {
+            calendar,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_default(mut self, hours: crate::OperatingHours) -> Self {
+        self.default = Some(hours);
+
+        self
+    }
+
+    pub fn with_service(mut self, service_code: u8, hours: crate::OperatingHours) -> Self {
+        self.services.insert(service_code, hours);
+
+        self
+    }
+
+    pub fn with_maintenance(mut self, maintenance: Maintenance) -> Self {
+        self.maintenance.push(maintenance);
+
+        self
+    }
+
+    pub fn calendar(&self) -> &crate::Calendar {
+        &self.calendar
+    }
+
+    pub fn get(&self, service_code: u8) -> Option<&crate::OperatingHours> {
+        self.services.get(&service_code).or(self.default.as_ref())
+    }
+
+    pub fn check_now(&self, service_code: u8) -> crate::Result<()> {
+        self.check(service_code, chrono::Utc::now())
+    }
+
+    /// `NotAllowedAtThisTime` with the reason when `service_code` is closed
+    /// at `at`. Past midnight, a window spanning it keeps the weekend and
+    /// holiday status of the day it opened on.
+    pub fn check(&self, service_code: u8, at: chrono::DateTime<chrono::Utc>) -> crate::Result<()> {
+        let closed = |reason: String| Err(crate::ScheduleError::NotAllowedAtThisTime(reason));
+
+        if let Some(maintenance) = self.maintenance.iter().find(|x| x.applies(service_code, at)) {
+            return closed(format!(
+                "{} until {}",
+                maintenance.reason,
+                maintenance
+                    .end
+                    .with_timezone(&kamu_snap_response::header::wib())
+                    .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+            ));
+        }
+
+        let Some(hours) = self.get(service_code) else {
+            return Ok(());
+        };
+        let local = at.with_timezone(&kamu_snap_response::header::wib());
+        let date = hours.opened_on(local.naive_local());
+
+        if !hours.on_holidays
+            && let Some(holiday) = self.calendar.holiday(date)
+        {
+            return closed(
+                format!("Closed on holiday {date} {holiday}")
+                    .trim_end()
+                    .to_owned(),
+            );
+        }
+
+        if !hours.on_weekends && crate::Calendar::is_weekend(date) {
+            return closed("Closed on weekends".to_owned());
+        }
+
+        if !hours.contains(local.time()) {
+            return closed(format!("Operating hours are {hours}"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Operating hours, weekends and holidays, windows spanning midnight,
+//! maintenance and calendar parsing.
+
+/// RTGS transfer
+const SERVICE_CODE: u8 = 22;
+
+fn at(value: &str) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::parse_from_rfc3339(value).unwrap().into()
+}
+
+fn date(value: &str) -> chrono::NaiveDate {
+    value.parse().unwrap()
+}
+
+fn reason(result: kamu_snap_schedule::Result<()>) -> String {
+    match result {
+        Err(kamu_snap_schedule::ScheduleError::NotAllowedAtThisTime(reason)) => reason,
+        result => panic!("unexpected {result:?}"),
+    }
+}
+
+#[test]
+fn operating_hours() {
+    let schedule = kamu_snap_schedule::Schedule::default().with_service(
+        SERVICE_CODE,
+        kamu_snap_schedule::OperatingHours::between((8, 0), (15, 0)),
+    );
+
+    // Friday, in WIB
+    schedule
+        .check(SERVICE_CODE, at("2026-10-16T08:00:00+07:00"))
+        .unwrap();
+    schedule.check(SERVICE_CODE, at("2026-10-16T07:59:59Z")).unwrap();
+    assert_eq!(
+        reason(schedule.check(SERVICE_CODE, at("2026-10-16T15:00:00+07:00"))),
+        "Operating hours are 08:00-15:00 WIB"
+    );
+    assert_eq!(
+        reason(schedule.check(SERVICE_CODE, at("2026-10-17T10:00:00+07:00"))),
+        "Closed on weekends"
+    );
+
+    // No default hours, always open
+    schedule
+        .check(SERVICE_CODE + 1, at("2026-10-17T03:00:00+07:00"))
+        .unwrap();
+}
+
+#[test]
+fn holidays() {
+    let calendar = kamu_snap_schedule::Calendar::new().with_holiday(date("2026-08-17"), "Hari Kemerdekaan");
+    let schedule = kamu_snap_schedule::Schedule::new(calendar)
+        .with_default(kamu_snap_schedule::OperatingHours::between((8, 0), (15, 0)))
+        .with_service(
+            SERVICE_CODE,
+            kamu_snap_schedule::OperatingHours::between((8, 0), (15, 0)).with_holidays(),
+        );
+
+    assert_eq!(
+        reason(schedule.check(1, at("2026-08-17T10:00:00+07:00"))),
+        "Closed on holiday 2026-08-17 Hari Kemerdekaan"
+    );
+    schedule
+        .check(SERVICE_CODE, at("2026-08-17T10:00:00+07:00"))
+        .unwrap();
+    // 16 August 20:00 UTC is already the 17th in WIB
+    assert!(schedule.check(1, at("2026-08-17T01:30:00Z")).is_err());
+    schedule.check(1, at("2026-08-18T10:00:00+07:00")).unwrap();
+}
+
+#[test]
+fn window_spanning_midnight() {
+    let calendar = kamu_snap_schedule::Calendar::new().with_holiday(date("2026-12-25"), "Hari Natal");
+    let schedule = kamu_snap_schedule::Schedule::new(calendar)
+        .with_default(kamu_snap_schedule::OperatingHours::between((22, 0), (2, 0)));
+
+    // Thursday night's window runs into the Christmas holiday
+    schedule.check(1, at("2026-12-25T01:00:00+07:00")).unwrap();
+    // Friday's window is a holiday one, even past midnight on Saturday
+    assert!(schedule.check(1, at("2026-12-25T23:00:00+07:00")).is_err());
+    assert!(schedule.check(1, at("2026-12-26T01:00:00+07:00")).is_err());
+
+    // Friday 16 October's window runs into Saturday
+    schedule.check(1, at("2026-10-16T22:00:00+07:00")).unwrap();
+    schedule.check(1, at("2026-10-17T01:00:00+07:00")).unwrap();
+    assert_eq!(
+        reason(schedule.check(1, at("2026-10-16T02:00:00+07:00"))),
+        "Operating hours are 22:00-02:00 WIB"
+    );
+    assert_eq!(
+        reason(schedule.check(1, at("2026-10-17T23:00:00+07:00"))),
+        "Closed on weekends"
+    );
+    // Sunday's window is closed, Monday 01:00 belongs to it
+    assert!(schedule.check(1, at("2026-10-19T01:00:00+07:00")).is_err());
+    schedule.check(1, at("2026-10-19T22:30:00+07:00")).unwrap();
+}
+
+#[test]
+fn maintenance() {
+    let start = chrono::DateTime::parse_from_rfc3339("2026-10-16T23:00:00+07:00").unwrap();
+    let end = chrono::DateTime::parse_from_rfc3339("2026-10-17T01:00:00+07:00").unwrap();
+    let schedule = kamu_snap_schedule::Schedule::default()
+        .with_maintenance(kamu_snap_schedule::Maintenance::new(
+            start,
+            end,
+            "Core banking upgrade",
+        ))
+        .with_maintenance(
+            kamu_snap_schedule::Maintenance::new(end, end + chrono::Duration::hours(1), "RTGS maintenance")
+                .for_services(vec![SERVICE_CODE]),
+        );
+
+    assert_eq!(
+        reason(schedule.check(1, at("2026-10-16T23:00:00+07:00"))),
+        "Core banking upgrade until 2026-10-17T01:00:00+07:00"
+    );
+    schedule.check(1, at("2026-10-17T01:00:00+07:00")).unwrap();
+    assert_eq!(
+        reason(schedule.check(SERVICE_CODE, at("2026-10-17T01:30:00+07:00"))),
+        "RTGS maintenance until 2026-10-17T02:00:00+07:00"
+    );
+    schedule
+        .check(SERVICE_CODE, at("2026-10-17T02:00:00+07:00"))
+        .unwrap();
+}
+
+#[test]
+fn calendar_parse() {
+    let calendar = kamu_snap_schedule::Calendar::parse(
+        "# Libur nasional 2026\n\n2026-08-17 Hari Kemerdekaan\n  2026-12-25   Hari Natal  \n2026-12-24\n",
+    )
+    .unwrap();
+
+    assert_eq!(calendar.holiday(date("2026-08-17")), Some("Hari Kemerdekaan"));
+    assert_eq!(calendar.holiday(date("2026-12-25")), Some("Hari Natal"));
+    assert_eq!(calendar.holiday(date("2026-12-24")), Some(""));
+    assert_eq!(calendar.holiday(date("2026-08-18")), None);
+    assert!(!calendar.is_business_day(date("2026-08-17")));
+    assert!(!calendar.is_business_day(date("2026-10-17")));
+    assert!(calendar.is_business_day(date("2026-10-16")));
+
+    match kamu_snap_schedule::Calendar::parse("2026-08-17 Hari Kemerdekaan\n17-08-2026 Hari Kemerdekaan") {
+        Err(kamu_snap_schedule::ScheduleError::InvalidCalendar(reason)) => {
+            assert!(reason.starts_with("line 2:"), "{reason}");
+        }
+        result => panic!("unexpected {result:?}"),
+    }
+}
@@ -0,0 +1,13 @@
+//! Fixtures shared by the tests of the other crates, never published.
+
+pub mod stub;
+
+pub use stub::{Request, Stub};
+
+/// PKCS#8 RSA private key, 1024 bits to keep signing in debug builds fast.
+/// For tests only.
+pub const PRIVATE_KEY: &str = include_str!("../fixtures/private_key.pem");
+/// SubjectPublicKeyInfo of [`PRIVATE_KEY`].
+pub const PUBLIC_KEY: &str = include_str!("../fixtures/public_key.pem");
+/// PKCS#8 RSA private key unrelated to [`PRIVATE_KEY`].
+pub const OTHER_PRIVATE_KEY: &str = include_str!("../fixtures/other_private_key.pem");
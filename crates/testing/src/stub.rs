@@ -0,0 +1,76 @@
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub path: String,
+    /// Names in upper case
+    pub headers: std::collections::HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// HTTP server answering one connection per canned response, in order, and
+/// recording the requests it received.
+pub struct Stub {
+    pub url: String,
+    requests: std::sync::Arc<std::sync::Mutex<Vec<Request>>>,
+}
+
+impl Stub {
+    /// Listen on a local port, `responses` are status and JSON body pairs.
+    pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream);
+                let mut request_line = String::new();
+                std::io::BufRead::read_line(&mut reader, &mut request_line).unwrap();
+                let mut headers = std::collections::HashMap::new();
+
+                loop {
+                    let mut line = String::new();
+                    std::io::BufRead::read_line(&mut reader, &mut line).unwrap();
+
+                    if line.trim().is_empty() {
+                        break;
+                    }
+
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.trim().to_ascii_uppercase(), value.trim().to_owned());
+                    }
+                }
+
+                let length = headers
+                    .get("CONTENT-LENGTH")
+                    .map_or(0, |x| x.parse::<usize>().unwrap());
+                let mut request_body = vec![0; length];
+                std::io::Read::read_exact(&mut reader, &mut request_body).unwrap();
+                recorded
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push(Request {
+                        path: request_line.split(' ').nth(1).unwrap_or_default().to_owned(),
+                        headers,
+                        body: request_body,
+                    });
+
+                let response = format!(
+                    "HTTP/1.1 {status} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                std::io::Write::write_all(reader.get_mut(), response.as_bytes()).unwrap();
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> std::sync::MutexGuard<'_, Vec<Request>> {
+        self.requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
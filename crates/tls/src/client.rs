@@ -0,0 +1,41 @@
+//! Client side mutual TLS, presenting our certificate to a bank:
+//!
+//! ```ignore
+//! let config = client_config(
+//!     load_certificates("bank-ca.pem")?,
+//!     load_certificates("client.pem")?,
+//!     load_private_key("client.key")?,
+//! )?;
+//! let http = http_client_builder(config).timeout(Duration::from_secs(30)).build()?;
+//! let client = SNAPClient::with_http_client(http, snap_config, signer, symmetric);
+//! ```
+
+/// Client configuration trusting `ca_certificates` for the server and
+/// presenting `certificate_chain`.
+pub fn client_config(
+    ca_certificates: Vec<rustls::pki_types::CertificateDer<'static>>,
+    certificate_chain: Vec<rustls::pki_types::CertificateDer<'static>>,
+    private_key: rustls::pki_types::PrivateKeyDer<'static>,
+) -> crate::Result<rustls::ClientConfig> {
+    let mut roots = rustls::RootCertStore::empty();
+
+    for certificate in ca_certificates {
+        roots
+            .add(certificate)
+            .map_err(|x| crate::TlsError::InvalidCertificate(x.to_string()))?;
+    }
+
+    let config = rustls::ClientConfig::builder_with_provider(std::sync::Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_root_certificates(roots)
+    .with_client_auth_cert(certificate_chain, private_key)?;
+
+    Ok(config)
+}
+
+/// `reqwest` client builder using `config` for every connection.
+pub fn http_client_builder(config: rustls::ClientConfig) -> reqwest::ClientBuilder {
+    reqwest::Client::builder().use_preconfigured_tls(config)
+}
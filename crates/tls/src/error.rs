@@ -0,0 +1,36 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Invalid TLS configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Client certificate missing or issued for no known partner")]
+    UnknownClientCertificate,
+    #[error("Client certificate issued for partner {certificate}, not {header}")]
+    PartnerMismatch { certificate: String, header: String },
+}
+
+impl From<rustls::Error> for Error {
+    fn from(value: rustls::Error) -> Self {
+        Error::InvalidConfig(value.to_string())
+    }
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::InvalidCertificate(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::InvalidPrivateKey(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::InvalidConfig(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::UnknownClientCertificate => {
+                kamu_snap_response::ResponseError::Unathorized("Client Certificate".to_owned())
+            }
+            Error::PartnerMismatch { .. } => {
+                kamu_snap_response::ResponseError::Unathorized("Client Certificate Mismatch".to_owned())
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod client;
+pub mod error;
+pub mod middleware;
+pub mod pem;
+pub mod server;
+
+pub use client::{client_config, http_client_builder};
+pub use error::Error as TlsError;
+pub use middleware::CertificatePartner;
+pub use pem::{load_certificates, load_private_key};
+pub use server::{ClientPartner, ClientVerifier};
+
+pub type Result<T> = core::result::Result<T, TlsError>;
@@ -0,0 +1,113 @@
+//! Actix middleware rejecting requests whose connection presented no partner
+//! certificate, or whose `X-PARTNER-ID` or `X-CLIENT-KEY` is not the partner
+//! of the certificate, with `Unathorized`:
+//!
+//! ```ignore
+//! web::scope("/v1.0")
+//!     .wrap(CertificatePartner::new(0))
+//!     .configure(routes)
+//! ```
+//!
+//! Requires the [`ClientVerifier::on_connect`](crate::ClientVerifier::on_connect)
+//! hook on the server.
+
+type LocalBoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T>>>;
+
+pub struct CertificatePartner {
+    service_code: u8,
+}
+
+impl CertificatePartner {
+    pub fn new(service_code: u8) -> Self {
+        Self { service_code }
+    }
+}
+
+impl<N, B> actix_web::dev::Transform<N, actix_web::dev::ServiceRequest> for CertificatePartner
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = Middleware<N>;
+    type InitError = ();
+    type Future = std::future::Ready<core::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: N) -> Self::Future {
+        std::future::ready(Ok(Middleware {
+            service_code: self.service_code,
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub struct Middleware<N> {
+    service_code: u8,
+    service: std::rc::Rc<N>,
+}
+
+impl<N, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for Middleware<N>
+where
+    N: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<core::result::Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: actix_web::dev::ServiceRequest) -> Self::Future {
+        if let Err(error) = check(request.request()) {
+            let response = actix_web::Responder::respond_to(
+                kamu_snap_response::SNAPResponse::<serde_json::Value>::from_error(
+                    error.into(),
+                    self.service_code,
+                ),
+                request.request(),
+            );
+
+            return Box::pin(std::future::ready(Ok(request.into_response(response))));
+        }
+
+        let service = self.service.clone();
+
+        Box::pin(async move { service.call(request).await.map(|x| x.map_into_boxed_body()) })
+    }
+}
+
+/// `X-PARTNER-ID` of service requests and `X-CLIENT-KEY` of access token
+/// requests both name the partner, each is checked when present.
+fn check(request: &actix_web::HttpRequest) -> crate::Result<()> {
+    let certificate = crate::ClientPartner::get(request).ok_or(crate::TlsError::UnknownClientCertificate)?;
+
+    for name in [
+        kamu_snap_response::header::X_PARTNER_ID,
+        kamu_snap_response::header::X_CLIENT_KEY,
+    ] {
+        let header = request
+            .headers()
+            .get(name)
+            .and_then(|x| x.to_str().ok())
+            .map(str::trim)
+            .filter(|x| !x.is_empty());
+
+        if let Some(header) = header.filter(|x| *x != certificate.partner_id()) {
+            return Err(crate::TlsError::PartnerMismatch {
+                certificate: certificate.partner_id().to_owned(),
+                header: header.to_owned(),
+            });
+        }
+    }
+
+    Ok(())
+}
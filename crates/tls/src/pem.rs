@@ -0,0 +1,51 @@
+//! Certificates and private keys in the form rustls expects, read from PEM
+//! with the `rustls::pki_types` decoder. Blocks are picked by their label,
+//! DER content is taken through the `rustls::pki_types` constructors of its
+//! format instead.
+
+/// Every `CERTIFICATE` block of a PEM bundle.
+pub fn certificates(content: &[u8]) -> crate::Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    let certificates =
+        <rustls::pki_types::CertificateDer as rustls::pki_types::pem::PemObject>::pem_slice_iter(content)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|x| crate::TlsError::InvalidCertificate(x.to_string()))?;
+
+    if certificates.is_empty() {
+        return Err(crate::TlsError::InvalidCertificate(
+            "no certificate found".to_owned(),
+        ));
+    }
+
+    Ok(certificates)
+}
+
+/// The first `PRIVATE KEY` (PKCS#8), `RSA PRIVATE KEY` (PKCS#1) or
+/// `EC PRIVATE KEY` (SEC1) block of a PEM file.
+pub fn private_key(content: &[u8]) -> crate::Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    <rustls::pki_types::PrivateKeyDer as rustls::pki_types::pem::PemObject>::from_pem_slice(content).map_err(
+        |x| match x {
+            rustls::pki_types::pem::Error::NoItemsFound => {
+                crate::TlsError::InvalidPrivateKey("no private key found".to_owned())
+            }
+            x => crate::TlsError::InvalidPrivateKey(x.to_string()),
+        },
+    )
+}
+
+pub fn load_certificates<P: AsRef<std::path::Path>>(
+    path: P,
+) -> crate::Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    certificates(&read(path)?)
+}
+
+pub fn load_private_key<P: AsRef<std::path::Path>>(
+    path: P,
+) -> crate::Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    private_key(&read(path)?)
+}
+
+fn read<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Vec<u8>> {
+    let path = path.as_ref();
+
+    std::fs::read(path).map_err(|x| crate::TlsError::InvalidConfig(format!("{}: {x}", path.display())))
+}
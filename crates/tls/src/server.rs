@@ -0,0 +1,205 @@
+//! Server side mutual TLS. Client certificates must chain up to the CA of a
+//! registered partner, the partner they map to is recorded per connection:
+//!
+//! ```ignore
+//! let verifier = Arc::new(
+//!     ClientVerifier::new()
+//!         .with_partner("BANK001", load_certificates("bank001-ca.pem")?)?
+//!         .with_partner("BANK002", load_certificates("bank002-ca.pem")?)?,
+//! );
+//! let config = verifier.server_config(load_certificates("server.pem")?, load_private_key("server.key")?)?;
+//!
+//! HttpServer::new(|| App::new().wrap(CertificatePartner::new(0)).configure(routes))
+//!     .on_connect(verifier.on_connect())
+//!     .bind_rustls_0_23(("0.0.0.0", 8443), config)?
+//!     .run()
+//!     .await
+//! ```
+
+fn provider() -> std::sync::Arc<rustls::crypto::CryptoProvider> {
+    std::sync::Arc::new(rustls::crypto::ring::default_provider())
+}
+
+/// The partner whose CA issued the client certificate of a connection, see
+/// [`ClientPartner::get`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientPartner(String);
+
+impl ClientPartner {
+    /// `None` when the connection did not present a partner certificate.
+    pub fn get(request: &actix_web::HttpRequest) -> Option<&Self> {
+        request.conn_data::<Self>()
+    }
+
+    pub fn partner_id(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Client certificate verifier accepting certificates issued by the CA of any
+/// registered partner.
+#[derive(Debug, Default)]
+pub struct ClientVerifier {
+    partners: Vec<(
+        String,
+        std::sync::Arc<dyn rustls::server::danger::ClientCertVerifier>,
+    )>,
+    subjects: Vec<rustls::DistinguishedName>,
+}
+
+impl ClientVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept client certificates chaining up to one of `ca_certificates` as
+    /// `partner_id`.
+    pub fn with_partner<P: Into<String>>(
+        mut self,
+        partner_id: P,
+        ca_certificates: Vec<rustls::pki_types::CertificateDer<'static>>,
+    ) -> crate::Result<Self> {
+        let mut roots = rustls::RootCertStore::empty();
+
+        for certificate in ca_certificates {
+            roots
+                .add(certificate)
+                .map_err(|x| crate::TlsError::InvalidCertificate(x.to_string()))?;
+        }
+
+        let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+            std::sync::Arc::new(roots),
+            provider(),
+        )
+        .build()
+        .map_err(|x| crate::TlsError::InvalidConfig(x.to_string()))?;
+        self.subjects.extend_from_slice(verifier.root_hint_subjects());
+        self.partners.push((partner_id.into(), verifier));
+
+        Ok(self)
+    }
+
+    /// The partner whose CA issued `end_entity`.
+    pub fn partner_id(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        intermediates: &[rustls::pki_types::CertificateDer<'_>],
+    ) -> Option<&str> {
+        let now = rustls::pki_types::UnixTime::now();
+
+        self.partners
+            .iter()
+            .find(|(_, verifier)| {
+                verifier
+                    .verify_client_cert(end_entity, intermediates, now)
+                    .is_ok()
+            })
+            .map(|(partner_id, _)| partner_id.as_str())
+    }
+
+    /// Server configuration requiring a partner client certificate, serving
+    /// `certificate_chain`.
+    pub fn server_config(
+        self: &std::sync::Arc<Self>,
+        certificate_chain: Vec<rustls::pki_types::CertificateDer<'static>>,
+        private_key: rustls::pki_types::PrivateKeyDer<'static>,
+    ) -> crate::Result<rustls::ServerConfig> {
+        if self.partners.is_empty() {
+            return Err(crate::TlsError::InvalidConfig(
+                "no partner CA registered".to_owned(),
+            ));
+        }
+
+        let config = rustls::ServerConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()?
+            .with_client_cert_verifier(self.clone())
+            .with_single_cert(certificate_chain, private_key)?;
+
+        Ok(config)
+    }
+
+    /// Connection hook for `HttpServer::on_connect` recording the
+    /// [`ClientPartner`] of each TLS connection.
+    pub fn on_connect(
+        self: &std::sync::Arc<Self>,
+    ) -> impl Fn(&dyn std::any::Any, &mut actix_web::dev::Extensions) + Send + Sync + 'static {
+        let verifier = self.clone();
+
+        move |connection, extensions| {
+            let Some(stream) = connection
+                .downcast_ref::<actix_tls::accept::rustls_0_23::TlsStream<actix_web::rt::net::TcpStream>>()
+            else {
+                return;
+            };
+            let partner_id = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|x| x.split_first())
+                .and_then(|(end_entity, intermediates)| verifier.partner_id(end_entity, intermediates));
+
+            if let Some(partner_id) = partner_id {
+                extensions.insert(ClientPartner(partner_id.to_owned()));
+            }
+        }
+    }
+
+    fn signature_verifier(
+        &self,
+    ) -> core::result::Result<&dyn rustls::server::danger::ClientCertVerifier, rustls::Error> {
+        self.partners
+            .first()
+            .map(|(_, verifier)| verifier.as_ref())
+            .ok_or(rustls::Error::NoCertificatesPresented)
+    }
+}
+
+impl rustls::server::danger::ClientCertVerifier for ClientVerifier {
+    fn root_hint_subjects(&self) -> &[rustls::DistinguishedName] {
+        &self.subjects
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        now: rustls::pki_types::UnixTime,
+    ) -> core::result::Result<rustls::server::danger::ClientCertVerified, rustls::Error> {
+        let mut error = rustls::Error::General("no partner CA registered".to_owned());
+
+        for (_, verifier) in &self.partners {
+            match verifier.verify_client_cert(end_entity, intermediates, now) {
+                Ok(verified) => return Ok(verified),
+                Err(x) => error = x,
+            }
+        }
+
+        Err(error)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> core::result::Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.signature_verifier()?
+            .verify_tls12_signature(message, certificate, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> core::result::Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.signature_verifier()?
+            .verify_tls13_signature(message, certificate, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.signature_verifier()
+            .map(|x| x.supported_verify_schemes())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,184 @@
+//! Client certificate to partner mapping, against CAs generated per test.
+
+/// Balance inquiry
+const SERVICE_CODE: u8 = 11;
+const PATH: &str = "/v1.0/balance-inquiry";
+
+struct Issuer {
+    key: rcgen::KeyPair,
+    certificate: rcgen::Certificate,
+}
+
+impl Issuer {
+    fn new(name: &str) -> Self {
+        let key = rcgen::KeyPair::generate().unwrap();
+        let mut params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, format!("{name} CA"));
+        let certificate = params.self_signed(&key).unwrap();
+
+        Self { key, certificate }
+    }
+
+    /// PEM bundle of the CA certificate.
+    fn ca(&self) -> Vec<rustls::pki_types::CertificateDer<'static>> {
+        kamu_snap_tls::pem::certificates(self.certificate.pem().as_bytes()).unwrap()
+    }
+
+    /// PEM certificate and private key issued for `names`.
+    fn issue(&self, names: &[&str]) -> (String, String) {
+        let key = rcgen::KeyPair::generate().unwrap();
+        let params =
+            rcgen::CertificateParams::new(names.iter().map(|x| (*x).to_owned()).collect::<Vec<_>>()).unwrap();
+        let certificate = params.signed_by(&key, &self.certificate, &self.key).unwrap();
+
+        (certificate.pem(), key.serialize_pem())
+    }
+}
+
+fn verifier(bank001: &Issuer, bank002: &Issuer) -> kamu_snap_tls::ClientVerifier {
+    kamu_snap_tls::ClientVerifier::new()
+        .with_partner("BANK001", bank001.ca())
+        .unwrap()
+        .with_partner("BANK002", bank002.ca())
+        .unwrap()
+}
+
+#[test]
+fn partner_id() {
+    let bank001 = Issuer::new("BANK001");
+    let bank002 = Issuer::new("BANK002");
+    let verifier = verifier(&bank001, &bank002);
+    let partner_id = |issuer: &Issuer| {
+        let (certificate, _) = issuer.issue(&["client"]);
+        let certificate = kamu_snap_tls::pem::certificates(certificate.as_bytes()).unwrap();
+
+        verifier.partner_id(&certificate[0], &[]).map(str::to_owned)
+    };
+
+    assert_eq!(partner_id(&bank001).as_deref(), Some("BANK001"));
+    assert_eq!(partner_id(&bank002).as_deref(), Some("BANK002"));
+    assert_eq!(partner_id(&Issuer::new("UNKNOWN")), None);
+}
+
+#[test]
+fn server_config_requires_partner() {
+    let server = Issuer::new("Server");
+    let (certificate, key) = server.issue(&["localhost"]);
+
+    assert!(matches!(
+        std::sync::Arc::new(kamu_snap_tls::ClientVerifier::new()).server_config(
+            kamu_snap_tls::pem::certificates(certificate.as_bytes()).unwrap(),
+            kamu_snap_tls::pem::private_key(key.as_bytes()).unwrap(),
+        ),
+        Err(kamu_snap_tls::TlsError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn pem_labels() {
+    let (certificate, key) = Issuer::new("BANK001").issue(&["client"]);
+    let der = kamu_snap_tls::pem::certificates(certificate.as_bytes()).unwrap()[0].to_vec();
+
+    assert!(kamu_snap_tls::pem::private_key(key.as_bytes()).is_ok());
+    // A private key is no certificate, nor the other way round
+    assert!(matches!(
+        kamu_snap_tls::pem::certificates(key.as_bytes()),
+        Err(kamu_snap_tls::TlsError::InvalidCertificate(_))
+    ));
+    assert!(matches!(
+        kamu_snap_tls::pem::private_key(certificate.as_bytes()),
+        Err(kamu_snap_tls::TlsError::InvalidPrivateKey(_))
+    ));
+    // Raw DER has no label to tell its format
+    assert!(kamu_snap_tls::pem::certificates(&der).is_err());
+    assert!(kamu_snap_tls::pem::private_key(&der).is_err());
+}
+
+async fn handle() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::Ok().json(serde_json::json!({"responseCode": "2001100"}))
+}
+
+/// Serves [`PATH`] over mutual TLS behind [`kamu_snap_tls::CertificatePartner`],
+/// returning its address.
+fn start_server(server: &Issuer, verifier: kamu_snap_tls::ClientVerifier) -> std::net::SocketAddr {
+    let (certificate, key) = server.issue(&["localhost"]);
+    let verifier = std::sync::Arc::new(verifier);
+    let config = verifier
+        .server_config(
+            kamu_snap_tls::pem::certificates(certificate.as_bytes()).unwrap(),
+            kamu_snap_tls::pem::private_key(key.as_bytes()).unwrap(),
+        )
+        .unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = actix_web::HttpServer::new(|| {
+        actix_web::App::new()
+            .wrap(kamu_snap_tls::CertificatePartner::new(SERVICE_CODE))
+            .service(actix_web::web::resource(PATH).route(actix_web::web::post().to(handle)))
+    })
+    .on_connect(verifier.on_connect())
+    .workers(1)
+    .listen_rustls_0_23(listener, config)
+    .unwrap()
+    .run();
+    actix_web::rt::spawn(server);
+
+    address
+}
+
+/// Client presenting a certificate issued by `client`, reaching `localhost`
+/// at `address`.
+fn http_client(server: &Issuer, client: &Issuer, address: std::net::SocketAddr) -> reqwest::Client {
+    let (certificate, key) = client.issue(&["client"]);
+    let config = kamu_snap_tls::client_config(
+        server.ca(),
+        kamu_snap_tls::pem::certificates(certificate.as_bytes()).unwrap(),
+        kamu_snap_tls::pem::private_key(key.as_bytes()).unwrap(),
+    )
+    .unwrap();
+
+    kamu_snap_tls::http_client_builder(config)
+        .resolve("localhost", address)
+        .build()
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn certificate_partner() {
+    let server = Issuer::new("Server");
+    let bank001 = Issuer::new("BANK001");
+    let bank002 = Issuer::new("BANK002");
+    let address = start_server(&server, verifier(&bank001, &bank002));
+    let url = format!("https://localhost:{}{PATH}", address.port());
+    let http = http_client(&server, &bank001, address);
+    let post = async |header: Option<(&str, &str)>| {
+        let mut request = http.post(&url);
+
+        if let Some((name, value)) = header {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.unwrap();
+        let status = response.status().as_u16();
+        let body: serde_json::Value = serde_json::from_slice(&response.bytes().await.unwrap()).unwrap();
+
+        (status, body["responseCode"].as_str().unwrap().to_owned())
+    };
+
+    let partner_id = |x| Some((kamu_snap_response::header::X_PARTNER_ID, x));
+    let client_key = |x| Some((kamu_snap_response::header::X_CLIENT_KEY, x));
+
+    assert_eq!(post(partner_id("BANK001")).await, (200, "2001100".to_owned()));
+    assert_eq!(post(client_key("BANK001")).await, (200, "2001100".to_owned()));
+    // The certificate identifies the partner without the header
+    assert_eq!(post(None).await, (200, "2001100".to_owned()));
+    assert_eq!(post(partner_id("BANK002")).await, (401, "4011100".to_owned()));
+    assert_eq!(post(client_key("BANK002")).await, (401, "4011100".to_owned()));
+
+    // Certificates of no registered partner fail the handshake
+    let unknown = http_client(&server, &Issuer::new("UNKNOWN"), address);
+    assert!(unknown.post(&url).send().await.is_err());
+}
@@ -0,0 +1,173 @@
+/// SNAP default lifetime of a B2B access token.
+pub const DEFAULT_TTL: std::time::Duration = std::time::Duration::from_secs(900);
+
+#[derive(Debug, Clone)]
+pub struct AccessToken {
+    pub(crate) token: String,
+    pub(crate) client_key: String,
+    pub(crate) issued_at: u64,
+    pub(crate) expires_at: u64,
+}
+
+impl AccessToken {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn client_key(&self) -> &str {
+        &self.client_key
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn issued_at(&self) -> u64 {
+        self.issued_at
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        crate::unix_now() >= self.expires_at
+    }
+
+    pub fn to_response(&self) -> kamu_snap_model::access_token::Response {
+        kamu_snap_model::access_token::Response::bearer(
+            &self.token,
+            std::time::Duration::from_secs(self.expires_at - self.issued_at),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Format {
+    /// Random string that only means something to the token store
+    Opaque,
+    /// HS256 signed JWT, checked before the token store is consulted
+    Jwt { secret: kamu_snap_crypto::Secret },
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub ttl: std::time::Duration,
+    pub format: Format,
+    /// How far `X-TIMESTAMP` may be from the current time
+    pub timestamp_skew: std::time::Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            format: Format::Opaque,
+            timestamp_skew: kamu_snap_response::header::DEFAULT_TIMESTAMP_SKEW,
+        }
+    }
+}
+
+/// Issues and validates the tokens of `/v1.0/access-token/b2b`.
+pub struct Manager<S: crate::TokenStore> {
+    store: S,
+    config: Config,
+}
+
+impl<S: crate::TokenStore> Manager<S> {
+    pub fn new(store: S, config: Config) -> Self {
+        Self { store, config }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Verify the signature of an access token request whose timestamp is
+    /// within the configured skew.
+    pub fn verify_request(
+        &self,
+        verifier: &kamu_snap_crypto::AsymmetricCryptoVerifier,
+        client_key: &str,
+        timestamp: &str,
+        signature: &str,
+    ) -> crate::Result<()> {
+        kamu_snap_response::header::check_timestamp(timestamp, self.config.timestamp_skew)
+            .map_err(crate::TokenError::Response)?;
+        verifier.verify_base64(
+            signature,
+            kamu_snap_crypto::string_to_sign::access_token(client_key, timestamp),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn issue(&self, client_key: &str) -> crate::Result<AccessToken> {
+        let issued_at = crate::unix_now();
+        let expires_at = issued_at + self.config.ttl.as_secs();
+        let token = match &self.config.format {
+            Format::Opaque => crate::random_token(32)?,
+            Format::Jwt { secret } => {
+                let claims = crate::jwt::Claims {
+                    sub: client_key.to_owned(),
+                    iat: issued_at,
+                    exp: expires_at,
+                    jti: crate::random_token(16)?,
+                };
+
+                crate::jwt::encode(secret.as_ref(), &claims)
+            }
+        };
+        let access_token = AccessToken {
+            token,
+            client_key: client_key.to_owned(),
+            issued_at,
+            expires_at,
+        };
+        self.store.insert_token(access_token.clone())?;
+
+        Ok(access_token)
+    }
+
+    /// Verify the signed access token request and issue a token for it.
+    pub fn authorize(
+        &self,
+        verifier: &kamu_snap_crypto::AsymmetricCryptoVerifier,
+        client_key: &str,
+        timestamp: &str,
+        signature: &str,
+    ) -> crate::Result<AccessToken> {
+        self.verify_request(verifier, client_key, timestamp, signature)?;
+
+        self.issue(client_key)
+    }
+
+    /// Validate the value of an `Authorization` header.
+    pub fn validate(&self, authorization: &str) -> crate::Result<AccessToken> {
+        let token = crate::parse_bearer(authorization).ok_or(crate::TokenError::InvalidTokenB2B)?;
+
+        if let Format::Jwt { secret } = &self.config.format {
+            let claims =
+                crate::jwt::decode(secret.as_ref(), token).ok_or(crate::TokenError::InvalidTokenB2B)?;
+
+            if crate::unix_now() >= claims.exp {
+                return Err(crate::TokenError::InvalidTokenB2B);
+            }
+        }
+
+        let access_token = self
+            .store
+            .get_token(token)?
+            .ok_or(crate::TokenError::TokenNotFoundB2B)?;
+
+        if access_token.is_expired() {
+            self.store.remove_token(token)?;
+
+            return Err(crate::TokenError::InvalidTokenB2B);
+        }
+
+        Ok(access_token)
+    }
+
+    pub fn revoke(&self, token: &str) -> crate::Result<()> {
+        self.store.remove_token(token)
+    }
+}
@@ -0,0 +1,248 @@
+#[derive(Debug, Clone)]
+pub struct AuthCode {
+    pub(crate) code: String,
+    pub(crate) client_key: String,
+    pub(crate) customer_id: String,
+    pub(crate) expires_at: u64,
+}
+
+impl AuthCode {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn client_key(&self) -> &str {
+        &self.client_key
+    }
+
+    pub fn customer_id(&self) -> &str {
+        &self.customer_id
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomerToken {
+    pub(crate) access_token: String,
+    pub(crate) refresh_token: String,
+    pub(crate) client_key: String,
+    pub(crate) customer_id: String,
+    pub(crate) issued_at: u64,
+    pub(crate) expires_at: u64,
+    pub(crate) refresh_expires_at: u64,
+}
+
+impl CustomerToken {
+    pub fn access_token(&self) -> &str {
+        &self.access_token
+    }
+
+    pub fn refresh_token(&self) -> &str {
+        &self.refresh_token
+    }
+
+    pub fn client_key(&self) -> &str {
+        &self.client_key
+    }
+
+    pub fn customer_id(&self) -> &str {
+        &self.customer_id
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn issued_at(&self) -> u64 {
+        self.issued_at
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn refresh_expires_at(&self) -> u64 {
+        self.refresh_expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        crate::unix_now() >= self.expires_at
+    }
+
+    pub fn to_response(&self) -> kamu_snap_model::registration::ApplyCustomerTokenResponse {
+        kamu_snap_model::registration::ApplyCustomerTokenResponse {
+            access_token: self.access_token.clone(),
+            token_type: "Bearer".to_owned(),
+            access_token_expiry_time: crate::to_iso8601(self.expires_at),
+            refresh_token: Some(self.refresh_token.clone()),
+            refresh_token_expiry_time: Some(crate::to_iso8601(self.refresh_expires_at)),
+            additional_info: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub auth_code_ttl: std::time::Duration,
+    pub access_token_ttl: std::time::Duration,
+    pub refresh_token_ttl: std::time::Duration,
+    /// How far `X-TIMESTAMP` may be from the current time
+    pub timestamp_skew: std::time::Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            auth_code_ttl: std::time::Duration::from_secs(600),
+            access_token_ttl: crate::b2b::DEFAULT_TTL,
+            refresh_token_ttl: std::time::Duration::from_secs(7 * 24 * 3600),
+            timestamp_skew: kamu_snap_response::header::DEFAULT_TIMESTAMP_SKEW,
+        }
+    }
+}
+
+/// B2B2C customer token lifecycle: auth code, access token, refresh and
+/// revocation on unbinding.
+pub struct Manager<S: crate::CustomerTokenStore> {
+    store: S,
+    config: Config,
+}
+
+impl<S: crate::CustomerTokenStore> Manager<S> {
+    pub fn new(store: S, config: Config) -> Self {
+        Self { store, config }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Issue a single use auth code once the customer has consented to link
+    /// their account to the partner identified by `client_key`.
+    pub fn issue_auth_code(&self, client_key: &str, customer_id: &str) -> crate::Result<AuthCode> {
+        let auth_code = AuthCode {
+            code: crate::random_token(24)?,
+            client_key: client_key.to_owned(),
+            customer_id: customer_id.to_owned(),
+            expires_at: crate::unix_now() + self.config.auth_code_ttl.as_secs(),
+        };
+        self.store.insert_auth_code(auth_code.clone())?;
+
+        Ok(auth_code)
+    }
+
+    /// Handle the `AUTHORIZATION_CODE` grant of `/v1.0/access-token/b2b2c`.
+    /// Only the partner the code was issued to can exchange it.
+    pub fn exchange_auth_code(&self, client_key: &str, code: &str) -> crate::Result<CustomerToken> {
+        let auth_code = self
+            .store
+            .take_auth_code(client_key, code)?
+            .ok_or(crate::TokenError::InvalidAuthCode)?;
+
+        if crate::unix_now() >= auth_code.expires_at {
+            return Err(crate::TokenError::InvalidAuthCode);
+        }
+
+        self.issue(client_key, &auth_code.customer_id)
+    }
+
+    /// Handle the `REFRESH_TOKEN` grant. The previous token pair is revoked.
+    /// Only the partner the token was issued to can refresh it.
+    pub fn refresh(&self, client_key: &str, refresh_token: &str) -> crate::Result<CustomerToken> {
+        let previous = self
+            .store
+            .take_refresh_token(client_key, refresh_token)?
+            .ok_or(crate::TokenError::CustomerTokenNotFound)?;
+
+        if crate::unix_now() >= previous.refresh_expires_at {
+            return Err(crate::TokenError::InvalidCustomerToken);
+        }
+
+        self.issue(client_key, &previous.customer_id)
+    }
+
+    /// Dispatch an apply customer token request on its grant type.
+    pub fn apply(
+        &self,
+        client_key: &str,
+        request: &kamu_snap_model::registration::ApplyCustomerTokenRequest,
+    ) -> crate::Result<CustomerToken> {
+        match (request.grant_type, &request.auth_code, &request.refresh_token) {
+            (kamu_snap_model::registration::CustomerTokenGrantType::AuthorizationCode, Some(code), _) => {
+                self.exchange_auth_code(client_key, code)
+            }
+            (kamu_snap_model::registration::CustomerTokenGrantType::AuthorizationCode, None, _) => {
+                Err(crate::TokenError::InvalidAuthCode)
+            }
+            (kamu_snap_model::registration::CustomerTokenGrantType::RefreshToken, _, Some(token)) => {
+                self.refresh(client_key, token)
+            }
+            (kamu_snap_model::registration::CustomerTokenGrantType::RefreshToken, _, None) => {
+                Err(crate::TokenError::CustomerTokenNotFound)
+            }
+        }
+    }
+
+    /// Validate the value of an `X-Authorization-Customer` header sent by the
+    /// partner identified by `client_key`.
+    pub fn validate(&self, client_key: &str, authorization: &str) -> crate::Result<CustomerToken> {
+        let token = crate::parse_bearer(authorization).ok_or(crate::TokenError::InvalidCustomerToken)?;
+        let customer_token = self
+            .store
+            .get_customer_token(token)?
+            .ok_or(crate::TokenError::CustomerTokenNotFound)?;
+
+        if customer_token.client_key != client_key || customer_token.is_expired() {
+            return Err(crate::TokenError::InvalidCustomerToken);
+        }
+
+        Ok(customer_token)
+    }
+
+    /// Revoke every token of the customer issued to the partner identified by
+    /// `client_key`, e.g. on account unbinding. Bindings of the customer with
+    /// other partners are left untouched.
+    pub fn revoke(&self, client_key: &str, customer_id: &str) -> crate::Result<()> {
+        self.store.remove_customer(client_key, customer_id)
+    }
+
+    /// Set the amount, in minor units, a single purchase with the customer's
+    /// token may not exceed. `None` removes the limit.
+    pub fn set_limit(&self, customer_id: &str, limit: Option<i64>) -> crate::Result<()> {
+        self.store.set_limit(customer_id, limit)
+    }
+
+    /// Check a purchase amount, in minor units, against the customer's token
+    /// limit.
+    pub fn check_limit(&self, customer_token: &CustomerToken, amount: i64) -> crate::Result<()> {
+        let limit = self
+            .store
+            .get_limit(&customer_token.customer_id)?
+            .ok_or(crate::TokenError::NeedToSetTokenLimit)?;
+
+        if amount > limit {
+            return Err(crate::TokenError::TokenLimitExceeded);
+        }
+
+        Ok(())
+    }
+
+    fn issue(&self, client_key: &str, customer_id: &str) -> crate::Result<CustomerToken> {
+        let issued_at = crate::unix_now();
+        let customer_token = CustomerToken {
+            access_token: crate::random_token(32)?,
+            refresh_token: crate::random_token(32)?,
+            client_key: client_key.to_owned(),
+            customer_id: customer_id.to_owned(),
+            issued_at,
+            expires_at: issued_at + self.config.access_token_ttl.as_secs(),
+            refresh_expires_at: issued_at + self.config.refresh_token_ttl.as_secs(),
+        };
+        self.store.insert_customer_token(customer_token.clone())?;
+
+        Ok(customer_token)
+    }
+}
@@ -0,0 +1,46 @@
+#[derive(Debug, Clone)]
+#[derive(thiserror::Error)]
+pub enum Error {
+    #[error("Invalid token (B2B)")]
+    InvalidTokenB2B,
+    #[error("Token not found (B2B)")]
+    TokenNotFoundB2B,
+    #[error("Invalid customer token")]
+    InvalidCustomerToken,
+    #[error("Customer token not found")]
+    CustomerTokenNotFound,
+    #[error("Invalid auth code")]
+    InvalidAuthCode,
+    #[error("Need to set token limit")]
+    NeedToSetTokenLimit,
+    #[error("Token limit exceeded")]
+    TokenLimitExceeded,
+    #[error("Token store failure: {0}")]
+    Store(String),
+    #[error("Random number generator failure: {0}")]
+    Random(String),
+    #[error(transparent)]
+    Crypto(#[from] kamu_snap_crypto::CryptoError),
+    #[error("{0}")]
+    Response(kamu_snap_response::ResponseError),
+}
+
+impl From<Error> for kamu_snap_response::ResponseError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::InvalidTokenB2B => kamu_snap_response::ResponseError::InvalidTokenB2B,
+            Error::TokenNotFoundB2B => kamu_snap_response::ResponseError::TokenNotFoundB2B,
+            Error::InvalidCustomerToken => kamu_snap_response::ResponseError::InvalidCustomerToken,
+            Error::CustomerTokenNotFound => kamu_snap_response::ResponseError::CustomerTokenNotFound,
+            Error::InvalidAuthCode => {
+                kamu_snap_response::ResponseError::Unathorized("Invalid Auth Code".to_owned())
+            }
+            Error::NeedToSetTokenLimit => kamu_snap_response::ResponseError::NeedToSetTokenLimit,
+            Error::TokenLimitExceeded => kamu_snap_response::ResponseError::TokenLimitExceeded,
+            Error::Store(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Random(_) => kamu_snap_response::ResponseError::InternalServerError,
+            Error::Crypto(x) => x.into(),
+            Error::Response(x) => x,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Mountable `/v1.0/access-token/b2b` and `/v1.0/access-token/b2b2c`
+//! endpoints. The application registers the token managers and its
+//! [`PartnerKeys`] as `web::Data`, then calls [`configure`]:
+//!
+//! ```ignore
+//! App::new()
+//!     .app_data(web::Data::new(b2b_manager))
+//!     .app_data(web::Data::new(customer_manager))
+//!     .app_data(web::Data::new(partner_keys))
+//!     .configure(kamu_snap_token::handler::configure::<Store, Store, Keys>)
+//! ```
+
+pub const PATH_B2B: &str = "/v1.0/access-token/b2b";
+pub const PATH_B2B2C: &str = "/v1.0/access-token/b2b2c";
+
+/// Resolves the public key a partner signs its access token requests with,
+/// from the `X-CLIENT-KEY` header.
+pub trait PartnerKeys: Send + Sync {
+    fn get_verifier(&self, client_key: &str) -> Option<kamu_snap_crypto::AsymmetricCryptoVerifier>;
+}
+
+impl PartnerKeys for std::collections::HashMap<String, kamu_snap_crypto::AsymmetricCryptoVerifier> {
+    fn get_verifier(&self, client_key: &str) -> Option<kamu_snap_crypto::AsymmetricCryptoVerifier> {
+        self.get(client_key).cloned()
+    }
+}
+
+/// Signed headers shared by both access token requests.
+struct SignedHeaders<'a> {
+    client_key: &'a str,
+    timestamp: &'a str,
+    signature: &'a str,
+}
+
+impl<'a> SignedHeaders<'a> {
+    /// Mandatory headers, with `X-TIMESTAMP` no further than `skew` from the
+    /// current time.
+    fn extract(
+        request: &'a actix_web::HttpRequest,
+        skew: std::time::Duration,
+    ) -> kamu_snap_response::Result<Self> {
+        let headers = request.headers();
+        let timestamp =
+            kamu_snap_response::header::get_mandatory(headers, kamu_snap_response::header::X_TIMESTAMP)?;
+        let client_key =
+            kamu_snap_response::header::get_mandatory(headers, kamu_snap_response::header::X_CLIENT_KEY)?;
+        let signature =
+            kamu_snap_response::header::get_mandatory(headers, kamu_snap_response::header::X_SIGNATURE)?;
+        kamu_snap_response::header::check_timestamp(timestamp, skew)?;
+
+        Ok(Self {
+            client_key,
+            timestamp,
+            signature,
+        })
+    }
+
+    fn get_verifier<K: PartnerKeys + ?Sized>(
+        &self,
+        keys: &K,
+    ) -> kamu_snap_response::Result<kamu_snap_crypto::AsymmetricCryptoVerifier> {
+        keys.get_verifier(self.client_key)
+            .ok_or_else(|| kamu_snap_response::ResponseError::Unathorized("Unknown Client".to_owned()))
+    }
+}
+
+/// Parse a JSON body, reporting a missing `mandatory` field or a field that
+/// fails to deserialize the way SNAP expects.
+fn parse_body<T: serde::de::DeserializeOwned>(body: &[u8], mandatory: &str) -> kamu_snap_response::Result<T> {
+    let value = serde_json::from_slice::<serde_json::Value>(body)
+        .map_err(|_| kamu_snap_response::ResponseError::BadRequest)?;
+
+    if value.get(mandatory).is_none_or(serde_json::Value::is_null) {
+        return Err(kamu_snap_response::ResponseError::InvalidMandatoryField(
+            mandatory.to_owned(),
+        ));
+    }
+
+    serde_json::from_value(value)
+        .map_err(|_| kamu_snap_response::ResponseError::InvalidFieldFormat(mandatory.to_owned()))
+}
+
+fn handle_b2b<S, K>(
+    request: &actix_web::HttpRequest,
+    body: &[u8],
+    manager: &crate::B2BTokenManager<S>,
+    keys: &K,
+) -> kamu_snap_response::Result<kamu_snap_model::access_token::Response>
+where
+    S: crate::TokenStore,
+    K: PartnerKeys + ?Sized,
+{
+    let headers = SignedHeaders::extract(request, manager.config().timestamp_skew)?;
+    let verifier = headers.get_verifier(keys)?;
+    parse_body::<kamu_snap_model::access_token::Request>(body, "grantType")?;
+    let access_token = manager.authorize(
+        &verifier,
+        headers.client_key,
+        headers.timestamp,
+        headers.signature,
+    )?;
+
+    Ok(access_token.to_response())
+}
+
+fn handle_b2b2c<S, K>(
+    request: &actix_web::HttpRequest,
+    body: &[u8],
+    manager: &crate::CustomerTokenManager<S>,
+    keys: &K,
+) -> kamu_snap_response::Result<kamu_snap_model::registration::ApplyCustomerTokenResponse>
+where
+    S: crate::CustomerTokenStore,
+    K: PartnerKeys + ?Sized,
+{
+    let headers = SignedHeaders::extract(request, manager.config().timestamp_skew)?;
+    let verifier = headers.get_verifier(keys)?;
+    let apply_request =
+        parse_body::<kamu_snap_model::registration::ApplyCustomerTokenRequest>(body, "grantType")?;
+    apply_request.validate()?;
+    verifier.verify_base64(
+        headers.signature,
+        kamu_snap_crypto::string_to_sign::access_token(headers.client_key, headers.timestamp),
+    )?;
+    let customer_token = manager.apply(headers.client_key, &apply_request)?;
+
+    Ok(customer_token.to_response())
+}
+
+pub async fn b2b<S, K>(
+    request: actix_web::HttpRequest,
+    body: actix_web::web::Bytes,
+    manager: actix_web::web::Data<crate::B2BTokenManager<S>>,
+    keys: actix_web::web::Data<K>,
+) -> kamu_snap_response::SNAPResponse<kamu_snap_model::access_token::Response>
+where
+    S: crate::TokenStore + 'static,
+    K: PartnerKeys + 'static,
+{
+    match handle_b2b(&request, &body, &manager, keys.as_ref()) {
+        Ok(response) => response.into_snap_response(),
+        Err(error) => kamu_snap_response::SNAPResponse::from_error(
+            error,
+            kamu_snap_model::access_token::SERVICE_CODE_B2B,
+        ),
+    }
+}
+
+pub async fn b2b2c<S, K>(
+    request: actix_web::HttpRequest,
+    body: actix_web::web::Bytes,
+    manager: actix_web::web::Data<crate::CustomerTokenManager<S>>,
+    keys: actix_web::web::Data<K>,
+) -> kamu_snap_response::SNAPResponse<kamu_snap_model::registration::ApplyCustomerTokenResponse>
+where
+    S: crate::CustomerTokenStore + 'static,
+    K: PartnerKeys + 'static,
+{
+    match handle_b2b2c(&request, &body, &manager, keys.as_ref()) {
+        Ok(response) => response.into_snap_response(),
+        Err(error) => kamu_snap_response::SNAPResponse::from_error(
+            error,
+            kamu_snap_model::registration::SERVICE_CODE_APPLY_CUSTOMER_TOKEN,
+        ),
+    }
+}
+
+/// Mount only the B2B access token endpoint.
+pub fn configure_b2b<S, K>(config: &mut actix_web::web::ServiceConfig)
+where
+    S: crate::TokenStore + 'static,
+    K: PartnerKeys + 'static,
+{
+    config.route(PATH_B2B, actix_web::web::post().to(b2b::<S, K>));
+}
+
+/// Mount only the B2B2C access token endpoint.
+pub fn configure_b2b2c<S, K>(config: &mut actix_web::web::ServiceConfig)
+where
+    S: crate::CustomerTokenStore + 'static,
+    K: PartnerKeys + 'static,
+{
+    config.route(PATH_B2B2C, actix_web::web::post().to(b2b2c::<S, K>));
+}
+
+/// Mount both access token endpoints.
+pub fn configure<S, C, K>(config: &mut actix_web::web::ServiceConfig)
+where
+    S: crate::TokenStore + 'static,
+    C: crate::CustomerTokenStore + 'static,
+    K: PartnerKeys + 'static,
+{
+    configure_b2b::<S, K>(config);
+    configure_b2b2c::<C, K>(config);
+}
@@ -0,0 +1,49 @@
+pub mod b2b;
+pub mod customer;
+pub mod error;
+pub mod handler;
+pub mod jwt;
+pub mod store;
+
+pub use b2b::{AccessToken as B2BAccessToken, Config as B2BTokenConfig, Manager as B2BTokenManager};
+pub use customer::{AuthCode, Config as CustomerTokenConfig, CustomerToken, Manager as CustomerTokenManager};
+pub use error::Error as TokenError;
+pub use handler::PartnerKeys;
+#[cfg(feature = "sqlite")]
+pub use store::SqliteStore as SqliteTokenStore;
+pub use store::{CustomerStore as CustomerTokenStore, MemoryStore as MemoryTokenStore, Store as TokenStore};
+
+pub type Result<T> = core::result::Result<T, TokenError>;
+
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
+
+/// ISO 8601 representation in WIB (UTC+7), as used throughout SNAP.
+pub(crate) fn to_iso8601(unix: u64) -> String {
+    chrono::DateTime::from_timestamp(unix as i64, 0)
+        .unwrap_or_default()
+        .with_timezone(&kamu_snap_response::header::wib())
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+pub(crate) fn random_token(length: usize) -> Result<String> {
+    let mut bytes = vec![0_u8; length];
+    getrandom::getrandom(&mut bytes).map_err(|x| TokenError::Random(x.to_string()))?;
+
+    Ok(base64::Engine::encode(
+        &base64::prelude::BASE64_URL_SAFE_NO_PAD,
+        bytes,
+    ))
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header value.
+pub fn parse_bearer(authorization: &str) -> Option<&str> {
+    let (scheme, token) = authorization.trim().split_once(' ')?;
+    let token = token.trim();
+
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
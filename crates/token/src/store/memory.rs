@@ -0,0 +1,120 @@
+#[derive(Debug, Default)]
+struct State {
+    tokens: std::collections::HashMap<String, crate::B2BAccessToken>,
+    auth_codes: std::collections::HashMap<String, crate::AuthCode>,
+    customer_tokens: std::collections::HashMap<String, crate::CustomerToken>,
+    limits: std::collections::HashMap<String, i64>,
+}
+
+#[derive(Debug, Default)]
+pub struct Store {
+    state: std::sync::Mutex<State>,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl crate::TokenStore for Store {
+    fn insert_token(&self, token: crate::B2BAccessToken) -> crate::Result<()> {
+        let now = crate::unix_now();
+        let mut state = self.lock();
+        state.tokens.retain(|_, x| x.expires_at > now);
+        state.tokens.insert(token.token.clone(), token);
+
+        Ok(())
+    }
+
+    fn get_token(&self, token: &str) -> crate::Result<Option<crate::B2BAccessToken>> {
+        Ok(self.lock().tokens.get(token).cloned())
+    }
+
+    fn remove_token(&self, token: &str) -> crate::Result<()> {
+        self.lock().tokens.remove(token);
+
+        Ok(())
+    }
+}
+
+impl crate::CustomerTokenStore for Store {
+    fn insert_auth_code(&self, auth_code: crate::AuthCode) -> crate::Result<()> {
+        let now = crate::unix_now();
+        let mut state = self.lock();
+        state.auth_codes.retain(|_, x| x.expires_at > now);
+        state.auth_codes.insert(auth_code.code.clone(), auth_code);
+
+        Ok(())
+    }
+
+    fn take_auth_code(&self, client_key: &str, code: &str) -> crate::Result<Option<crate::AuthCode>> {
+        let mut state = self.lock();
+
+        match state.auth_codes.get(code) {
+            Some(auth_code) if auth_code.client_key == client_key => Ok(state.auth_codes.remove(code)),
+            _ => Ok(None),
+        }
+    }
+
+    fn insert_customer_token(&self, token: crate::CustomerToken) -> crate::Result<()> {
+        let now = crate::unix_now();
+        let mut state = self.lock();
+        state.customer_tokens.retain(|_, x| x.refresh_expires_at > now);
+        state.customer_tokens.insert(token.access_token.clone(), token);
+
+        Ok(())
+    }
+
+    fn get_customer_token(&self, access_token: &str) -> crate::Result<Option<crate::CustomerToken>> {
+        Ok(self.lock().customer_tokens.get(access_token).cloned())
+    }
+
+    fn take_refresh_token(
+        &self,
+        client_key: &str,
+        refresh_token: &str,
+    ) -> crate::Result<Option<crate::CustomerToken>> {
+        let mut state = self.lock();
+        let access_token = state
+            .customer_tokens
+            .values()
+            .find(|x| x.refresh_token == refresh_token && x.client_key == client_key)
+            .map(|x| x.access_token.clone());
+
+        Ok(access_token.and_then(|x| state.customer_tokens.remove(&x)))
+    }
+
+    fn remove_customer(&self, client_key: &str, customer_id: &str) -> crate::Result<()> {
+        let mut state = self.lock();
+        state
+            .auth_codes
+            .retain(|_, x| x.client_key != client_key || x.customer_id != customer_id);
+        state
+            .customer_tokens
+            .retain(|_, x| x.client_key != client_key || x.customer_id != customer_id);
+
+        Ok(())
+    }
+
+    fn set_limit(&self, customer_id: &str, limit: Option<i64>) -> crate::Result<()> {
+        let mut state = self.lock();
+
+        match limit {
+            Some(limit) => state.limits.insert(customer_id.to_owned(), limit),
+            None => state.limits.remove(customer_id),
+        };
+
+        Ok(())
+    }
+
+    fn get_limit(&self, customer_id: &str) -> crate::Result<Option<i64>> {
+        Ok(self.lock().limits.get(customer_id).copied())
+    }
+}
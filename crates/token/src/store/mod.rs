@@ -0,0 +1,97 @@
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::Store as MemoryStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::Store as SqliteStore;
+
+/// Persistence of issued B2B access tokens, keyed by the token string.
+pub trait Store: Send + Sync {
+    fn insert_token(&self, token: crate::B2BAccessToken) -> crate::Result<()>;
+
+    fn get_token(&self, token: &str) -> crate::Result<Option<crate::B2BAccessToken>>;
+
+    fn remove_token(&self, token: &str) -> crate::Result<()>;
+}
+
+/// Persistence of B2B2C auth codes, customer tokens and token limits.
+pub trait CustomerStore: Send + Sync {
+    fn insert_auth_code(&self, auth_code: crate::AuthCode) -> crate::Result<()>;
+
+    /// Remove and return the auth code, so that it can only be used once.
+    /// Codes issued to another client key are left untouched.
+    fn take_auth_code(&self, client_key: &str, code: &str) -> crate::Result<Option<crate::AuthCode>>;
+
+    fn insert_customer_token(&self, token: crate::CustomerToken) -> crate::Result<()>;
+
+    fn get_customer_token(&self, access_token: &str) -> crate::Result<Option<crate::CustomerToken>>;
+
+    /// Remove and return the token pair the refresh token belongs to. Tokens
+    /// issued to another client key are left untouched.
+    fn take_refresh_token(
+        &self,
+        client_key: &str,
+        refresh_token: &str,
+    ) -> crate::Result<Option<crate::CustomerToken>>;
+
+    /// Remove every auth code and token issued for the customer to the
+    /// partner identified by `client_key`.
+    fn remove_customer(&self, client_key: &str, customer_id: &str) -> crate::Result<()>;
+
+    fn set_limit(&self, customer_id: &str, limit: Option<i64>) -> crate::Result<()>;
+
+    fn get_limit(&self, customer_id: &str) -> crate::Result<Option<i64>>;
+}
+
+impl<S: Store + ?Sized> Store for std::sync::Arc<S> {
+    fn insert_token(&self, token: crate::B2BAccessToken) -> crate::Result<()> {
+        (**self).insert_token(token)
+    }
+
+    fn get_token(&self, token: &str) -> crate::Result<Option<crate::B2BAccessToken>> {
+        (**self).get_token(token)
+    }
+
+    fn remove_token(&self, token: &str) -> crate::Result<()> {
+        (**self).remove_token(token)
+    }
+}
+
+impl<S: CustomerStore + ?Sized> CustomerStore for std::sync::Arc<S> {
+    fn insert_auth_code(&self, auth_code: crate::AuthCode) -> crate::Result<()> {
+        (**self).insert_auth_code(auth_code)
+    }
+
+    fn take_auth_code(&self, client_key: &str, code: &str) -> crate::Result<Option<crate::AuthCode>> {
+        (**self).take_auth_code(client_key, code)
+    }
+
+    fn insert_customer_token(&self, token: crate::CustomerToken) -> crate::Result<()> {
+        (**self).insert_customer_token(token)
+    }
+
+    fn get_customer_token(&self, access_token: &str) -> crate::Result<Option<crate::CustomerToken>> {
+        (**self).get_customer_token(access_token)
+    }
+
+    fn take_refresh_token(
+        &self,
+        client_key: &str,
+        refresh_token: &str,
+    ) -> crate::Result<Option<crate::CustomerToken>> {
+        (**self).take_refresh_token(client_key, refresh_token)
+    }
+
+    fn remove_customer(&self, client_key: &str, customer_id: &str) -> crate::Result<()> {
+        (**self).remove_customer(client_key, customer_id)
+    }
+
+    fn set_limit(&self, customer_id: &str, limit: Option<i64>) -> crate::Result<()> {
+        (**self).set_limit(customer_id, limit)
+    }
+
+    fn get_limit(&self, customer_id: &str) -> crate::Result<Option<i64>> {
+        (**self).get_limit(customer_id)
+    }
+}
@@ -0,0 +1,259 @@
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS snap_b2b_token (
+    token TEXT PRIMARY KEY,
+    client_key TEXT NOT NULL,
+    issued_at INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS snap_auth_code (
+    code TEXT PRIMARY KEY,
+    client_key TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    expires_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS snap_customer_token (
+    access_token TEXT PRIMARY KEY,
+    refresh_token TEXT NOT NULL UNIQUE,
+    client_key TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    issued_at INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL,
+    refresh_expires_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS snap_customer_token_customer_id ON snap_customer_token (customer_id);
+CREATE TABLE IF NOT EXISTS snap_customer_limit (
+    customer_id TEXT PRIMARY KEY,
+    token_limit INTEGER NOT NULL
+);
+"#;
+
+const CUSTOMER_TOKEN_COLUMNS: &str =
+    "access_token, refresh_token, client_key, customer_id, issued_at, expires_at, refresh_expires_at";
+
+fn store_error(error: rusqlite::Error) -> crate::TokenError {
+    crate::TokenError::Store(error.to_string())
+}
+
+fn customer_token_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<crate::CustomerToken> {
+    Ok(crate::CustomerToken {
+        access_token: row.get(0)?,
+        refresh_token: row.get(1)?,
+        client_key: row.get(2)?,
+        customer_id: row.get(3)?,
+        issued_at: row.get(4)?,
+        expires_at: row.get(5)?,
+        refresh_expires_at: row.get(6)?,
+    })
+}
+
+pub struct Store {
+    connection: std::sync::Mutex<rusqlite::Connection>,
+}
+
+impl Store {
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open(path).map_err(store_error)?)
+    }
+
+    pub fn open_in_memory() -> crate::Result<Self> {
+        Self::from_connection(rusqlite::Connection::open_in_memory().map_err(store_error)?)
+    }
+
+    pub fn from_connection(connection: rusqlite::Connection) -> crate::Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(store_error)?;
+
+        Ok(Self {
+            connection: std::sync::Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl crate::TokenStore for Store {
+    fn insert_token(&self, token: crate::B2BAccessToken) -> crate::Result<()> {
+        let connection = self.lock();
+        connection
+            .execute(
+                "DELETE FROM snap_b2b_token WHERE expires_at <= ?1",
+                [crate::unix_now()],
+            )
+            .map_err(store_error)?;
+        connection
+            .execute(
+                "INSERT OR REPLACE INTO snap_b2b_token (token, client_key, issued_at, expires_at) VALUES (?1, \
+                 ?2, ?3, ?4)",
+                rusqlite::params![token.token, token.client_key, token.issued_at, token.expires_at],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn get_token(&self, token: &str) -> crate::Result<Option<crate::B2BAccessToken>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            "SELECT token, client_key, issued_at, expires_at FROM snap_b2b_token WHERE token = ?1",
+            [token],
+            |row| {
+                Ok(crate::B2BAccessToken {
+                    token: row.get(0)?,
+                    client_key: row.get(1)?,
+                    issued_at: row.get(2)?,
+                    expires_at: row.get(3)?,
+                })
+            },
+        ))
+        .map_err(store_error)
+    }
+
+    fn remove_token(&self, token: &str) -> crate::Result<()> {
+        self.lock()
+            .execute("DELETE FROM snap_b2b_token WHERE token = ?1", [token])
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+}
+
+impl crate::CustomerTokenStore for Store {
+    fn insert_auth_code(&self, auth_code: crate::AuthCode) -> crate::Result<()> {
+        let connection = self.lock();
+        connection
+            .execute(
+                "DELETE FROM snap_auth_code WHERE expires_at <= ?1",
+                [crate::unix_now()],
+            )
+            .map_err(store_error)?;
+        connection
+            .execute(
+                "INSERT OR REPLACE INTO snap_auth_code (code, client_key, customer_id, expires_at) VALUES (?1, \
+                 ?2, ?3, ?4)",
+                rusqlite::params![
+                    auth_code.code,
+                    auth_code.client_key,
+                    auth_code.customer_id,
+                    auth_code.expires_at
+                ],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn take_auth_code(&self, client_key: &str, code: &str) -> crate::Result<Option<crate::AuthCode>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            "DELETE FROM snap_auth_code WHERE code = ?1 AND client_key = ?2 RETURNING code, client_key, \
+             customer_id, expires_at",
+            [code, client_key],
+            |row| {
+                Ok(crate::AuthCode {
+                    code: row.get(0)?,
+                    client_key: row.get(1)?,
+                    customer_id: row.get(2)?,
+                    expires_at: row.get(3)?,
+                })
+            },
+        ))
+        .map_err(store_error)
+    }
+
+    fn insert_customer_token(&self, token: crate::CustomerToken) -> crate::Result<()> {
+        let connection = self.lock();
+        connection
+            .execute(
+                "DELETE FROM snap_customer_token WHERE refresh_expires_at <= ?1",
+                [crate::unix_now()],
+            )
+            .map_err(store_error)?;
+        connection
+            .execute(
+                &format!("INSERT INTO snap_customer_token ({CUSTOMER_TOKEN_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"),
+                rusqlite::params![
+                    token.access_token,
+                    token.refresh_token,
+                    token.client_key,
+                    token.customer_id,
+                    token.issued_at,
+                    token.expires_at,
+                    token.refresh_expires_at
+                ],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn get_customer_token(&self, access_token: &str) -> crate::Result<Option<crate::CustomerToken>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            &format!("SELECT {CUSTOMER_TOKEN_COLUMNS} FROM snap_customer_token WHERE access_token = ?1"),
+            [access_token],
+            customer_token_from_row,
+        ))
+        .map_err(store_error)
+    }
+
+    fn take_refresh_token(
+        &self,
+        client_key: &str,
+        refresh_token: &str,
+    ) -> crate::Result<Option<crate::CustomerToken>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            &format!(
+                "DELETE FROM snap_customer_token WHERE refresh_token = ?1 AND client_key = ?2 RETURNING \
+                 {CUSTOMER_TOKEN_COLUMNS}"
+            ),
+            [refresh_token, client_key],
+            customer_token_from_row,
+        ))
+        .map_err(store_error)
+    }
+
+    fn remove_customer(&self, client_key: &str, customer_id: &str) -> crate::Result<()> {
+        let connection = self.lock();
+        connection
+            .execute(
+                "DELETE FROM snap_auth_code WHERE customer_id = ?1 AND client_key = ?2",
+                [customer_id, client_key],
+            )
+            .map_err(store_error)?;
+        connection
+            .execute(
+                "DELETE FROM snap_customer_token WHERE customer_id = ?1 AND client_key = ?2",
+                [customer_id, client_key],
+            )
+            .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn set_limit(&self, customer_id: &str, limit: Option<i64>) -> crate::Result<()> {
+        let connection = self.lock();
+
+        match limit {
+            Some(limit) => connection.execute(
+                "INSERT OR REPLACE INTO snap_customer_limit (customer_id, token_limit) VALUES (?1, ?2)",
+                rusqlite::params![customer_id, limit],
+            ),
+            None => connection.execute(
+                "DELETE FROM snap_customer_limit WHERE customer_id = ?1",
+                [customer_id],
+            ),
+        }
+        .map_err(store_error)?;
+
+        Ok(())
+    }
+
+    fn get_limit(&self, customer_id: &str) -> crate::Result<Option<i64>> {
+        rusqlite::OptionalExtension::optional(self.lock().query_row(
+            "SELECT token_limit FROM snap_customer_limit WHERE customer_id = ?1",
+            [customer_id],
+            |row| row.get(0),
+        ))
+        .map_err(store_error)
+    }
+}
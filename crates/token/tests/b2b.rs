@@ -0,0 +1,89 @@
+//! B2B access token request verification.
+
+mod common;
+
+fn manager(
+    config: kamu_snap_token::B2BTokenConfig,
+) -> kamu_snap_token::B2BTokenManager<kamu_snap_token::MemoryTokenStore> {
+    kamu_snap_token::B2BTokenManager::new(kamu_snap_token::MemoryTokenStore::new(), config)
+}
+
+fn verify(
+    manager: &kamu_snap_token::B2BTokenManager<kamu_snap_token::MemoryTokenStore>,
+    timestamp: &str,
+) -> kamu_snap_response::Result<()> {
+    let signature = common::sign(common::CLIENT_KEY, timestamp);
+
+    Ok(manager.verify_request(&common::key_pair().1, common::CLIENT_KEY, timestamp, &signature)?)
+}
+
+#[test]
+fn accepts_timestamp_within_skew() {
+    let manager = manager(Default::default());
+
+    for offset in [0, -290, 290] {
+        verify(&manager, &common::timestamp(offset)).unwrap();
+    }
+}
+
+#[test]
+fn rejects_timestamp_outside_skew() {
+    let manager = manager(Default::default());
+
+    for offset in [-310, 310, -86_400] {
+        assert!(matches!(
+            verify(&manager, &common::timestamp(offset)),
+            Err(kamu_snap_response::ResponseError::Unathorized(reason)) if reason == "Invalid Timestamp"
+        ));
+    }
+}
+
+#[test]
+fn uses_configured_skew() {
+    let manager = manager(kamu_snap_token::B2BTokenConfig {
+        timestamp_skew: std::time::Duration::from_secs(30),
+        ..Default::default()
+    });
+
+    verify(&manager, &common::timestamp(-20)).unwrap();
+    assert!(verify(&manager, &common::timestamp(-60)).is_err());
+}
+
+#[test]
+fn rejects_malformed_timestamp() {
+    let manager = manager(Default::default());
+
+    assert!(matches!(
+        verify(&manager, "2025-08-09 10:00:00"),
+        Err(kamu_snap_response::ResponseError::InvalidFieldFormat(field)) if field == "X-TIMESTAMP"
+    ));
+}
+
+#[test]
+fn rejects_bad_signature() {
+    let manager = manager(Default::default());
+    let timestamp = common::timestamp(0);
+    let signature = common::sign("partner-2", &timestamp);
+
+    assert!(
+        manager
+            .verify_request(&common::key_pair().1, common::CLIENT_KEY, &timestamp, &signature)
+            .is_err()
+    );
+}
+
+#[test]
+fn authorize_issues_valid_token() {
+    let manager = manager(Default::default());
+    let timestamp = common::timestamp(0);
+    let signature = common::sign(common::CLIENT_KEY, &timestamp);
+    let access_token = manager
+        .authorize(&common::key_pair().1, common::CLIENT_KEY, &timestamp, &signature)
+        .unwrap();
+
+    let validated = manager
+        .validate(&format!("Bearer {}", access_token.token()))
+        .unwrap();
+    assert_eq!(validated.client_key(), common::CLIENT_KEY);
+    assert_eq!(access_token.to_response().expires_in, "900");
+}
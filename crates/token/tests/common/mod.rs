@@ -0,0 +1,32 @@
+//! Fixtures shared by the token tests.
+
+pub const CLIENT_KEY: &str = "partner-1";
+
+pub type KeyPair = (
+    kamu_snap_crypto::AsymmetricCryptoSigner,
+    kamu_snap_crypto::AsymmetricCryptoVerifier,
+);
+
+/// Key pair of [`CLIENT_KEY`].
+pub fn key_pair() -> KeyPair {
+    (
+        kamu_snap_crypto::AsymmetricCryptoSigner::create(kamu_snap_testing::PRIVATE_KEY).unwrap(),
+        kamu_snap_crypto::AsymmetricCryptoVerifier::create(kamu_snap_testing::PUBLIC_KEY).unwrap(),
+    )
+}
+
+/// `X-TIMESTAMP` value `offset_secs` away from now, in WIB.
+pub fn timestamp(offset_secs: i64) -> String {
+    (chrono::Utc::now() + chrono::Duration::seconds(offset_secs))
+        .with_timezone(&kamu_snap_response::header::wib())
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+/// `X-SIGNATURE` of an access token request.
+pub fn sign(client_key: &str, timestamp: &str) -> String {
+    key_pair()
+        .0
+        .sign_as_base64(kamu_snap_crypto::string_to_sign::access_token(
+            client_key, timestamp,
+        ))
+}
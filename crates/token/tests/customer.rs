@@ -0,0 +1,150 @@
+//! B2B2C customer token lifecycle against every store.
+
+const PARTNER: &str = "partner-1";
+const OTHER_PARTNER: &str = "partner-2";
+const CUSTOMER: &str = "customer-1";
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {token}")
+}
+
+fn lifecycle<S: kamu_snap_token::CustomerTokenStore>(store: S) {
+    let manager = kamu_snap_token::CustomerTokenManager::new(store, Default::default());
+
+    let auth_code = manager.issue_auth_code(PARTNER, CUSTOMER).unwrap();
+    let token = manager.exchange_auth_code(PARTNER, auth_code.code()).unwrap();
+    assert_eq!(token.customer_id(), CUSTOMER);
+    assert!(matches!(
+        manager.exchange_auth_code(PARTNER, auth_code.code()),
+        Err(kamu_snap_token::TokenError::InvalidAuthCode)
+    ));
+
+    let validated = manager.validate(PARTNER, &bearer(token.access_token())).unwrap();
+    assert_eq!(validated.customer_id(), CUSTOMER);
+    assert!(matches!(
+        manager.validate(OTHER_PARTNER, &bearer(token.access_token())),
+        Err(kamu_snap_token::TokenError::InvalidCustomerToken)
+    ));
+
+    let refreshed = manager.refresh(PARTNER, token.refresh_token()).unwrap();
+    assert_ne!(refreshed.access_token(), token.access_token());
+    assert!(matches!(
+        manager.validate(PARTNER, &bearer(token.access_token())),
+        Err(kamu_snap_token::TokenError::CustomerTokenNotFound)
+    ));
+    assert!(matches!(
+        manager.refresh(PARTNER, token.refresh_token()),
+        Err(kamu_snap_token::TokenError::CustomerTokenNotFound)
+    ));
+
+    manager.set_limit(CUSTOMER, Some(100_000)).unwrap();
+    manager.check_limit(&refreshed, 100_000).unwrap();
+    assert!(matches!(
+        manager.check_limit(&refreshed, 100_001),
+        Err(kamu_snap_token::TokenError::TokenLimitExceeded)
+    ));
+
+    manager.revoke(PARTNER, CUSTOMER).unwrap();
+    assert!(matches!(
+        manager.validate(PARTNER, &bearer(refreshed.access_token())),
+        Err(kamu_snap_token::TokenError::CustomerTokenNotFound)
+    ));
+}
+
+/// A partner presenting another partner's code or refresh token must not
+/// consume it.
+fn ownership<S: kamu_snap_token::CustomerTokenStore>(store: S) {
+    let manager = kamu_snap_token::CustomerTokenManager::new(store, Default::default());
+
+    let auth_code = manager.issue_auth_code(PARTNER, CUSTOMER).unwrap();
+    assert!(matches!(
+        manager.exchange_auth_code(OTHER_PARTNER, auth_code.code()),
+        Err(kamu_snap_token::TokenError::InvalidAuthCode)
+    ));
+    let token = manager.exchange_auth_code(PARTNER, auth_code.code()).unwrap();
+
+    assert!(matches!(
+        manager.refresh(OTHER_PARTNER, token.refresh_token()),
+        Err(kamu_snap_token::TokenError::CustomerTokenNotFound)
+    ));
+    manager.validate(PARTNER, &bearer(token.access_token())).unwrap();
+    manager.refresh(PARTNER, token.refresh_token()).unwrap();
+}
+
+/// Unbinding the customer from one partner keeps its binding with another.
+fn revoke_per_partner<S: kamu_snap_token::CustomerTokenStore>(store: S) {
+    let manager = kamu_snap_token::CustomerTokenManager::new(store, Default::default());
+
+    let auth_code = manager.issue_auth_code(PARTNER, CUSTOMER).unwrap();
+    let token = manager.exchange_auth_code(PARTNER, auth_code.code()).unwrap();
+    let other_auth_code = manager.issue_auth_code(OTHER_PARTNER, CUSTOMER).unwrap();
+    let other_token = manager
+        .exchange_auth_code(OTHER_PARTNER, other_auth_code.code())
+        .unwrap();
+    let pending_auth_code = manager.issue_auth_code(OTHER_PARTNER, CUSTOMER).unwrap();
+
+    manager.revoke(PARTNER, CUSTOMER).unwrap();
+    assert!(matches!(
+        manager.validate(PARTNER, &bearer(token.access_token())),
+        Err(kamu_snap_token::TokenError::CustomerTokenNotFound)
+    ));
+    manager
+        .validate(OTHER_PARTNER, &bearer(other_token.access_token()))
+        .unwrap();
+    manager
+        .exchange_auth_code(OTHER_PARTNER, pending_auth_code.code())
+        .unwrap();
+}
+
+fn expired_auth_code<S: kamu_snap_token::CustomerTokenStore>(store: S) {
+    let manager = kamu_snap_token::CustomerTokenManager::new(
+        store,
+        kamu_snap_token::CustomerTokenConfig {
+            auth_code_ttl: std::time::Duration::ZERO,
+            ..Default::default()
+        },
+    );
+
+    let auth_code = manager.issue_auth_code(PARTNER, CUSTOMER).unwrap();
+    assert!(matches!(
+        manager.exchange_auth_code(PARTNER, auth_code.code()),
+        Err(kamu_snap_token::TokenError::InvalidAuthCode)
+    ));
+}
+
+fn expired_access_token<S: kamu_snap_token::CustomerTokenStore>(store: S) {
+    let manager = kamu_snap_token::CustomerTokenManager::new(
+        store,
+        kamu_snap_token::CustomerTokenConfig {
+            access_token_ttl: std::time::Duration::ZERO,
+            ..Default::default()
+        },
+    );
+
+    let auth_code = manager.issue_auth_code(PARTNER, CUSTOMER).unwrap();
+    let token = manager.exchange_auth_code(PARTNER, auth_code.code()).unwrap();
+    assert!(matches!(
+        manager.validate(PARTNER, &bearer(token.access_token())),
+        Err(kamu_snap_token::TokenError::InvalidCustomerToken)
+    ));
+    manager.refresh(PARTNER, token.refresh_token()).unwrap();
+}
+
+#[test]
+fn memory_store() {
+    lifecycle(kamu_snap_token::MemoryTokenStore::new());
+    ownership(kamu_snap_token::MemoryTokenStore::new());
+    revoke_per_partner(kamu_snap_token::MemoryTokenStore::new());
+    expired_auth_code(kamu_snap_token::MemoryTokenStore::new());
+    expired_access_token(kamu_snap_token::MemoryTokenStore::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_store() {
+    lifecycle(kamu_snap_token::SqliteTokenStore::open_in_memory().unwrap());
+    ownership(kamu_snap_token::SqliteTokenStore::open_in_memory().unwrap());
+    revoke_per_partner(kamu_snap_token::SqliteTokenStore::open_in_memory().unwrap());
+    expired_auth_code(kamu_snap_token::SqliteTokenStore::open_in_memory().unwrap());
+    expired_access_token(kamu_snap_token::SqliteTokenStore::open_in_memory().unwrap());
+}
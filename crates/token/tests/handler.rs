@@ -0,0 +1,194 @@
+//! Access token endpoints mounted in an actix-web test service.
+
+mod common;
+
+type Keys = std::collections::HashMap<String, kamu_snap_crypto::AsymmetricCryptoVerifier>;
+type CustomerTokenManager = kamu_snap_token::CustomerTokenManager<kamu_snap_token::MemoryTokenStore>;
+
+const B2B_BODY: &str = r#"{"grantType":"client_credentials"}"#;
+
+/// Both endpoints with their managers and the key of [`common::CLIENT_KEY`].
+/// The customer token manager is returned to issue auth codes.
+fn setup() -> (
+    impl FnOnce(&mut actix_web::web::ServiceConfig),
+    actix_web::web::Data<CustomerTokenManager>,
+) {
+    let b2b =
+        kamu_snap_token::B2BTokenManager::new(kamu_snap_token::MemoryTokenStore::new(), Default::default());
+    let customer = actix_web::web::Data::new(kamu_snap_token::CustomerTokenManager::new(
+        kamu_snap_token::MemoryTokenStore::new(),
+        Default::default(),
+    ));
+    let keys = Keys::from([(common::CLIENT_KEY.to_owned(), common::key_pair().1)]);
+    let data = customer.clone();
+
+    let configure = move |config: &mut actix_web::web::ServiceConfig| {
+        config
+            .app_data(actix_web::web::Data::new(b2b))
+            .app_data(data)
+            .app_data(actix_web::web::Data::new(keys))
+            .configure(
+                kamu_snap_token::handler::configure::<
+                    kamu_snap_token::MemoryTokenStore,
+                    kamu_snap_token::MemoryTokenStore,
+                    Keys,
+                >,
+            );
+    };
+
+    (configure, customer)
+}
+
+fn request(
+    path: &str,
+    client_key: &str,
+    signature: Option<&str>,
+    body: &str,
+) -> actix_web::test::TestRequest {
+    let timestamp = common::timestamp(0);
+    let request = actix_web::test::TestRequest::post()
+        .uri(path)
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header((kamu_snap_response::header::X_TIMESTAMP, timestamp.clone()))
+        .insert_header((kamu_snap_response::header::X_CLIENT_KEY, client_key))
+        .set_payload(body.to_owned());
+
+    match signature {
+        Some(signature) => request.insert_header((kamu_snap_response::header::X_SIGNATURE, signature)),
+        None => request.insert_header((
+            kamu_snap_response::header::X_SIGNATURE,
+            common::sign(client_key, &timestamp),
+        )),
+    }
+}
+
+async fn call(
+    configure: impl FnOnce(&mut actix_web::web::ServiceConfig),
+    request: actix_web::test::TestRequest,
+) -> (u16, serde_json::Value) {
+    let app = actix_web::test::init_service(actix_web::App::new().configure(configure)).await;
+    let response = actix_web::test::call_service(&app, request.to_request()).await;
+    let status = response.status().as_u16();
+
+    (status, actix_web::test::read_body_json(response).await)
+}
+
+#[actix_web::test]
+async fn b2b_issues_token() {
+    let (configure, _) = setup();
+    let (status, body) = call(
+        configure,
+        request(
+            kamu_snap_token::handler::PATH_B2B,
+            common::CLIENT_KEY,
+            None,
+            B2B_BODY,
+        ),
+    )
+    .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["responseCode"], "2007300");
+    assert_eq!(body["tokenType"], "Bearer");
+    assert_eq!(body["expiresIn"], "900");
+    assert!(body["accessToken"].as_str().is_some_and(|x| !x.is_empty()));
+}
+
+#[actix_web::test]
+async fn b2b_rejects_missing_header() {
+    let (configure, _) = setup();
+    let request = actix_web::test::TestRequest::post()
+        .uri(kamu_snap_token::handler::PATH_B2B)
+        .insert_header((kamu_snap_response::header::X_CLIENT_KEY, common::CLIENT_KEY))
+        .insert_header((kamu_snap_response::header::X_SIGNATURE, "c2lnbmF0dXJl"))
+        .set_payload(B2B_BODY);
+    let (status, body) = call(configure, request).await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["responseCode"], "4007302");
+    assert_eq!(body["responseMessage"], "Invalid Mandatory Field X-TIMESTAMP");
+}
+
+#[actix_web::test]
+async fn b2b_rejects_bad_signature() {
+    let (configure, _) = setup();
+    let signature = common::sign("partner-2", &common::timestamp(0));
+    let (status, body) = call(
+        configure,
+        request(
+            kamu_snap_token::handler::PATH_B2B,
+            common::CLIENT_KEY,
+            Some(&signature),
+            B2B_BODY,
+        ),
+    )
+    .await;
+
+    assert_eq!(status, 401);
+    assert_eq!(body["responseCode"], "4017300");
+    assert!(body.get("accessToken").is_none());
+}
+
+#[actix_web::test]
+async fn b2b_rejects_unknown_client() {
+    let (configure, _) = setup();
+    let (status, body) = call(
+        configure,
+        request(kamu_snap_token::handler::PATH_B2B, "partner-2", None, B2B_BODY),
+    )
+    .await;
+
+    assert_eq!(status, 401);
+    assert_eq!(body["responseCode"], "4017300");
+    assert_eq!(body["responseMessage"], "Unauthorized. Unknown Client");
+}
+
+#[actix_web::test]
+async fn b2b2c_issues_customer_token() {
+    let (configure, customer) = setup();
+    let auth_code = customer
+        .issue_auth_code(common::CLIENT_KEY, "customer-1")
+        .unwrap();
+    let body = format!(
+        r#"{{"grantType":"AUTHORIZATION_CODE","authCode":"{}"}}"#,
+        auth_code.code()
+    );
+    let (status, body) = call(
+        configure,
+        request(
+            kamu_snap_token::handler::PATH_B2B2C,
+            common::CLIENT_KEY,
+            None,
+            &body,
+        ),
+    )
+    .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["responseCode"], "2007400");
+    assert_eq!(body["tokenType"], "Bearer");
+
+    let access_token = body["accessToken"].as_str().unwrap();
+    let validated = customer
+        .validate(common::CLIENT_KEY, &format!("Bearer {access_token}"))
+        .unwrap();
+    assert_eq!(validated.customer_id(), "customer-1");
+}
+
+#[actix_web::test]
+async fn b2b2c_rejects_missing_grant_type() {
+    let (configure, _) = setup();
+    let (status, body) = call(
+        configure,
+        request(
+            kamu_snap_token::handler::PATH_B2B2C,
+            common::CLIENT_KEY,
+            None,
+            r#"{"authCode":"x"}"#,
+        ),
+    )
+    .await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["responseCode"], "4007402");
+}